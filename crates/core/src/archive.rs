@@ -0,0 +1,152 @@
+//! Minimal ZIP writer for downloadable bundles.
+//!
+//! Entries are stored uncompressed: bundles mostly hold PNGs and other
+//! already-compressed data, and storing keeps the writer dependency-free.
+
+/// CRC-32 (IEEE) lookup table.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 checksum as used by ZIP and PNG.
+#[must_use]
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// One file in an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Path inside the archive, using `/` as separator.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl ArchiveEntry {
+    #[must_use]
+    pub fn new(name: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            data: data.into(),
+        }
+    }
+}
+
+/// DOS date for 1980-01-01, the earliest ZIP timestamp. A fixed date keeps
+/// archives reproducible.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+fn u16_len(n: usize) -> u16 {
+    u16::try_from(n).unwrap_or(u16::MAX)
+}
+
+fn u32_len(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+/// Write entries into a ZIP archive without compression.
+#[must_use]
+pub fn zip(entries: &[ArchiveEntry]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for entry in entries {
+        let offset = u32_len(out.len());
+        let crc = crc32(&entry.data);
+        let size = u32_len(entry.data.len());
+        let name = entry.name.as_bytes();
+
+        // Fields shared by the local and central headers, from "version
+        // needed" to "extra field length"
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        common.extend_from_slice(&0x0800u16.to_le_bytes()); // UTF-8 names
+        common.extend_from_slice(&0u16.to_le_bytes()); // stored
+        common.extend_from_slice(&0u16.to_le_bytes()); // time
+        common.extend_from_slice(&DOS_DATE.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes()); // compressed
+        common.extend_from_slice(&size.to_le_bytes()); // uncompressed
+        common.extend_from_slice(&u16_len(name.len()).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes()); // extra length
+
+        out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        out.extend_from_slice(&common);
+        out.extend_from_slice(name);
+        out.extend_from_slice(&entry.data);
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&common);
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name);
+    }
+
+    let central_offset = u32_len(out.len());
+    let count = u16_len(entries.len());
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // disk number
+    out.extend_from_slice(&0u16.to_le_bytes()); // disk with central directory
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&u32_len(central.len()).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_reference() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn writes_stored_entries() {
+        let archive = zip(&[
+            ArchiveEntry::new("a.txt", "hello"),
+            ArchiveEntry::new("dir/b.txt", "world!"),
+        ]);
+        // Local header signature, then name and data
+        assert_eq!(&archive[..4], b"PK\x03\x04");
+        assert_eq!(&archive[30..35], b"a.txt");
+        assert_eq!(&archive[35..40], b"hello");
+
+        // End of central directory record
+        let end = &archive[archive.len() - 22..];
+        assert_eq!(&end[..4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2);
+        let central_offset = u32::from_le_bytes([end[16], end[17], end[18], end[19]]) as usize;
+        assert_eq!(&archive[central_offset..central_offset + 4], b"PK\x01\x02");
+        // Second local header starts right after the first entry
+        assert_eq!(&archive[40..44], b"PK\x03\x04");
+    }
+}
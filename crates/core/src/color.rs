@@ -0,0 +1,691 @@
+//! Colour analysis and rewriting for icon bodies.
+//!
+//! Finds every paint used by a body (presentation attributes, `style`
+//! attributes and `<style>` elements), classifies the icon as monotone or
+//! multicolour and rewrites colours, most commonly to `currentColor`.
+//! Paints inside masks and clip paths shape the icon rather than colour it,
+//! so they are neither counted nor rewritten.
+
+use std::fmt;
+
+use crate::tree::{self, Node, ParseError};
+use crate::types::ResolvedIcon;
+
+/// Attributes and CSS properties that take a colour or paint.
+const COLOR_PROPERTIES: &[&str] = &[
+    "fill",
+    "stroke",
+    "stop-color",
+    "flood-color",
+    "lighting-color",
+    "color",
+];
+
+/// Elements whose paints only shape or cut out other content, so their
+/// colours are not the icon's.
+const NON_RENDERED: &[&str] = &["mask", "clipPath"];
+
+/// Elements that are filled with the initial black unless told otherwise.
+const FILLED_SHAPES: &[&str] = &[
+    "path", "rect", "circle", "ellipse", "polygon", "polyline", "text",
+];
+
+/// An sRGB colour with alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+
+    /// An opaque colour.
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// Parse a hex, `rgb()`/`rgba()` or named CSS colour.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        if let Some(hex) = value.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+        if let Some(args) = value
+            .strip_prefix("rgba(")
+            .or_else(|| value.strip_prefix("rgb("))
+        {
+            return parse_rgb_function(args.strip_suffix(')')?);
+        }
+        NAMED_COLORS
+            .iter()
+            .find(|(name, _)| *name == value)
+            .and_then(|(_, hex)| parse_hex(hex))
+    }
+
+    /// Shortest hex notation, e.g. `#000` or `#12345680`.
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        let channels = [self.r, self.g, self.b, self.a];
+        let short = channels.iter().all(|c| c >> 4 == c & 0x0f);
+        let channels = if self.a == 255 {
+            &channels[..3]
+        } else {
+            &channels[..]
+        };
+        let digits: String = channels
+            .iter()
+            .map(|c| {
+                if short {
+                    format!("{:x}", c & 0x0f)
+                } else {
+                    format!("{c:02x}")
+                }
+            })
+            .collect();
+        format!("#{digits}")
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok();
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let expand = |i: usize| digit(i).map(|d| d * 17);
+            Some(Color {
+                r: expand(0)?,
+                g: expand(1)?,
+                b: expand(2)?,
+                a: if hex.len() == 4 { expand(3)? } else { 255 },
+            })
+        }
+        6 | 8 => Some(Color {
+            r: pair(0)?,
+            g: pair(2)?,
+            b: pair(4)?,
+            a: if hex.len() == 8 { pair(6)? } else { 255 },
+        }),
+        _ => None,
+    }
+}
+
+fn parse_rgb_function(args: &str) -> Option<Color> {
+    let parts: Vec<&str> = args
+        .split([',', ' ', '/'])
+        .filter(|s| !s.is_empty())
+        .collect();
+    let channel = |s: &str, scale: f64| -> Option<u8> {
+        let value = match s.strip_suffix('%') {
+            Some(p) => p.parse::<f64>().ok()? * scale / 100.0,
+            None => s.parse::<f64>().ok()?,
+        };
+        Some(clamp_channel(value))
+    };
+    let (r, g, b) = match parts.as_slice() {
+        [r, g, b] | [r, g, b, _] => (channel(r, 255.0)?, channel(g, 255.0)?, channel(b, 255.0)?),
+        _ => return None,
+    };
+    let a = match parts.get(3) {
+        Some(a) if a.ends_with('%') => channel(a, 255.0)?,
+        Some(a) => clamp_channel(a.parse::<f64>().ok()? * 255.0),
+        None => 255,
+    };
+    Some(Color { r, g, b, a })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn clamp_channel(value: f64) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+/// A parsed paint value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Paint {
+    None,
+    CurrentColor,
+    /// `inherit`, `initial`, `unset` and friends.
+    Inherit,
+    Color(Color),
+    /// A paint server reference such as `url(#gradient)`.
+    Url(String),
+    /// A value that is not understood, such as `hsl()`.
+    Other(String),
+}
+
+impl Paint {
+    /// Parse an attribute or CSS value.
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let trimmed = value.trim();
+        let lower = trimmed.to_ascii_lowercase();
+        match lower.as_str() {
+            "none" | "transparent" => Self::None,
+            "currentcolor" => Self::CurrentColor,
+            "inherit" | "initial" | "unset" | "revert" | "context-fill" | "context-stroke" => {
+                Self::Inherit
+            }
+            _ if lower.starts_with("url(") => Self::Url(trimmed.to_string()),
+            _ => {
+                Color::parse(trimmed).map_or_else(|| Self::Other(trimmed.to_string()), Self::Color)
+            }
+        }
+    }
+
+    /// Whether this paint draws a concrete colour chosen by the icon.
+    #[must_use]
+    pub fn is_concrete(&self) -> bool {
+        matches!(self, Self::Color(_) | Self::Url(_) | Self::Other(_))
+    }
+}
+
+/// Whether an icon can be tinted with a single colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorKind {
+    /// Uses at most one colour and no gradients or patterns.
+    Monotone,
+    /// Uses several colours or paint servers.
+    Multicolor,
+}
+
+/// Every paint found in a body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColorAnalysis {
+    /// Distinct concrete paints in order of first use.
+    pub paints: Vec<Paint>,
+    pub uses_current_color: bool,
+    /// Some shape sets no `fill`, nor does any ancestor, so it is painted
+    /// with the root's fill: initially black, or `currentColor` once a
+    /// generator sets that on `<svg>`.
+    pub uses_root_fill: bool,
+}
+
+impl ColorAnalysis {
+    /// Distinct parsed colours, ignoring paint servers and unknown values.
+    #[must_use]
+    pub fn colors(&self) -> Vec<Color> {
+        self.paints
+            .iter()
+            .filter_map(|p| match p {
+                Paint::Color(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Classify the icon.
+    #[must_use]
+    pub fn kind(&self) -> ColorKind {
+        let has_url = self.paints.iter().any(|p| matches!(p, Paint::Url(_)));
+        // The root fill is one more colour unless it can be the black or
+        // `currentColor` already in use
+        let root_fill_shared =
+            self.uses_current_color || self.paints.contains(&Paint::Color(Color::BLACK));
+        let concrete = self.paints.len()
+            + usize::from(self.uses_current_color)
+            + usize::from(self.uses_root_fill && !root_fill_shared);
+        if has_url || concrete > 1 {
+            ColorKind::Multicolor
+        } else {
+            ColorKind::Monotone
+        }
+    }
+}
+
+/// Collect every paint used in a body.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn analyze(body: &str) -> Result<ColorAnalysis, ParseError> {
+    let mut nodes = tree::parse(body)?;
+    let mut analysis = ColorAnalysis::default();
+    visit_paints(&mut nodes, &mut |paint| {
+        if paint == &Paint::CurrentColor {
+            analysis.uses_current_color = true;
+        } else if paint.is_concrete() && !analysis.paints.contains(paint) {
+            analysis.paints.push(paint.clone());
+        }
+        None
+    });
+    analysis.uses_root_fill = uses_root_fill(&nodes, false);
+    Ok(analysis)
+}
+
+/// Whether a shape is left to the root's fill. `filled` is whether an
+/// ancestor sets `fill`; stylesheet rules are not considered.
+fn uses_root_fill(nodes: &[Node], filled: bool) -> bool {
+    nodes.iter().any(|node| {
+        let Node::Element(el) = node else {
+            return false;
+        };
+        if NON_RENDERED.contains(&el.name.as_str()) {
+            return false;
+        }
+        let filled = filled
+            || el.attr("fill").is_some()
+            || el.attr("style").is_some_and(|style| {
+                style.split(';').any(|declaration| {
+                    declaration
+                        .split_once(':')
+                        .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case("fill"))
+                })
+            });
+        (!filled && FILLED_SHAPES.contains(&el.name.as_str()))
+            || uses_root_fill(&el.children, filled)
+    })
+}
+
+/// Rewrite paints in a body. `replace` returns the new value for a paint,
+/// or `None` to keep it.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn replace_paints(
+    body: &str,
+    replace: &mut impl FnMut(&Paint) -> Option<String>,
+) -> Result<String, ParseError> {
+    let mut nodes = tree::parse(body)?;
+    visit_paints(&mut nodes, replace);
+    Ok(tree::to_markup(&nodes))
+}
+
+/// Replace each listed colour with its paired value.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn recolor(body: &str, replacements: &[(Color, String)]) -> Result<String, ParseError> {
+    replace_paints(body, &mut |paint| match paint {
+        Paint::Color(color) => replacements
+            .iter()
+            .find(|(from, _)| from == color)
+            .map(|(_, to)| to.clone()),
+        _ => None,
+    })
+}
+
+/// Replace one colour (usually [`Color::BLACK`]) with `currentColor`.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn to_current_color(body: &str, color: Color) -> Result<String, ParseError> {
+    recolor(body, &[(color, "currentColor".to_string())])
+}
+
+/// Prepare an icon for generators that set `fill="currentColor"` on the root.
+///
+/// A monotone icon has its single hard-coded colour turned into
+/// `currentColor` so it follows the surrounding text colour. Returns the
+/// icon and whether it keeps its own colours (a palette collection or a
+/// multicolour body), in which case the root fill should be left out.
+#[must_use]
+pub fn prepare_for_tinting(icon: &ResolvedIcon) -> (ResolvedIcon, bool) {
+    if icon.palette {
+        return (icon.clone(), true);
+    }
+    let Ok(analysis) = analyze(&icon.body) else {
+        return (icon.clone(), false);
+    };
+    match (analysis.kind(), analysis.colors().as_slice()) {
+        (ColorKind::Multicolor, _) => (icon.clone(), true),
+        (ColorKind::Monotone, [color]) => {
+            let body = to_current_color(&icon.body, *color).unwrap_or_else(|_| icon.body.clone());
+            (
+                ResolvedIcon {
+                    body,
+                    ..icon.clone()
+                },
+                false,
+            )
+        }
+        (ColorKind::Monotone, _) => (icon.clone(), false),
+    }
+}
+
+/// Call `f` for every paint in the tree, replacing values it returns.
+/// Masks and clip paths are skipped.
+fn visit_paints(nodes: &mut [Node], f: &mut impl FnMut(&Paint) -> Option<String>) {
+    for node in nodes {
+        let Node::Element(el) = node else { continue };
+        if NON_RENDERED.contains(&el.name.as_str()) {
+            continue;
+        }
+        for attr in &mut el.attributes {
+            if COLOR_PROPERTIES.contains(&attr.name.as_str()) {
+                if let Some(new) = f(&Paint::parse(&attr.value)) {
+                    attr.value = new;
+                }
+            } else if attr.name == "style" {
+                attr.value = rewrite_declarations(&attr.value, f);
+            }
+        }
+        if el.name == "style" {
+            for child in &mut el.children {
+                if let Node::Text(css) | Node::CData(css) = child {
+                    *css = rewrite_stylesheet(css, f);
+                }
+            }
+        } else {
+            visit_paints(&mut el.children, f);
+        }
+    }
+}
+
+/// Rewrite colour declarations inside every `{ ... }` block of a stylesheet.
+fn rewrite_stylesheet(css: &str, f: &mut impl FnMut(&Paint) -> Option<String>) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..=open]);
+        rest = &rest[open + 1..];
+        let close = rest.find('}').unwrap_or(rest.len());
+        out.push_str(&rewrite_declarations(&rest[..close], f));
+        rest = &rest[close..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rewrite colour declarations in a `prop: value; ...` list.
+fn rewrite_declarations(css: &str, f: &mut impl FnMut(&Paint) -> Option<String>) -> String {
+    css.split(';')
+        .map(|declaration| {
+            let Some((name, value)) = declaration.split_once(':') else {
+                return declaration.to_string();
+            };
+            let property = name.trim().to_ascii_lowercase();
+            if !COLOR_PROPERTIES.contains(&property.as_str()) {
+                return declaration.to_string();
+            }
+            let (value, important) = match value.trim().strip_suffix("!important") {
+                Some(v) => (v.trim(), "!important"),
+                None => (value.trim(), ""),
+            };
+            match f(&Paint::parse(value)) {
+                Some(new) => format!("{name}:{new}{important}"),
+                None => declaration.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// CSS named colours.
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("aliceblue", "f0f8ff"),
+    ("antiquewhite", "faebd7"),
+    ("aqua", "00ffff"),
+    ("aquamarine", "7fffd4"),
+    ("azure", "f0ffff"),
+    ("beige", "f5f5dc"),
+    ("bisque", "ffe4c4"),
+    ("black", "000000"),
+    ("blanchedalmond", "ffebcd"),
+    ("blue", "0000ff"),
+    ("blueviolet", "8a2be2"),
+    ("brown", "a52a2a"),
+    ("burlywood", "deb887"),
+    ("cadetblue", "5f9ea0"),
+    ("chartreuse", "7fff00"),
+    ("chocolate", "d2691e"),
+    ("coral", "ff7f50"),
+    ("cornflowerblue", "6495ed"),
+    ("cornsilk", "fff8dc"),
+    ("crimson", "dc143c"),
+    ("cyan", "00ffff"),
+    ("darkblue", "00008b"),
+    ("darkcyan", "008b8b"),
+    ("darkgoldenrod", "b8860b"),
+    ("darkgray", "a9a9a9"),
+    ("darkgreen", "006400"),
+    ("darkgrey", "a9a9a9"),
+    ("darkkhaki", "bdb76b"),
+    ("darkmagenta", "8b008b"),
+    ("darkolivegreen", "556b2f"),
+    ("darkorange", "ff8c00"),
+    ("darkorchid", "9932cc"),
+    ("darkred", "8b0000"),
+    ("darksalmon", "e9967a"),
+    ("darkseagreen", "8fbc8f"),
+    ("darkslateblue", "483d8b"),
+    ("darkslategray", "2f4f4f"),
+    ("darkslategrey", "2f4f4f"),
+    ("darkturquoise", "00ced1"),
+    ("darkviolet", "9400d3"),
+    ("deeppink", "ff1493"),
+    ("deepskyblue", "00bfff"),
+    ("dimgray", "696969"),
+    ("dimgrey", "696969"),
+    ("dodgerblue", "1e90ff"),
+    ("firebrick", "b22222"),
+    ("floralwhite", "fffaf0"),
+    ("forestgreen", "228b22"),
+    ("fuchsia", "ff00ff"),
+    ("gainsboro", "dcdcdc"),
+    ("ghostwhite", "f8f8ff"),
+    ("gold", "ffd700"),
+    ("goldenrod", "daa520"),
+    ("gray", "808080"),
+    ("green", "008000"),
+    ("greenyellow", "adff2f"),
+    ("grey", "808080"),
+    ("honeydew", "f0fff0"),
+    ("hotpink", "ff69b4"),
+    ("indianred", "cd5c5c"),
+    ("indigo", "4b0082"),
+    ("ivory", "fffff0"),
+    ("khaki", "f0e68c"),
+    ("lavender", "e6e6fa"),
+    ("lavenderblush", "fff0f5"),
+    ("lawngreen", "7cfc00"),
+    ("lemonchiffon", "fffacd"),
+    ("lightblue", "add8e6"),
+    ("lightcoral", "f08080"),
+    ("lightcyan", "e0ffff"),
+    ("lightgoldenrodyellow", "fafad2"),
+    ("lightgray", "d3d3d3"),
+    ("lightgreen", "90ee90"),
+    ("lightgrey", "d3d3d3"),
+    ("lightpink", "ffb6c1"),
+    ("lightsalmon", "ffa07a"),
+    ("lightseagreen", "20b2aa"),
+    ("lightskyblue", "87cefa"),
+    ("lightslategray", "778899"),
+    ("lightslategrey", "778899"),
+    ("lightsteelblue", "b0c4de"),
+    ("lightyellow", "ffffe0"),
+    ("lime", "00ff00"),
+    ("limegreen", "32cd32"),
+    ("linen", "faf0e6"),
+    ("magenta", "ff00ff"),
+    ("maroon", "800000"),
+    ("mediumaquamarine", "66cdaa"),
+    ("mediumblue", "0000cd"),
+    ("mediumorchid", "ba55d3"),
+    ("mediumpurple", "9370db"),
+    ("mediumseagreen", "3cb371"),
+    ("mediumslateblue", "7b68ee"),
+    ("mediumspringgreen", "00fa9a"),
+    ("mediumturquoise", "48d1cc"),
+    ("mediumvioletred", "c71585"),
+    ("midnightblue", "191970"),
+    ("mintcream", "f5fffa"),
+    ("mistyrose", "ffe4e1"),
+    ("moccasin", "ffe4b5"),
+    ("navajowhite", "ffdead"),
+    ("navy", "000080"),
+    ("oldlace", "fdf5e6"),
+    ("olive", "808000"),
+    ("olivedrab", "6b8e23"),
+    ("orange", "ffa500"),
+    ("orangered", "ff4500"),
+    ("orchid", "da70d6"),
+    ("palegoldenrod", "eee8aa"),
+    ("palegreen", "98fb98"),
+    ("paleturquoise", "afeeee"),
+    ("palevioletred", "db7093"),
+    ("papayawhip", "ffefd5"),
+    ("peachpuff", "ffdab9"),
+    ("peru", "cd853f"),
+    ("pink", "ffc0cb"),
+    ("plum", "dda0dd"),
+    ("powderblue", "b0e0e6"),
+    ("purple", "800080"),
+    ("rebeccapurple", "663399"),
+    ("red", "ff0000"),
+    ("rosybrown", "bc8f8f"),
+    ("royalblue", "4169e1"),
+    ("saddlebrown", "8b4513"),
+    ("salmon", "fa8072"),
+    ("sandybrown", "f4a460"),
+    ("seagreen", "2e8b57"),
+    ("seashell", "fff5ee"),
+    ("sienna", "a0522d"),
+    ("silver", "c0c0c0"),
+    ("skyblue", "87ceeb"),
+    ("slateblue", "6a5acd"),
+    ("slategray", "708090"),
+    ("slategrey", "708090"),
+    ("snow", "fffafa"),
+    ("springgreen", "00ff7f"),
+    ("steelblue", "4682b4"),
+    ("tan", "d2b48c"),
+    ("teal", "008080"),
+    ("thistle", "d8bfd8"),
+    ("tomato", "ff6347"),
+    ("turquoise", "40e0d0"),
+    ("violet", "ee82ee"),
+    ("wheat", "f5deb3"),
+    ("white", "ffffff"),
+    ("whitesmoke", "f5f5f5"),
+    ("yellow", "ffff00"),
+    ("yellowgreen", "9acd32"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_colour_notations() {
+        assert_eq!(Color::parse("#000"), Some(Color::BLACK));
+        assert_eq!(Color::parse("Black"), Some(Color::BLACK));
+        assert_eq!(Color::parse("rgb(0, 0, 0)"), Some(Color::BLACK));
+        assert_eq!(
+            Color::parse("rgba(255 0 0 / 50%)"),
+            Some(Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 128
+            })
+        );
+        assert_eq!(Color::parse("#1234"), Color::parse("#11223344"));
+        assert_eq!(Color::parse("#12345"), None);
+        assert_eq!(Color::parse("hsl(0 0% 0%)"), None);
+        assert_eq!(Color::rgb(0x12, 0x34, 0x56).to_hex(), "#123456");
+        assert_eq!(Color::WHITE.to_hex(), "#fff");
+    }
+
+    #[test]
+    fn finds_colours_everywhere() {
+        let body = r##"<style>.a{fill:#f00}</style><path fill="#000" stroke="currentColor"/><stop style="stop-color: blue; opacity: .5"/><rect fill="url(#g)"/><g fill="none"/>"##;
+        let analysis = analyze(body).unwrap();
+        assert_eq!(
+            analysis.paints,
+            [
+                Paint::Color(Color::rgb(255, 0, 0)),
+                Paint::Color(Color::BLACK),
+                Paint::Color(Color::rgb(0, 0, 255)),
+                Paint::Url("url(#g)".into()),
+            ]
+        );
+        assert!(analysis.uses_current_color);
+        assert_eq!(analysis.kind(), ColorKind::Multicolor);
+    }
+
+    #[test]
+    fn classifies_monotone_icons() {
+        let kind = |body| analyze(body).unwrap().kind();
+        assert_eq!(kind(r#"<path d="M0 0"/>"#), ColorKind::Monotone);
+        assert_eq!(
+            kind(r##"<path fill="#000"/><path fill="black"/>"##),
+            ColorKind::Monotone
+        );
+        assert_eq!(
+            kind(r#"<g fill="none" stroke="currentColor"/>"#),
+            ColorKind::Monotone
+        );
+        assert_eq!(
+            kind(r##"<path fill="#000"/><path stroke="currentColor"/>"##),
+            ColorKind::Multicolor
+        );
+    }
+
+    #[test]
+    fn rewrites_colours() {
+        let body = r##"<path fill="#000" style="stroke:black !important"/><path fill="red"/>"##;
+        assert_eq!(
+            to_current_color(body, Color::BLACK).unwrap(),
+            r#"<path fill="currentColor" style="stroke:currentColor!important"/><path fill="red"/>"#
+        );
+        let recolored = recolor(body, &[(Color::rgb(255, 0, 0), "#0f0".into())]).unwrap();
+        assert!(recolored.ends_with(r##"<path fill="#0f0"/>"##));
+    }
+
+    #[test]
+    fn prepares_icons_for_tinting() {
+        let icon = |body: &str| ResolvedIcon {
+            body: body.into(),
+            ..Default::default()
+        };
+        let (mono, palette) = prepare_for_tinting(&icon(r##"<path fill="#333"/>"##));
+        assert!(!palette);
+        assert_eq!(mono.body, r#"<path fill="currentColor"/>"#);
+
+        let body = r##"<path fill="#333"/><path fill="#f00"/>"##;
+        let (multi, palette) = prepare_for_tinting(&icon(body));
+        assert!(palette);
+        assert_eq!(multi.body, body);
+
+        let palette_icon = ResolvedIcon {
+            palette: true,
+            ..icon(r##"<path fill="#333"/>"##)
+        };
+        assert!(prepare_for_tinting(&palette_icon).1);
+
+        // An unfilled shape is black, so a white cut-out is a second colour
+        let body = r##"<path d="M0 0h24v24H0z"/><path fill="#fff" d="M8 8h8v8H8z"/>"##;
+        assert_eq!(analyze(body).unwrap().kind(), ColorKind::Multicolor);
+        let (cut_out, palette) = prepare_for_tinting(&icon(body));
+        assert!(palette);
+        assert_eq!(cut_out.body, body);
+        // ...but black or `currentColor` next to it is the same colour
+        for body in [
+            r##"<path d="M0 0h1"/><path fill="#000" d="M0 0h1"/>"##,
+            r#"<path d="M0 0h1"/><path fill="currentColor" d="M0 0h1"/>"#,
+        ] {
+            assert_eq!(analyze(body).unwrap().kind(), ColorKind::Monotone, "{body}");
+        }
+
+        // A white mask is not the icon's colour and must stay white
+        let body = r##"<mask id="m"><path fill="#fff" d="M0 0h24v24H0z"/><circle r="4"/></mask><path mask="url(#m)" d="M0 0h24v24H0z"/>"##;
+        let (masked, palette) = prepare_for_tinting(&icon(body));
+        assert!(!palette);
+        assert_eq!(masked.body, body);
+        assert_eq!(analyze(body).unwrap().colors(), []);
+    }
+}
@@ -0,0 +1,266 @@
+//! Pure-CSS icons: one class per icon, drawn with `mask-image` so it follows
+//! `currentColor`, or with `background-image` for icons with their own
+//! colours. Modelled on the output of `UnoCSS` `preset-icons`.
+
+use std::fmt::Write;
+
+use crate::data_uri::{self, DataUriContext};
+use crate::sprite::{self, IdScheme};
+use crate::svg::format_number;
+use crate::types::ResolvedIcon;
+
+/// How icons are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CssMode {
+    /// Mask for monotone icons, background for palette and multicolour ones.
+    #[default]
+    Auto,
+    /// Always use the icon as a mask over `currentColor`.
+    Mask,
+    /// Always use the icon as a background image.
+    Background,
+}
+
+/// Options for [`stylesheet`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssOptions {
+    pub mode: CssMode,
+    /// Prefix of the classes, e.g. `i` for `.i-mdi-home`.
+    pub class_prefix: String,
+    /// How the part of the class after the prefix is derived.
+    pub naming: IdScheme,
+    /// Selector for each icon, where `{class}`, `{prefix}` and `{name}` are
+    /// substituted, e.g. `.{class}` or `[data-icon="{prefix}:{name}"]`.
+    pub selector: String,
+    /// Icon height in `em`. The width follows the icon's aspect ratio.
+    pub size: f64,
+}
+
+impl Default for CssOptions {
+    fn default() -> Self {
+        Self {
+            mode: CssMode::default(),
+            class_prefix: "i".to_string(),
+            naming: IdScheme::default(),
+            selector: ".{class}".to_string(),
+            size: 1.0,
+        }
+    }
+}
+
+/// One icon in a stylesheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CssIcon {
+    /// The icon as `prefix:name`.
+    pub icon: String,
+    /// Class that shows it.
+    pub class: String,
+    pub selector: String,
+    /// Whether the icon is drawn as a mask and follows `currentColor`.
+    pub mask: bool,
+}
+
+/// A generated stylesheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stylesheet {
+    pub css: String,
+    /// One entry per distinct input icon, in input order.
+    pub icons: Vec<CssIcon>,
+}
+
+fn em(n: f64) -> String {
+    format!("{}em", format_number(n))
+}
+
+/// A selector list, one selector per line.
+fn selector_list(icons: &[&CssIcon]) -> String {
+    icons
+        .iter()
+        .map(|i| i.selector.as_str())
+        .collect::<Vec<_>>()
+        .join(",\n")
+}
+
+/// Generate a stylesheet with one class per icon.
+///
+/// Rules shared by every icon, by all mask icons and by all background
+/// icons are emitted once; each icon's own rule only sets its image (and its
+/// width if it is not square).
+#[must_use]
+pub fn stylesheet(icons: &[ResolvedIcon], options: &CssOptions) -> Stylesheet {
+    let mut entries: Vec<CssIcon> = Vec::new();
+    let mut rules = String::new();
+    let mut used_classes = Vec::new();
+
+    for icon in icons {
+        let key = format!("{}:{}", icon.prefix, icon.name);
+        if entries.iter().any(|e| e.icon == key) {
+            continue;
+        }
+        let (prepared, palette) =
+            crate::color::prepare_for_tinting(&crate::svg::apply_transformations(icon));
+        let mask = match options.mode {
+            CssMode::Auto => !palette,
+            CssMode::Mask => true,
+            CssMode::Background => false,
+        };
+        let base = format!(
+            "{}-{}",
+            options.class_prefix,
+            sprite::safe_id(&options.naming.raw_id(icon))
+        );
+        let class = sprite::unique_id(&base, &mut used_classes);
+        let selector = options
+            .selector
+            .replace("{class}", &class)
+            .replace("{prefix}", &icon.prefix)
+            .replace("{name}", &icon.name);
+
+        let _ = write!(
+            rules,
+            "\n{selector} {{\n  --svg: {};\n",
+            DataUriContext::Css.quote(&data_uri::encode(
+                &crate::svg::build_svg(&prepared),
+                DataUriContext::Css
+            ))
+        );
+        if prepared.width != prepared.height {
+            let ratio = f64::from(prepared.width) / f64::from(prepared.height.max(1));
+            let _ = writeln!(rules, "  width: {};", em(options.size * ratio));
+        }
+        rules.push_str("}\n");
+
+        entries.push(CssIcon {
+            icon: key,
+            class,
+            selector,
+            mask,
+        });
+    }
+
+    let mut css = String::new();
+    if !entries.is_empty() {
+        let all: Vec<&CssIcon> = entries.iter().collect();
+        let size = em(options.size);
+        let _ = writeln!(
+            css,
+            "{} {{\n  display: inline-block;\n  width: {size};\n  height: {size};\n}}",
+            selector_list(&all)
+        );
+    }
+    let masks: Vec<&CssIcon> = entries.iter().filter(|e| e.mask).collect();
+    if !masks.is_empty() {
+        let _ = write!(
+            css,
+            "\n{} {{\n  -webkit-mask: var(--svg) no-repeat;\n  mask: var(--svg) no-repeat;\n  \
+             -webkit-mask-size: 100% 100%;\n  mask-size: 100% 100%;\n  \
+             background-color: currentColor;\n}}\n",
+            selector_list(&masks)
+        );
+    }
+    let backgrounds: Vec<&CssIcon> = entries.iter().filter(|e| !e.mask).collect();
+    if !backgrounds.is_empty() {
+        let _ = write!(
+            css,
+            "\n{} {{\n  background: var(--svg) no-repeat;\n  background-size: 100% 100%;\n  \
+             background-color: transparent;\n}}\n",
+            selector_list(&backgrounds)
+        );
+    }
+    css.push_str(&rules);
+
+    Stylesheet {
+        css,
+        icons: entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon(name: &str, body: &str, palette: bool) -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: "test".into(),
+            name: name.into(),
+            body: body.into(),
+            width: 24,
+            height: 24,
+            palette,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn picks_mask_or_background() {
+        let icons = [
+            icon("home", r#"<path d="M0 0h24v24H0z"/>"#, false),
+            icon("flag", r##"<path fill="#f00" d="M0 0h24v24H0z"/>"##, true),
+        ];
+        let sheet = stylesheet(&icons, &CssOptions::default());
+        let classes: Vec<(&str, bool)> = sheet
+            .icons
+            .iter()
+            .map(|i| (i.class.as_str(), i.mask))
+            .collect();
+        assert_eq!(classes, [("i-test-home", true), ("i-test-flag", false)]);
+
+        let css = &sheet.css;
+        assert!(css.starts_with(
+            ".i-test-home,\n.i-test-flag {\n  display: inline-block;\n  width: 1em;\n  height: 1em;\n}\n"
+        ));
+        assert!(css.contains(".i-test-home {\n  -webkit-mask: var(--svg) no-repeat;"));
+        assert!(css.contains(".i-test-flag {\n  background: var(--svg) no-repeat;"));
+        assert!(css.contains(".i-test-home {\n  --svg: url(\"data:image/svg+xml"));
+        // Each icon's image is written once
+        assert_eq!(css.matches("--svg: url(").count(), 2);
+
+        let forced = stylesheet(
+            &icons,
+            &CssOptions {
+                mode: CssMode::Mask,
+                ..Default::default()
+            },
+        );
+        assert!(forced.icons.iter().all(|i| i.mask));
+        assert!(!forced.css.contains("background: var(--svg)"));
+    }
+
+    #[test]
+    fn custom_selectors_and_sizes() {
+        let wide = ResolvedIcon {
+            width: 48,
+            ..icon("wide", r#"<path d="M0 0h48v24H0z"/>"#, false)
+        };
+        let options = CssOptions {
+            class_prefix: "icon".into(),
+            naming: IdScheme::Name,
+            selector: r#"[data-icon="{prefix}:{name}"], .{class}"#.into(),
+            size: 1.5,
+            ..Default::default()
+        };
+        let sheet = stylesheet(&[wide], &options);
+        assert_eq!(sheet.icons[0].class, "icon-wide");
+        assert!(sheet.css.contains("  width: 1.5em;\n  height: 1.5em;"));
+        assert!(sheet
+            .css
+            .contains("[data-icon=\"test:wide\"], .icon-wide {\n  --svg: "));
+        assert!(sheet.css.contains("  width: 3em;\n}"));
+    }
+
+    #[test]
+    fn mask_images_are_opaque() {
+        // A monotone icon in a fixed colour still works as a mask
+        let sheet = stylesheet(
+            &[icon(
+                "dot",
+                r##"<circle fill="#123456" cx="12" cy="12" r="6"/>"##,
+                false,
+            )],
+            &CssOptions::default(),
+        );
+        assert!(sheet.icons[0].mask);
+        assert!(!sheet.css.contains("123456"));
+        assert_eq!(stylesheet(&[], &CssOptions::default()).css, "");
+    }
+}
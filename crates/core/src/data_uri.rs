@@ -0,0 +1,325 @@
+//! Compact SVG data URIs.
+//!
+//! SVG compresses poorly in base64 and is mostly URL-safe text, so the
+//! smallest data URI percent-encodes only what the surrounding context
+//! requires (in the spirit of `mini-svg-data-uri`). Double quotes around
+//! attribute values become single quotes when no tag contains one; every
+//! other double quote, including those in text, is encoded.
+
+use std::fmt;
+
+const PREFIX: &str = "data:image/svg+xml,";
+
+/// Where a data URI is embedded. Each context has its own reserved
+/// characters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataUriContext {
+    /// Inside a double-quoted CSS `url("…")`.
+    #[default]
+    Css,
+    /// Inside a double-quoted HTML attribute such as `src="…"`, where `&`
+    /// would start a character reference.
+    HtmlAttribute,
+    /// As a Markdown link destination, `![alt](…)`, where spaces and
+    /// parentheses end the link.
+    Markdown,
+}
+
+impl DataUriContext {
+    /// Whether a printable ASCII character must be percent-encoded.
+    fn reserves(self, c: char) -> bool {
+        match c {
+            // Never valid unencoded: `%` starts an escape, `#` a fragment,
+            // and the rest are not URL code points.
+            '%' | '#' | '<' | '>' | '"' | '\\' | '^' | '`' | '{' | '|' | '}' | '[' | ']' => true,
+            '&' => self == Self::HtmlAttribute,
+            ' ' | '(' | ')' => self == Self::Markdown,
+            _ => false,
+        }
+    }
+
+    /// Wrap an encoded URI for its context: `url("…")` for CSS, `"…"` for
+    /// HTML attributes and `<…>` for Markdown.
+    #[must_use]
+    pub fn quote(self, uri: &str) -> String {
+        match self {
+            Self::Css => format!(r#"url("{uri}")"#),
+            Self::HtmlAttribute => format!(r#""{uri}""#),
+            Self::Markdown => format!("<{uri}>"),
+        }
+    }
+}
+
+/// Tracks whether a character of markup is inside a tag, skipping `>` in
+/// quoted attribute values.
+#[derive(Default)]
+struct TagScanner {
+    in_tag: bool,
+    quote: Option<char>,
+}
+
+impl TagScanner {
+    /// Advance past `c`, returning whether it belongs to a tag.
+    fn step(&mut self, c: char) -> bool {
+        let in_tag = self.in_tag || c == '<';
+        if !self.in_tag {
+            self.in_tag = c == '<';
+        } else if let Some(quote) = self.quote {
+            if c == quote {
+                self.quote = None;
+            }
+        } else {
+            match c {
+                '"' | '\'' => self.quote = Some(c),
+                '>' => self.in_tag = false,
+                _ => {}
+            }
+        }
+        in_tag
+    }
+}
+
+/// Whether attribute quotes can become single quotes: no tag contains one,
+/// so every double quote inside a tag delimits a value.
+fn swaps_quotes(svg: &str) -> bool {
+    let mut scanner = TagScanner::default();
+    !svg.chars().any(|c| scanner.step(c) && c == '\'')
+}
+
+/// Encode an SVG document as a `data:image/svg+xml,` URI for a context.
+#[must_use]
+pub fn encode(svg: &str, context: DataUriContext) -> String {
+    let swap_quotes = swaps_quotes(svg);
+    let mut scanner = TagScanner::default();
+    let mut out = String::with_capacity(PREFIX.len() + svg.len());
+    out.push_str(PREFIX);
+    let mut buf = [0u8; 4];
+    for c in svg.chars() {
+        let in_tag = scanner.step(c);
+        if c == '"' && in_tag && swap_quotes {
+            out.push('\'');
+        } else if c.is_ascii_graphic() || c == ' ' {
+            if context.reserves(c) {
+                push_escaped(&mut out, c as u8);
+            } else {
+                out.push(c);
+            }
+        } else {
+            // Controls, whitespace other than spaces and non-ASCII text
+            for &byte in c.encode_utf8(&mut buf).as_bytes() {
+                push_escaped(&mut out, byte);
+            }
+        }
+    }
+    out
+}
+
+fn push_escaped(out: &mut String, byte: u8) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    out.push('%');
+    out.push(char::from(HEX[usize::from(byte >> 4)]));
+    out.push(char::from(HEX[usize::from(byte & 0xf)]));
+}
+
+/// Error produced when decoding a data URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataUriError {
+    /// The input does not start with `data:` or has no `,`.
+    NotDataUri,
+    /// A `%` is not followed by two hex digits.
+    InvalidEscape(usize),
+    InvalidBase64,
+    /// The decoded bytes are not UTF-8 text.
+    InvalidUtf8,
+}
+
+impl fmt::Display for DataUriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotDataUri => f.write_str("not a data URI"),
+            Self::InvalidEscape(offset) => write!(f, "invalid percent escape at byte {offset}"),
+            Self::InvalidBase64 => f.write_str("invalid base64 data"),
+            Self::InvalidUtf8 => f.write_str("data is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for DataUriError {}
+
+/// Decode the text of a percent-encoded or base64 data URI.
+///
+/// # Errors
+/// Returns a [`DataUriError`] if the input is not a data URI or its data
+/// cannot be decoded to UTF-8 text.
+pub fn decode(uri: &str) -> Result<String, DataUriError> {
+    let rest = uri
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+        .map(|_| &uri[5..])
+        .ok_or(DataUriError::NotDataUri)?;
+    let (media_type, data) = rest.split_once(',').ok_or(DataUriError::NotDataUri)?;
+    let bytes = percent_decode(data)?;
+    let bytes = if media_type
+        .rsplit(';')
+        .next()
+        .is_some_and(|p| p.trim().eq_ignore_ascii_case("base64"))
+    {
+        base64_decode(&bytes)?
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).map_err(|_| DataUriError::InvalidUtf8)
+}
+
+fn percent_decode(data: &str) -> Result<Vec<u8>, DataUriError> {
+    let bytes = data.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = data
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(DataUriError::InvalidEscape(i))?;
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(out)
+}
+
+fn base64_decode(data: &[u8]) -> Result<Vec<u8>, DataUriError> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    };
+    let digits = data
+        .iter()
+        .filter(|c| !c.is_ascii_whitespace())
+        .take_while(|&&c| c != b'=')
+        .map(|&c| value(c).ok_or(DataUriError::InvalidBase64))
+        .collect::<Result<Vec<u8>, _>>()?;
+    if digits.len() % 4 == 1 {
+        return Err(DataUriError::InvalidBase64);
+    }
+    let mut out = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &d)| n | u32::from(d) << (18 - 6 * i));
+        let [_, b0, b1, b2] = n.to_be_bytes();
+        out.extend_from_slice(&[b0, b1, b2][..chunk.len() - 1]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree;
+
+    const CONTEXTS: [DataUriContext; 3] = [
+        DataUriContext::Css,
+        DataUriContext::HtmlAttribute,
+        DataUriContext::Markdown,
+    ];
+
+    /// Markup equivalence, ignoring which quote character attributes use.
+    fn same_document(a: &str, b: &str) -> bool {
+        tree::to_markup(&tree::parse(a).unwrap()) == tree::to_markup(&tree::parse(b).unwrap())
+    }
+
+    #[test]
+    fn encodes_minimally() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"><path fill="#f00" d="M0 0h24v24H0z"/></svg>"##;
+        assert_eq!(
+            encode(svg, DataUriContext::Css),
+            "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 24 24'%3E%3Cpath fill='%23f00' d='M0 0h24v24H0z'/%3E%3C/svg%3E"
+        );
+        let markdown = encode(svg, DataUriContext::Markdown);
+        assert!(!markdown.contains(' '));
+        assert!(markdown.contains("viewBox='0%200%2024%2024'"));
+    }
+
+    #[test]
+    fn keeps_double_quotes_when_single_quotes_are_used() {
+        let svg = r#"<svg><text font-family="'Fira Sans'">it's 100% &amp; more</text></svg>"#;
+        let css = encode(svg, DataUriContext::Css);
+        assert!(css.contains(r"font-family=%22'Fira Sans'%22"), "{css}");
+        assert!(css.contains("100%25 &amp; more"));
+        let html = encode(svg, DataUriContext::HtmlAttribute);
+        assert!(html.contains("100%25 %26amp; more"));
+        assert!(!html.contains('"'));
+    }
+
+    #[test]
+    fn round_trips_in_every_context() {
+        let documents = [
+            r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"/></svg>"#,
+            r#"<svg><text x="1">it's "quoted" (100%) #1 &lt;ok&gt;</text></svg>"#,
+            "<svg>\n\t<title>Café ☕ [x] {y} | z \\ ^ `q`</title>\r\n</svg>",
+        ];
+        for svg in documents {
+            for context in CONTEXTS {
+                let uri = encode(svg, context);
+                assert!(!uri.contains(['\n', '\t', '"', '#', '<', '>']), "{uri}");
+                assert!(uri.is_ascii());
+                let decoded = decode(&uri).unwrap();
+                assert!(same_document(&decoded, svg), "{context:?}: {decoded}");
+                if !swaps_quotes(svg) {
+                    assert_eq!(decoded, svg);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_double_quotes_in_text() {
+        let svg = r#"<svg viewBox="0 0 24 24"><text x="1">say "hi" > 'bye'</text></svg>"#;
+        for context in CONTEXTS {
+            let uri = encode(svg, context);
+            assert!(uri.contains("%22hi%22"), "{uri}");
+            assert_eq!(
+                decode(&uri).unwrap(),
+                r#"<svg viewBox='0 0 24 24'><text x='1'>say "hi" > 'bye'</text></svg>"#
+            );
+        }
+    }
+
+    #[test]
+    fn quotes_for_context() {
+        let uri = encode("<svg/>", DataUriContext::Css);
+        assert_eq!(
+            DataUriContext::Css.quote(&uri),
+            r#"url("data:image/svg+xml,%3Csvg/%3E")"#
+        );
+        assert_eq!(
+            DataUriContext::HtmlAttribute.quote(&uri),
+            r#""data:image/svg+xml,%3Csvg/%3E""#
+        );
+        assert_eq!(
+            DataUriContext::Markdown.quote(&uri),
+            "<data:image/svg+xml,%3Csvg/%3E>"
+        );
+    }
+
+    #[test]
+    fn decodes_base64_and_rejects_garbage() {
+        assert_eq!(
+            decode("data:image/svg+xml;base64,PHN2Zy8+").unwrap(),
+            "<svg/>"
+        );
+        assert_eq!(decode("data:,a%2"), Err(DataUriError::InvalidEscape(1)));
+        assert_eq!(decode("https://example.com"), Err(DataUriError::NotDataUri));
+        assert_eq!(decode("data:;base64,*"), Err(DataUriError::InvalidBase64));
+        assert_eq!(decode("data:,%FF"), Err(DataUriError::InvalidUtf8));
+    }
+}
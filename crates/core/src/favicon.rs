@@ -0,0 +1,332 @@
+//! Favicon bundles: ICO, Apple touch icon, PWA icons and the markup to use
+//! them.
+//!
+//! Available with the `raster` feature.
+
+use std::fmt::Write;
+
+use crate::archive::{self, ArchiveEntry};
+use crate::color::Color;
+use crate::raster::{self, RasterError, RasterOptions};
+use crate::types::ResolvedIcon;
+
+/// Sizes packed into `favicon.ico`.
+pub const ICO_SIZES: &[u32] = &[16, 32, 48];
+
+/// Size of `apple-touch-icon.png`.
+pub const APPLE_TOUCH_SIZE: u32 = 180;
+
+/// Sizes of the PWA manifest icons.
+pub const PWA_SIZES: &[u32] = &[192, 512];
+
+/// Padding of maskable icons as a fraction of their size. Platforms may crop
+/// maskable icons to a circle covering 80% of the image, so the corners of
+/// the padded square have to stay inside it: half its diagonal must be at
+/// most 40% of the size, which takes a padding of at least 21.7%.
+const MASKABLE_PADDING: f64 = 0.22;
+
+/// Padding of the Apple touch icon as a fraction of its size.
+const APPLE_TOUCH_PADDING: f64 = 0.1;
+
+/// Colours and naming for a favicon bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaviconOptions {
+    /// Colour used for `currentColor`.
+    pub foreground: Color,
+    /// Background of the icons that must be opaque (Apple touch and
+    /// maskable icons). Other icons stay transparent.
+    pub background: Color,
+    /// Application name written to the manifest fragment.
+    pub name: String,
+    /// URL path the files are served from, e.g. `/` or `/static/`.
+    pub base_path: String,
+}
+
+impl Default for FaviconOptions {
+    fn default() -> Self {
+        Self {
+            foreground: Color::BLACK,
+            background: Color::WHITE,
+            name: String::new(),
+            base_path: "/".to_string(),
+        }
+    }
+}
+
+/// A generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaviconFile {
+    pub name: String,
+    pub mime: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// Everything needed to install a favicon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaviconBundle {
+    pub files: Vec<FaviconFile>,
+    /// `<link>` tags for the document `<head>`.
+    pub link_tags: String,
+    /// Contents of `manifest.webmanifest`.
+    pub manifest: String,
+}
+
+impl FaviconBundle {
+    /// Pack the files, the manifest and the link tags (as `head.html`) into
+    /// a ZIP archive.
+    #[must_use]
+    pub fn to_zip(&self) -> Vec<u8> {
+        let mut entries: Vec<ArchiveEntry> = self
+            .files
+            .iter()
+            .map(|f| ArchiveEntry::new(f.name.clone(), f.data.clone()))
+            .collect();
+        entries.push(ArchiveEntry::new(
+            "manifest.webmanifest",
+            self.manifest.clone(),
+        ));
+        entries.push(ArchiveEntry::new("head.html", self.link_tags.clone()));
+        archive::zip(&entries)
+    }
+}
+
+/// Pack PNG images into an ICO file. Each image is `(size, png)`.
+#[must_use]
+pub fn encode_ico(images: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let count = u16::try_from(images.len()).unwrap_or(u16::MAX);
+    let mut out = Vec::new();
+    out.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    out.extend_from_slice(&1u16.to_le_bytes()); // type: icon
+    out.extend_from_slice(&count.to_le_bytes());
+
+    let mut offset = 6 + 16 * images.len();
+    for (size, png) in images {
+        // A dimension byte of 0 means 256 pixels
+        let dimension = u8::try_from(*size).unwrap_or(0);
+        out.extend_from_slice(&[dimension, dimension, 0, 0]);
+        out.extend_from_slice(&1u16.to_le_bytes()); // colour planes
+        out.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+        out.extend_from_slice(&u32::try_from(png.len()).unwrap_or(u32::MAX).to_le_bytes());
+        out.extend_from_slice(&u32::try_from(offset).unwrap_or(u32::MAX).to_le_bytes());
+        offset += png.len();
+    }
+    for (_, png) in images {
+        out.extend_from_slice(png);
+    }
+    out
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn padding(size: u32, fraction: f64) -> u32 {
+    (f64::from(size) * fraction).round() as u32
+}
+
+/// Escape a string for a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Generate a favicon bundle from an icon.
+///
+/// # Errors
+/// Returns a [`RasterError`] if the icon cannot be rendered.
+pub fn generate(
+    icon: &ResolvedIcon,
+    options: &FaviconOptions,
+) -> Result<FaviconBundle, RasterError> {
+    let render = |size: u32, background: Option<Color>, padding: u32| {
+        raster::render_png(
+            icon,
+            &RasterOptions {
+                size,
+                foreground: options.foreground,
+                background,
+                padding,
+            },
+        )
+    };
+    let base = &options.base_path;
+    let mut files = Vec::new();
+
+    let ico_images = ICO_SIZES
+        .iter()
+        .map(|&size| render(size, None, 0).map(|png| (size, png)))
+        .collect::<Result<Vec<_>, _>>()?;
+    files.push(FaviconFile {
+        name: "favicon.ico".to_string(),
+        mime: "image/x-icon",
+        data: encode_ico(&ico_images),
+    });
+    files.push(FaviconFile {
+        name: "icon.svg".to_string(),
+        mime: "image/svg+xml",
+        data: raster::tinted_svg(icon, options.foreground)?.into_bytes(),
+    });
+    files.push(FaviconFile {
+        name: "apple-touch-icon.png".to_string(),
+        mime: "image/png",
+        data: render(
+            APPLE_TOUCH_SIZE,
+            Some(options.background),
+            padding(APPLE_TOUCH_SIZE, APPLE_TOUCH_PADDING),
+        )?,
+    });
+
+    let mut manifest_icons = Vec::new();
+    for &size in PWA_SIZES {
+        let name = format!("icon-{size}.png");
+        manifest_icons.push(format!(
+            r#"    {{ "src": {}, "sizes": "{size}x{size}", "type": "image/png" }}"#,
+            json_string(&format!("{base}{name}"))
+        ));
+        files.push(FaviconFile {
+            name,
+            mime: "image/png",
+            data: render(size, None, 0)?,
+        });
+    }
+    for &size in PWA_SIZES {
+        let name = format!("icon-maskable-{size}.png");
+        manifest_icons.push(format!(
+            r#"    {{ "src": {}, "sizes": "{size}x{size}", "type": "image/png", "purpose": "maskable" }}"#,
+            json_string(&format!("{base}{name}"))
+        ));
+        files.push(FaviconFile {
+            name,
+            mime: "image/png",
+            data: render(
+                size,
+                Some(options.background),
+                padding(size, MASKABLE_PADDING),
+            )?,
+        });
+    }
+
+    let manifest = format!(
+        "{{\n  \"name\": {},\n  \"icons\": [\n{}\n  ],\n  \"background_color\": \"{}\"\n}}\n",
+        json_string(&options.name),
+        manifest_icons.join(",\n"),
+        options.background
+    );
+    let link_tags = format!(
+        r#"<link rel="icon" href="{base}favicon.ico" sizes="32x32">
+<link rel="icon" href="{base}icon.svg" type="image/svg+xml">
+<link rel="apple-touch-icon" href="{base}apple-touch-icon.png">
+<link rel="manifest" href="{base}manifest.webmanifest">
+"#
+    );
+
+    Ok(FaviconBundle {
+        files,
+        link_tags,
+        manifest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon() -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: "test".into(),
+            name: "square".into(),
+            body: r#"<path d="M4 4h16v16H4z"/>"#.into(),
+            width: 24,
+            height: 24,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn encodes_ico_directory() {
+        let ico = encode_ico(&[(16, vec![1, 2, 3]), (256, vec![4, 5])]);
+        assert_eq!(&ico[..6], &[0, 0, 1, 0, 2, 0]);
+        // First entry: 16x16, 3 bytes at offset 6 + 2 * 16
+        assert_eq!(&ico[6..8], &[16, 16]);
+        assert_eq!(&ico[14..18], &3u32.to_le_bytes());
+        assert_eq!(&ico[18..22], &38u32.to_le_bytes());
+        // Second entry: 256 is written as 0
+        assert_eq!(&ico[22..24], &[0, 0]);
+        assert_eq!(&ico[38..], &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn generates_every_file() {
+        let options = FaviconOptions {
+            name: "My \"App\"".into(),
+            ..Default::default()
+        };
+        let bundle = generate(&icon(), &options).unwrap();
+        let names: Vec<&str> = bundle.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "favicon.ico",
+                "icon.svg",
+                "apple-touch-icon.png",
+                "icon-192.png",
+                "icon-512.png",
+                "icon-maskable-192.png",
+                "icon-maskable-512.png",
+            ]
+        );
+        assert_eq!(&bundle.files[0].data[..4], &[0, 0, 1, 0]);
+        assert!(bundle.link_tags.contains(r#"href="/manifest.webmanifest""#));
+
+        let manifest: serde_json::Value = serde_json::from_str(&bundle.manifest).unwrap();
+        assert_eq!(manifest["name"], "My \"App\"");
+        assert_eq!(manifest["icons"][2]["purpose"], "maskable");
+        assert_eq!(manifest["icons"][1]["src"], "/icon-512.png");
+    }
+
+    #[test]
+    fn maskable_icons_keep_the_safe_zone() {
+        let image = raster::render(
+            &icon(),
+            &RasterOptions {
+                size: 100,
+                background: Some(Color::WHITE),
+                padding: padding(100, MASKABLE_PADDING),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // Opaque everywhere, with the icon only inside the central area
+        assert_eq!(image.pixel(0, 0), Some(Color::WHITE));
+        assert_eq!(image.pixel(15, 50), Some(Color::WHITE));
+        assert_eq!(image.pixel(50, 50), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn maskable_padding_fits_the_safe_zone() {
+        for &size in PWA_SIZES.iter().chain(&[48, 100]) {
+            let inner = f64::from(size - 2 * padding(size, MASKABLE_PADDING));
+            let corner_radius = inner / 2.0 * std::f64::consts::SQRT_2;
+            assert!(corner_radius <= 0.4 * f64::from(size), "{size}");
+        }
+    }
+
+    #[test]
+    fn zips_the_bundle() {
+        let zip = generate(&icon(), &FaviconOptions::default())
+            .unwrap()
+            .to_zip();
+        assert_eq!(&zip[..4], b"PK\x03\x04");
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 9);
+    }
+}
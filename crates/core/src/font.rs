@@ -0,0 +1,1437 @@
+//! Icon fonts: TrueType and WOFF2 fonts built from monochrome icons, with
+//! CSS classes, an HTML preview and a persistent codepoint mapping.
+//!
+//! Available with the `font` feature (on by default). Glyph outlines are
+//! taken from filled shapes; icons that rely on strokes or several colours
+//! cannot be represented by a font and are reported as skipped.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::io::Write as _;
+
+use crate::archive::{self, ArchiveEntry};
+use crate::color::{self, ColorKind};
+use crate::geometry::{self, Transform};
+use crate::path::{self, Command};
+use crate::sprite::{self, IdScheme};
+use crate::tree::{self, Element, Node};
+use crate::types::ResolvedIcon;
+
+/// First codepoint of the Unicode Private Use Area.
+pub const PUA_START: u32 = 0xE000;
+/// Last codepoint of the Private Use Area in the Basic Multilingual Plane.
+pub const PUA_END: u32 = 0xF8FF;
+
+/// Maximum distance, in font units, between a cubic curve and the quadratic
+/// curves that replace it.
+const QUADRATIC_TOLERANCE: f64 = 0.5;
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Error produced while building a font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontError {
+    /// Every Private Use Area codepoint is taken.
+    CodepointsExhausted,
+    /// A line of a codepoint mapping file could not be read.
+    InvalidMapping { line: usize, message: String },
+    /// A codepoint mapping assigns one codepoint to two icons.
+    DuplicateCodepoint(u32),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CodepointsExhausted => f.write_str("no free Private Use Area codepoints"),
+            Self::InvalidMapping { line, message } => {
+                write!(f, "invalid codepoint mapping on line {line}: {message}")
+            }
+            Self::DuplicateCodepoint(cp) => write!(f, "codepoint U+{cp:04X} is assigned twice"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+// =============================================================================
+// Codepoint mapping
+// =============================================================================
+
+/// Stable assignment of icons (`prefix:name`) to Private Use Area codepoints.
+///
+/// Codepoints are never reused, even for icons that are no longer built, so
+/// a character keeps its meaning across builds. Serialized as one
+/// `U+E001 prefix:name` line per icon.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodepointMap {
+    map: BTreeMap<String, u32>,
+}
+
+impl CodepointMap {
+    /// Read a mapping file. Blank lines and `#` comments are ignored.
+    ///
+    /// # Errors
+    /// Returns [`FontError::InvalidMapping`] for malformed lines and
+    /// [`FontError::DuplicateCodepoint`] if two icons share a codepoint.
+    pub fn parse(text: &str) -> Result<Self, FontError> {
+        let mut map = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |message: &str| FontError::InvalidMapping {
+                line: i + 1,
+                message: message.to_string(),
+            };
+            let (codepoint, icon) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| invalid("expected `U+XXXX prefix:name`"))?;
+            let hex = codepoint
+                .strip_prefix("U+")
+                .ok_or_else(|| invalid("codepoint must start with `U+`"))?;
+            let codepoint =
+                u32::from_str_radix(hex, 16).map_err(|_| invalid("invalid hex codepoint"))?;
+            if !(PUA_START..=PUA_END).contains(&codepoint) {
+                return Err(invalid("codepoint outside the Private Use Area"));
+            }
+            if map.values().any(|&cp| cp == codepoint) {
+                return Err(FontError::DuplicateCodepoint(codepoint));
+            }
+            map.insert(icon.trim().to_string(), codepoint);
+        }
+        Ok(Self { map })
+    }
+
+    /// Serialize in codepoint order.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut entries: Vec<(&String, &u32)> = self.map.iter().collect();
+        entries.sort_by_key(|(_, cp)| **cp);
+        let mut text = String::new();
+        for (icon, cp) in entries {
+            let _ = writeln!(text, "U+{cp:04X} {icon}");
+        }
+        text
+    }
+
+    /// The codepoint of an icon, if assigned.
+    #[must_use]
+    pub fn get(&self, icon: &str) -> Option<u32> {
+        self.map.get(icon).copied()
+    }
+
+    /// The codepoint of an icon, assigning the next free one if needed.
+    ///
+    /// # Errors
+    /// Returns [`FontError::CodepointsExhausted`] when the Private Use Area
+    /// is full.
+    pub fn assign(&mut self, icon: &str) -> Result<u32, FontError> {
+        if let Some(cp) = self.get(icon) {
+            return Ok(cp);
+        }
+        let next = self.map.values().max().map_or(PUA_START, |max| max + 1);
+        let codepoint = if next <= PUA_END {
+            next
+        } else {
+            // Only fill gaps once the end is reached, so new icons never
+            // take a codepoint that was handed out in the past.
+            (PUA_START..=PUA_END)
+                .find(|cp| !self.map.values().any(|used| used == cp))
+                .ok_or(FontError::CodepointsExhausted)?
+        };
+        self.map.insert(icon.to_string(), codepoint);
+        Ok(codepoint)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+// =============================================================================
+// Options and output
+// =============================================================================
+
+/// Options for [`build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontOptions {
+    /// CSS `font-family` and font name.
+    pub family: String,
+    /// Base name of the generated files, e.g. `icons` for `icons.ttf`.
+    pub file_name: String,
+    /// Prefix of the CSS classes, e.g. `icon` for `.icon-mdi-home`.
+    pub class_prefix: String,
+    /// How the part of the class after the prefix is derived.
+    pub naming: IdScheme,
+    pub units_per_em: u16,
+}
+
+impl Default for FontOptions {
+    fn default() -> Self {
+        Self {
+            family: "Icons".to_string(),
+            file_name: "icons".to_string(),
+            class_prefix: "icon".to_string(),
+            naming: IdScheme::default(),
+            units_per_em: 1000,
+        }
+    }
+}
+
+/// Why an icon was left out of a font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The icon uses several colours or gradients.
+    Multicolor,
+    /// The icon is drawn with strokes, which fonts cannot express.
+    Strokes,
+    /// The icon contains an element that cannot be converted.
+    Unsupported(String),
+    /// The body could not be parsed.
+    Malformed,
+    /// Nothing is filled.
+    Empty,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Multicolor => f.write_str("uses more than one colour"),
+            Self::Strokes => f.write_str("drawn with strokes"),
+            Self::Unsupported(element) => write!(f, "contains unsupported <{element}>"),
+            Self::Malformed => f.write_str("malformed SVG"),
+            Self::Empty => f.write_str("no filled shapes"),
+        }
+    }
+}
+
+/// An icon that was not included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedIcon {
+    /// The icon as `prefix:name`.
+    pub icon: String,
+    pub reason: SkipReason,
+}
+
+/// An icon included in the font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontGlyph {
+    /// The icon as `prefix:name`.
+    pub icon: String,
+    /// CSS class that shows it.
+    pub class: String,
+    pub codepoint: u32,
+}
+
+/// A built icon font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconFont {
+    pub ttf: Vec<u8>,
+    pub woff2: Vec<u8>,
+    pub css: String,
+    pub html: String,
+    /// The mapping to persist for the next build.
+    pub codepoints: CodepointMap,
+    pub glyphs: Vec<FontGlyph>,
+    pub skipped: Vec<SkippedIcon>,
+}
+
+impl IconFont {
+    /// Pack every file, including `codepoints.txt`, into a ZIP archive.
+    #[must_use]
+    pub fn to_zip(&self, options: &FontOptions) -> Vec<u8> {
+        let name = &options.file_name;
+        archive::zip(&[
+            ArchiveEntry::new(format!("{name}.ttf"), self.ttf.clone()),
+            ArchiveEntry::new(format!("{name}.woff2"), self.woff2.clone()),
+            ArchiveEntry::new(format!("{name}.css"), self.css.clone()),
+            ArchiveEntry::new(format!("{name}.html"), self.html.clone()),
+            ArchiveEntry::new("codepoints.txt", self.codepoints.to_text()),
+        ])
+    }
+}
+
+// =============================================================================
+// Outline extraction
+// =============================================================================
+
+type Point = (f64, f64);
+
+/// A closed contour of cubic segments `(control1, control2, end)`.
+#[derive(Debug, Clone)]
+struct Contour {
+    start: Point,
+    segments: Vec<(Point, Point, Point)>,
+}
+
+impl Contour {
+    fn reversed(&self) -> Self {
+        let mut points = vec![self.start];
+        points.extend(self.segments.iter().map(|s| s.2));
+        let end = *points.last().unwrap_or(&self.start);
+        let segments = self
+            .segments
+            .iter()
+            .rev()
+            .zip(points.iter().rev().skip(1))
+            .map(|(&(c1, c2, _), &to)| (c2, c1, to))
+            .collect();
+        Self {
+            start: end,
+            segments,
+        }
+    }
+
+    /// Vertices of a rough polygon approximation.
+    fn polygon(&self) -> Vec<Point> {
+        let mut points = vec![self.start];
+        let mut from = self.start;
+        for &(c1, c2, to) in &self.segments {
+            for i in 1..=4 {
+                points.push(cubic_at(from, c1, c2, to, f64::from(i) / 4.0));
+            }
+            from = to;
+        }
+        points
+    }
+}
+
+#[allow(clippy::many_single_char_names)]
+fn cubic_at(p0: Point, p1: Point, p2: Point, p3: Point, t: f64) -> Point {
+    let mt = 1.0 - t;
+    let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+fn signed_area(polygon: &[Point]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn contains(polygon: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.1 > p.1) != (b.1 > p.1) && p.0 < (b.0 - a.0) * (p.1 - a.1) / (b.1 - a.1) + a.0 {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Orient even-odd contours so a non-zero renderer draws the same shape:
+/// contours nested an even number of times wind one way, odd ones the other.
+fn orient_even_odd(contours: Vec<Contour>) -> Vec<Contour> {
+    let polygons: Vec<Vec<Point>> = contours.iter().map(Contour::polygon).collect();
+    contours
+        .into_iter()
+        .enumerate()
+        .map(|(i, contour)| {
+            let probe = polygons[i][0];
+            let depth = polygons
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && contains(other, probe))
+                .count();
+            let positive = signed_area(&polygons[i]) > 0.0;
+            if positive == depth.is_multiple_of(2) {
+                contour
+            } else {
+                contour.reversed()
+            }
+        })
+        .collect()
+}
+
+/// Painting state inherited from ancestors.
+#[derive(Clone, Copy)]
+struct Paint {
+    transform: Transform,
+    filled: bool,
+    stroked: bool,
+    even_odd: bool,
+}
+
+/// Collect filled contours in icon coordinates.
+fn collect(el: &Element, parent: Paint, out: &mut Vec<Contour>) -> Result<(), SkipReason> {
+    if geometry::NON_RENDERED.contains(&el.name.as_str())
+        || geometry::property(el, "display") == Some("none")
+    {
+        return Ok(());
+    }
+    let mut paint = parent;
+    if let Some(t) = el.attr("transform").and_then(Transform::parse) {
+        paint.transform = paint.transform.then(t);
+    }
+    if let Some(fill) = geometry::property(el, "fill") {
+        paint.filled = fill != "none";
+    }
+    if let Some(stroke) = geometry::property(el, "stroke") {
+        paint.stroked = stroke != "none";
+    }
+    if let Some(rule) = geometry::property(el, "fill-rule") {
+        paint.even_odd = rule == "evenodd";
+    }
+
+    match el.name.as_str() {
+        "g" | "svg" | "a" | "switch" => {
+            for child in el.child_elements() {
+                collect(child, paint, out)?;
+            }
+            Ok(())
+        }
+        "path" | "rect" | "circle" | "ellipse" | "line" | "polyline" | "polygon" => {
+            if paint.stroked {
+                return Err(SkipReason::Strokes);
+            }
+            if !paint.filled {
+                return Ok(());
+            }
+            let Some(d) = geometry::shape_path(el) else {
+                return Ok(());
+            };
+            let segments = path::parse(&d).map_err(|_| SkipReason::Malformed)?;
+            let mut contours = path_contours(&segments, paint.transform);
+            if paint.even_odd {
+                contours = orient_even_odd(contours);
+            }
+            out.extend(contours);
+            Ok(())
+        }
+        other => Err(SkipReason::Unsupported(other.to_string())),
+    }
+}
+
+/// Split a path into transformed cubic contours.
+fn path_contours(segments: &[path::Segment], transform: Transform) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    let mut current: Option<Contour> = None;
+    for segment in path::to_cubics(segments) {
+        let a = &segment.args;
+        match segment.command {
+            Command::MoveTo => {
+                contours.extend(current.take());
+                current = Some(Contour {
+                    start: transform.apply(a[0], a[1]),
+                    segments: Vec::new(),
+                });
+            }
+            Command::CubicTo => {
+                if let Some(contour) = current.as_mut() {
+                    contour.segments.push((
+                        transform.apply(a[0], a[1]),
+                        transform.apply(a[2], a[3]),
+                        transform.apply(a[4], a[5]),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    contours.extend(current);
+    contours.retain(|c| !c.segments.is_empty());
+    contours
+}
+
+// =============================================================================
+// Glyphs
+// =============================================================================
+
+/// A TrueType point: `(x, y, on_curve)`.
+type GlyphPoint = (i16, i16, bool);
+
+#[derive(Debug, Clone, Default)]
+struct Glyph {
+    contours: Vec<Vec<GlyphPoint>>,
+    advance: u16,
+}
+
+impl Glyph {
+    fn points(&self) -> impl Iterator<Item = &GlyphPoint> {
+        self.contours.iter().flatten()
+    }
+
+    /// `[x_min, y_min, x_max, y_max]`, all zero for empty glyphs.
+    fn bounds(&self) -> [i16; 4] {
+        let mut points = self.points().peekable();
+        if points.peek().is_none() {
+            return [0; 4];
+        }
+        points.fold([i16::MAX, i16::MAX, i16::MIN, i16::MIN], |b, p| {
+            [b[0].min(p.0), b[1].min(p.1), b[2].max(p.0), b[3].max(p.1)]
+        })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_unit(n: f64) -> i16 {
+    n.round().clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+}
+
+fn distance_to_line(p: Point, a: Point, b: Point) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len = dx.hypot(dy);
+    if len < 1e-9 {
+        return (p.0 - a.0).hypot(p.1 - a.1);
+    }
+    ((p.0 - a.0) * dy - (p.1 - a.1) * dx).abs() / len
+}
+
+/// Append quadratic curves approximating one cubic, as `(control, end)`.
+fn cubic_to_quads(p0: Point, p1: Point, p2: Point, p3: Point, out: &mut Vec<(Point, Point)>) {
+    // Error of a single quadratic is about sqrt(3)/36 * |p3 - 3p2 + 3p1 - p0|
+    // and shrinks with the cube of the number of pieces.
+    let dx = p3.0 - 3.0 * p2.0 + 3.0 * p1.0 - p0.0;
+    let dy = p3.1 - 3.0 * p2.1 + 3.0 * p1.1 - p0.1;
+    let error = 3f64.sqrt() / 36.0 * dx.hypot(dy);
+    let pieces = (error / QUADRATIC_TOLERANCE).cbrt().ceil().clamp(1.0, 64.0);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let n = pieces as u32;
+    for i in 0..n {
+        let (t0, t1) = (f64::from(i) / pieces, f64::from(i + 1) / pieces);
+        let (q0, q3) = (cubic_at(p0, p1, p2, p3, t0), cubic_at(p0, p1, p2, p3, t1));
+        // Control points of the sub-curve from its derivative at both ends
+        let d0 = derivative(p0, p1, p2, p3, t0);
+        let d1 = derivative(p0, p1, p2, p3, t1);
+        let h = (t1 - t0) / 3.0;
+        let q1 = (q0.0 + d0.0 * h, q0.1 + d0.1 * h);
+        let q2 = (q3.0 - d1.0 * h, q3.1 - d1.1 * h);
+        let control = (
+            (3.0 * (q1.0 + q2.0) - q0.0 - q3.0) / 4.0,
+            (3.0 * (q1.1 + q2.1) - q0.1 - q3.1) / 4.0,
+        );
+        out.push((control, q3));
+    }
+}
+
+fn derivative(p0: Point, p1: Point, p2: Point, p3: Point, t: f64) -> Point {
+    let mt = 1.0 - t;
+    let (a, b, c) = (3.0 * mt * mt, 6.0 * mt * t, 3.0 * t * t);
+    (
+        a * (p1.0 - p0.0) + b * (p2.0 - p1.0) + c * (p3.0 - p2.0),
+        a * (p1.1 - p0.1) + b * (p2.1 - p1.1) + c * (p3.1 - p2.1),
+    )
+}
+
+/// Convert contours to TrueType points, mapping the icon's viewBox onto the
+/// em square with the bottom edge on the baseline.
+fn glyph_from_contours(contours: &[Contour], icon: &ResolvedIcon, em: u16) -> Glyph {
+    let scale = f64::from(em) / f64::from(icon.height.max(1));
+    let bottom = f64::from(icon.top) + f64::from(icon.height);
+    let map = |p: Point| ((p.0 - f64::from(icon.left)) * scale, (bottom - p.1) * scale);
+    let mut glyph = Glyph {
+        contours: Vec::new(),
+        advance: u16::try_from(to_unit(f64::from(icon.width) * scale).max(0)).unwrap_or(0),
+    };
+    for contour in contours {
+        let start = map(contour.start);
+        let mut points: Vec<GlyphPoint> = vec![(to_unit(start.0), to_unit(start.1), true)];
+        let mut from = start;
+        for &(c1, c2, to) in &contour.segments {
+            let (c1, c2, to) = (map(c1), map(c2), map(to));
+            let straight = distance_to_line(c1, from, to) < QUADRATIC_TOLERANCE
+                && distance_to_line(c2, from, to) < QUADRATIC_TOLERANCE;
+            if straight {
+                points.push((to_unit(to.0), to_unit(to.1), true));
+            } else {
+                let mut quads = Vec::new();
+                cubic_to_quads(from, c1, c2, to, &mut quads);
+                for (control, end) in quads {
+                    points.push((to_unit(control.0), to_unit(control.1), false));
+                    points.push((to_unit(end.0), to_unit(end.1), true));
+                }
+            }
+            from = to;
+        }
+        points.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1 && a.2 == b.2);
+        // Contours close implicitly
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() > 2 {
+            glyph.contours.push(points);
+        }
+    }
+    glyph
+}
+
+fn icon_glyph(icon: &ResolvedIcon, em: u16) -> Result<Glyph, SkipReason> {
+    let icon = crate::svg::apply_transformations(icon);
+    let analysis = color::analyze(&icon.body).map_err(|_| SkipReason::Malformed)?;
+    if icon.palette || analysis.kind() == ColorKind::Multicolor {
+        return Err(SkipReason::Multicolor);
+    }
+    let nodes = tree::parse(&icon.body).map_err(|_| SkipReason::Malformed)?;
+    let paint = Paint {
+        transform: Transform::IDENTITY,
+        filled: true,
+        stroked: false,
+        even_odd: false,
+    };
+    let mut contours = Vec::new();
+    for node in &nodes {
+        if let Node::Element(el) = node {
+            collect(el, paint, &mut contours)?;
+        }
+    }
+    let glyph = glyph_from_contours(&contours, &icon, em);
+    if glyph.contours.is_empty() {
+        return Err(SkipReason::Empty);
+    }
+    Ok(glyph)
+}
+
+// =============================================================================
+// TrueType tables
+// =============================================================================
+
+/// Big-endian writers for font tables.
+trait Put {
+    fn u16(&mut self, v: u16);
+    fn i16(&mut self, v: i16);
+    fn u32(&mut self, v: u32);
+}
+
+impl Put for Vec<u8> {
+    fn u16(&mut self, v: u16) {
+        self.extend_from_slice(&v.to_be_bytes());
+    }
+    fn i16(&mut self, v: i16) {
+        self.extend_from_slice(&v.to_be_bytes());
+    }
+    fn u32(&mut self, v: u32) {
+        self.extend_from_slice(&v.to_be_bytes());
+    }
+}
+
+fn len_u16(n: usize) -> u16 {
+    u16::try_from(n).unwrap_or(u16::MAX)
+}
+
+fn len_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// `searchRange`, `entrySelector` and `rangeShift` for `count` items of
+/// `size` bytes.
+fn search_params(count: u16, size: u16) -> (u16, u16, u16) {
+    let mut power = 1u16;
+    let mut log = 0u16;
+    while power.saturating_mul(2) <= count {
+        power *= 2;
+        log += 1;
+    }
+    let range = power.saturating_mul(size);
+    (range, log, count.saturating_mul(size).saturating_sub(range))
+}
+
+fn glyf_and_loca(glyphs: &[Glyph]) -> (Vec<u8>, Vec<u8>) {
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    for glyph in glyphs {
+        loca.u32(len_u32(glyf.len()));
+        if glyph.contours.is_empty() {
+            continue;
+        }
+        let [x_min, y_min, x_max, y_max] = glyph.bounds();
+        glyf.i16(i16::try_from(glyph.contours.len()).unwrap_or(i16::MAX));
+        for v in [x_min, y_min, x_max, y_max] {
+            glyf.i16(v);
+        }
+        let mut end = 0usize;
+        for contour in &glyph.contours {
+            end += contour.len();
+            glyf.u16(len_u16(end - 1));
+        }
+        // No instructions
+        glyf.u16(0);
+        // Flags only mark on-curve points, so every coordinate is a signed
+        // 16-bit delta.
+        for &(_, _, on_curve) in glyph.points() {
+            glyf.push(u8::from(on_curve));
+        }
+        let mut last = 0i16;
+        for &(x, _, _) in glyph.points() {
+            glyf.i16(x.wrapping_sub(last));
+            last = x;
+        }
+        last = 0;
+        for &(_, y, _) in glyph.points() {
+            glyf.i16(y.wrapping_sub(last));
+            last = y;
+        }
+        while glyf.len() % 4 != 0 {
+            glyf.push(0);
+        }
+    }
+    loca.u32(len_u32(glyf.len()));
+    (glyf, loca)
+}
+
+/// Format 4 `cmap` mapping each codepoint to its glyph.
+fn cmap(mapping: &[(u32, u16)]) -> Vec<u8> {
+    // One segment per codepoint, plus the required final 0xFFFF segment
+    let mut segments: Vec<(u16, u16)> = mapping
+        .iter()
+        .filter_map(|&(cp, glyph)| u16::try_from(cp).ok().map(|cp| (cp, glyph)))
+        .collect();
+    segments.sort_unstable();
+    segments.push((0xFFFF, 0));
+    let count = len_u16(segments.len());
+    let (range, selector, shift) = search_params(count, 2);
+
+    let mut sub = Vec::new();
+    sub.u16(4);
+    sub.u16(len_u16(16 + 8 * segments.len()));
+    sub.u16(0); // language
+    sub.u16(count * 2);
+    sub.u16(range);
+    sub.u16(selector);
+    sub.u16(shift);
+    for (cp, _) in &segments {
+        sub.u16(*cp);
+    }
+    sub.u16(0); // reserved pad
+    for (cp, _) in &segments {
+        sub.u16(*cp);
+    }
+    for &(cp, glyph) in &segments {
+        // idDelta is added modulo 65536
+        sub.u16(if cp == 0xFFFF {
+            1
+        } else {
+            glyph.wrapping_sub(cp)
+        });
+    }
+    for _ in &segments {
+        sub.u16(0); // idRangeOffset
+    }
+
+    let mut table = Vec::new();
+    table.u16(0);
+    table.u16(1);
+    table.u16(3); // Windows
+    table.u16(1); // Unicode BMP
+    table.u32(12);
+    table.extend_from_slice(&sub);
+    table
+}
+
+fn name_table(family: &str) -> Vec<u8> {
+    let postscript: String = family
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !"[](){}<>/%".contains(*c))
+        .collect();
+    let records = [
+        (1u16, family.to_string()),
+        (2, "Regular".to_string()),
+        (3, format!("{postscript}-Regular")),
+        (4, family.to_string()),
+        (5, "Version 1.0".to_string()),
+        (6, postscript),
+    ];
+    let encoded: Vec<Vec<u8>> = records
+        .iter()
+        .map(|(_, s)| s.encode_utf16().flat_map(u16::to_be_bytes).collect())
+        .collect();
+
+    let mut table = Vec::new();
+    table.u16(0);
+    table.u16(len_u16(records.len()));
+    table.u16(len_u16(6 + 12 * records.len()));
+    let mut offset = 0usize;
+    for ((id, _), bytes) in records.iter().zip(&encoded) {
+        table.u16(3); // Windows
+        table.u16(1); // Unicode BMP
+        table.u16(0x0409); // English (US)
+        table.u16(*id);
+        table.u16(len_u16(bytes.len()));
+        table.u16(len_u16(offset));
+        offset += bytes.len();
+    }
+    for bytes in encoded {
+        table.extend_from_slice(&bytes);
+    }
+    table
+}
+
+/// Assemble an sfnt file from tables, filling in `head.checkSumAdjustment`.
+fn sfnt(mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    tables.sort_by_key(|(tag, _)| *tag);
+    let count = len_u16(tables.len());
+    let (range, selector, shift) = search_params(count, 16);
+
+    let mut out = Vec::new();
+    out.u32(0x0001_0000);
+    out.u16(count);
+    out.u16(range);
+    out.u16(selector);
+    out.u16(shift);
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = None;
+    for (tag, data) in &tables {
+        out.extend_from_slice(tag);
+        out.u32(checksum(data));
+        out.u32(len_u32(offset));
+        out.u32(len_u32(data.len()));
+        if tag == b"head" {
+            head_offset = Some(offset);
+        }
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in &tables {
+        out.extend_from_slice(data);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    if let Some(head) = head_offset {
+        let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&out));
+        out[head + 8..head + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    out
+}
+
+/// Write a TrueType font. Glyph 0 must be `.notdef`.
+fn ttf(glyphs: &[Glyph], mapping: &[(u32, u16)], options: &FontOptions) -> Vec<u8> {
+    let em = options.units_per_em;
+    let em_i16 = i16::try_from(em).unwrap_or(i16::MAX);
+    let bounds: Vec<[i16; 4]> = glyphs.iter().map(Glyph::bounds).collect();
+    let drawn = || {
+        bounds
+            .iter()
+            .zip(glyphs)
+            .filter(|(_, g)| !g.contours.is_empty())
+    };
+    let x_min = drawn().map(|(b, _)| b[0]).min().unwrap_or(0);
+    let y_min = drawn().map(|(b, _)| b[1]).min().unwrap_or(0);
+    let x_max = drawn().map(|(b, _)| b[2]).max().unwrap_or(0);
+    let y_max = drawn().map(|(b, _)| b[3]).max().unwrap_or(0);
+    let advance_max = glyphs.iter().map(|g| g.advance).max().unwrap_or(0);
+    let num_glyphs = len_u16(glyphs.len());
+    let (glyf, loca) = glyf_and_loca(glyphs);
+
+    let mut head = Vec::new();
+    head.u32(0x0001_0000);
+    head.u32(0x0001_0000); // font revision 1.0
+    head.u32(0); // checksum adjustment, filled in by `sfnt`
+    head.u32(0x5F0F_3CF5);
+    head.u16(0x000B); // baseline at y=0, lsb at x=0, integer scaling
+    head.u16(em);
+    head.extend_from_slice(&[0; 16]); // created and modified
+    for v in [x_min, y_min, x_max, y_max] {
+        head.i16(v);
+    }
+    head.u16(0); // mac style
+    head.u16(8); // smallest readable size
+    head.i16(2); // font direction hint
+    head.i16(1); // long loca offsets
+    head.i16(0);
+
+    let mut hhea = Vec::new();
+    hhea.u32(0x0001_0000);
+    hhea.i16(em_i16); // ascender
+    hhea.i16(0); // descender
+    hhea.i16(0); // line gap
+    hhea.u16(advance_max);
+    hhea.i16(drawn().map(|(b, _)| b[0]).min().unwrap_or(0));
+    hhea.i16(
+        drawn()
+            .map(|(b, g)| to_unit(f64::from(g.advance)) - b[2])
+            .min()
+            .unwrap_or(0),
+    );
+    hhea.i16(x_max);
+    hhea.i16(1); // caret slope rise
+    hhea.i16(0); // caret slope run
+    hhea.extend_from_slice(&[0; 10]); // caret offset and reserved
+    hhea.i16(0); // metric data format
+    hhea.u16(num_glyphs);
+
+    let mut hmtx = Vec::new();
+    for (glyph, b) in glyphs.iter().zip(&bounds) {
+        hmtx.u16(glyph.advance);
+        hmtx.i16(b[0]);
+    }
+
+    let max_points = glyphs.iter().map(|g| g.points().count()).max().unwrap_or(0);
+    let max_contours = glyphs.iter().map(|g| g.contours.len()).max().unwrap_or(0);
+    let mut maxp = Vec::new();
+    maxp.u32(0x0001_0000);
+    maxp.u16(num_glyphs);
+    maxp.u16(len_u16(max_points));
+    maxp.u16(len_u16(max_contours));
+    maxp.u16(0); // composite points
+    maxp.u16(0); // composite contours
+    maxp.u16(2); // zones
+    maxp.extend_from_slice(&[0; 18]); // instruction limits and components
+
+    let codepoints: Vec<u16> = mapping
+        .iter()
+        .filter_map(|(cp, _)| u16::try_from(*cp).ok())
+        .collect();
+    let mut os2 = Vec::new();
+    os2.u16(4);
+    let average =
+        glyphs.iter().map(|g| u32::from(g.advance)).sum::<u32>() / u32::from(num_glyphs.max(1));
+    os2.i16(i16::try_from(average).unwrap_or(i16::MAX));
+    os2.u16(400); // weight
+    os2.u16(5); // width
+    os2.u16(0); // embedding allowed
+    for v in [
+        em_i16 / 2,
+        em_i16 / 2,
+        0,
+        em_i16 / 10,
+        em_i16 / 2,
+        em_i16 / 2,
+        0,
+        em_i16 / 3,
+    ] {
+        os2.i16(v); // subscript and superscript metrics
+    }
+    os2.i16(em_i16 / 20); // strikeout size
+    os2.i16(em_i16 / 4); // strikeout position
+    os2.i16(0); // family class
+    os2.extend_from_slice(&[0; 10]); // panose
+    os2.u32(0);
+    os2.u32(1 << 28); // Private Use Area
+    os2.u32(0);
+    os2.u32(0);
+    os2.extend_from_slice(b"NONE");
+    os2.u16(0x0040); // regular
+    os2.u16(codepoints.iter().copied().min().unwrap_or(0));
+    os2.u16(codepoints.iter().copied().max().unwrap_or(0));
+    os2.i16(em_i16); // typo ascender
+    os2.i16(0); // typo descender
+    os2.i16(0); // typo line gap
+    os2.u16(u16::try_from(y_max.max(0)).unwrap_or(0)); // win ascent
+    os2.u16(u16::try_from(-y_min.min(0)).unwrap_or(0)); // win descent
+    os2.u32(1); // Latin 1 code page
+    os2.u32(0);
+    os2.i16(em_i16 / 2); // x height
+    os2.i16(em_i16); // cap height
+    os2.u16(0); // default char
+    os2.u16(0x20); // break char
+    os2.u16(0); // max context
+
+    let mut post = Vec::new();
+    post.u32(0x0003_0000); // no glyph names
+    post.u32(0); // italic angle
+    post.i16(-(em_i16 / 10)); // underline position
+    post.i16(em_i16 / 20); // underline thickness
+    post.extend_from_slice(&[0; 20]); // fixed pitch and memory hints
+
+    sfnt(vec![
+        (*b"OS/2", os2),
+        (*b"cmap", cmap(mapping)),
+        (*b"glyf", glyf),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+        (*b"name", name_table(&options.family)),
+        (*b"post", post),
+    ])
+}
+
+// =============================================================================
+// WOFF2
+// =============================================================================
+
+/// Index of a table tag in the WOFF2 known-tags list.
+fn woff2_tag_index(tag: [u8; 4]) -> Option<u8> {
+    Some(match &tag {
+        b"cmap" => 0,
+        b"head" => 1,
+        b"hhea" => 2,
+        b"hmtx" => 3,
+        b"maxp" => 4,
+        b"name" => 5,
+        b"OS/2" => 6,
+        b"post" => 7,
+        b"glyf" => 10,
+        b"loca" => 11,
+        _ => return None,
+    })
+}
+
+fn push_base128(out: &mut Vec<u8>, mut value: u32) {
+    let mut bytes = vec![u8::try_from(value & 0x7f).unwrap_or(0)];
+    value >>= 7;
+    while value > 0 {
+        bytes.push(u8::try_from(value & 0x7f).unwrap_or(0) | 0x80);
+        value >>= 7;
+    }
+    out.extend(bytes.iter().rev());
+}
+
+/// Read the tables of an sfnt file written by [`sfnt`].
+fn sfnt_tables(font: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let be_u32 =
+        |at: usize| u32::from_be_bytes([font[at], font[at + 1], font[at + 2], font[at + 3]]);
+    let count = usize::from(u16::from_be_bytes([font[4], font[5]]));
+    (0..count)
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let tag = [
+                font[record],
+                font[record + 1],
+                font[record + 2],
+                font[record + 3],
+            ];
+            let offset = be_u32(record + 8) as usize;
+            let length = be_u32(record + 12) as usize;
+            (tag, &font[offset..offset + length])
+        })
+        .collect()
+}
+
+/// Convert a TrueType font to WOFF2, storing every table untransformed.
+fn woff2(font: &[u8]) -> Vec<u8> {
+    let tables = sfnt_tables(font);
+    let mut directory = Vec::new();
+    let mut data = Vec::new();
+    for (tag, table) in &tables {
+        // Transform version 3 is the null transform for glyf and loca;
+        // version 0 is the null transform for everything else.
+        let transform = if tag == b"glyf" || tag == b"loca" {
+            3 << 6
+        } else {
+            0
+        };
+        if let Some(index) = woff2_tag_index(*tag) {
+            directory.push(index | transform);
+        } else {
+            directory.push(63 | transform);
+            directory.extend_from_slice(tag);
+        }
+        push_base128(&mut directory, len_u32(table.len()));
+        data.extend_from_slice(table);
+    }
+
+    let mut compressed = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        // Writing to a Vec cannot fail
+        let _ = writer.write_all(&data);
+    }
+
+    let header_len = 48;
+    let total = (header_len + directory.len() + compressed.len()).next_multiple_of(4);
+    let mut out = Vec::with_capacity(total);
+    out.extend_from_slice(b"wOF2");
+    out.u32(0x0001_0000);
+    out.u32(len_u32(total));
+    out.u16(len_u16(tables.len()));
+    out.u16(0);
+    out.u32(len_u32(font.len()));
+    out.u32(len_u32(compressed.len()));
+    out.u16(1); // major version
+    out.u16(0); // minor version
+    out.extend_from_slice(&[0; 20]); // no metadata or private data
+    out.extend_from_slice(&directory);
+    out.extend_from_slice(&compressed);
+    out.resize(total, 0);
+    out
+}
+
+// =============================================================================
+// CSS and preview
+// =============================================================================
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn css(glyphs: &[FontGlyph], options: &FontOptions) -> String {
+    let family = options.family.replace('"', "");
+    let name = &options.file_name;
+    let prefix = &options.class_prefix;
+    let mut css = format!(
+        r#"@font-face {{
+  font-family: "{family}";
+  src: url("{name}.woff2") format("woff2"), url("{name}.ttf") format("truetype");
+  font-weight: normal;
+  font-style: normal;
+  font-display: block;
+}}
+
+[class^="{prefix}-"]::before,
+[class*=" {prefix}-"]::before {{
+  font-family: "{family}" !important;
+  font-style: normal;
+  font-weight: normal;
+  font-variant: normal;
+  text-transform: none;
+  line-height: 1;
+  display: inline-block;
+  vertical-align: -0.125em;
+  -webkit-font-smoothing: antialiased;
+  -moz-osx-font-smoothing: grayscale;
+}}
+"#
+    );
+    for glyph in glyphs {
+        let _ = write!(
+            css,
+            "\n.{}::before {{\n  content: \"\\{:x}\";\n}}\n",
+            glyph.class, glyph.codepoint
+        );
+    }
+    css
+}
+
+fn html(glyphs: &[FontGlyph], options: &FontOptions) -> String {
+    let title = escape_html(&options.family);
+    let mut cells = String::new();
+    for glyph in glyphs {
+        let _ = write!(
+            cells,
+            r#"
+    <div class="glyph">
+      <i class="{class}"></i>
+      <code>.{class}</code>
+      <small>{icon} &middot; U+{cp:04X}</small>
+    </div>"#,
+            class = glyph.class,
+            icon = escape_html(&glyph.icon),
+            cp = glyph.codepoint
+        );
+    }
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>{title}</title>
+  <link rel="stylesheet" href="{name}.css">
+  <style>
+    body {{ font-family: system-ui, sans-serif; margin: 2rem; }}
+    .grid {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(12rem, 1fr)); gap: 1rem; }}
+    .glyph {{ display: flex; flex-direction: column; align-items: center; gap: .5rem; padding: 1rem; border: 1px solid #ddd; border-radius: .5rem; }}
+    .glyph i {{ font-size: 2rem; }}
+    .glyph small {{ color: #666; }}
+  </style>
+</head>
+<body>
+  <h1>{title}</h1>
+  <div class="grid">{cells}
+  </div>
+</body>
+</html>
+"#,
+        name = options.file_name
+    )
+}
+
+// =============================================================================
+// Build
+// =============================================================================
+
+/// Build an icon font.
+///
+/// `codepoints` is the mapping from a previous build, or an empty map; the
+/// returned font carries the updated mapping to persist. Icons that cannot
+/// be converted are listed in [`IconFont::skipped`] and get no codepoint.
+///
+/// # Errors
+/// Returns [`FontError::CodepointsExhausted`] when the Private Use Area is
+/// full.
+pub fn build(
+    icons: &[ResolvedIcon],
+    mut codepoints: CodepointMap,
+    options: &FontOptions,
+) -> Result<IconFont, FontError> {
+    let mut glyphs = vec![Glyph {
+        contours: Vec::new(),
+        advance: options.units_per_em,
+    }];
+    let mut entries = Vec::new();
+    let mut mapping = Vec::new();
+    let mut skipped = Vec::new();
+    let mut used_classes = Vec::new();
+
+    for icon in icons {
+        let key = format!("{}:{}", icon.prefix, icon.name);
+        if entries.iter().any(|e: &FontGlyph| e.icon == key) {
+            continue;
+        }
+        let glyph = match icon_glyph(icon, options.units_per_em) {
+            Ok(glyph) => glyph,
+            Err(reason) => {
+                skipped.push(SkippedIcon { icon: key, reason });
+                continue;
+            }
+        };
+        let codepoint = codepoints.assign(&key)?;
+        let base = format!(
+            "{}-{}",
+            options.class_prefix,
+            sprite::safe_id(&options.naming.raw_id(icon))
+        );
+        let class = sprite::unique_id(&base, &mut used_classes);
+        mapping.push((codepoint, len_u16(glyphs.len())));
+        glyphs.push(glyph);
+        entries.push(FontGlyph {
+            icon: key,
+            class,
+            codepoint,
+        });
+    }
+
+    let ttf = ttf(&glyphs, &mapping, options);
+    Ok(IconFont {
+        woff2: woff2(&ttf),
+        ttf,
+        css: css(&entries, options),
+        html: html(&entries, options),
+        codepoints,
+        glyphs: entries,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn icon(name: &str, body: &str) -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: "test".into(),
+            name: name.into(),
+            body: body.into(),
+            width: 24,
+            height: 24,
+            ..Default::default()
+        }
+    }
+
+    /// Records outline commands as text.
+    #[derive(Default)]
+    struct Outline(Vec<String>);
+
+    impl ttf_parser::OutlineBuilder for Outline {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.0.push(format!("M{x} {y}"));
+        }
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.0.push(format!("L{x} {y}"));
+        }
+        fn quad_to(&mut self, _: f32, _: f32, x: f32, y: f32) {
+            self.0.push(format!("Q{x} {y}"));
+        }
+        fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, x: f32, y: f32) {
+            self.0.push(format!("C{x} {y}"));
+        }
+        fn close(&mut self) {
+            self.0.push("Z".into());
+        }
+    }
+
+    #[test]
+    fn codepoint_mapping_is_stable() {
+        let mut map = CodepointMap::parse("# icons\nU+E005 test:a\n\nU+E002 test:b\n").unwrap();
+        assert_eq!(map.assign("test:a").unwrap(), 0xE005);
+        // New icons never take codepoints below the highest one in use
+        assert_eq!(map.assign("test:c").unwrap(), 0xE006);
+        assert_eq!(
+            map.to_text(),
+            "U+E002 test:b\nU+E005 test:a\nU+E006 test:c\n"
+        );
+        assert_eq!(CodepointMap::parse(&map.to_text()).unwrap(), map);
+
+        assert!(matches!(
+            CodepointMap::parse("E001 test:a"),
+            Err(FontError::InvalidMapping { line: 1, .. })
+        ));
+        assert_eq!(
+            CodepointMap::parse("U+E001 a:a\nU+E001 a:b"),
+            Err(FontError::DuplicateCodepoint(0xE001))
+        );
+    }
+
+    #[test]
+    fn builds_a_valid_truetype_font() {
+        let icons = [
+            icon("square", r#"<path d="M0 0h24v24H0z"/>"#),
+            icon("dot", r#"<circle cx="12" cy="12" r="6"/>"#),
+        ];
+        let font = build(&icons, CodepointMap::default(), &FontOptions::default()).unwrap();
+        assert_eq!(font.skipped, []);
+
+        let face = ttf_parser::Face::parse(&font.ttf, 0).unwrap();
+        assert_eq!(face.number_of_glyphs(), 3);
+        assert_eq!(face.units_per_em(), 1000);
+        let family = face
+            .names()
+            .into_iter()
+            .find(|n| n.name_id == ttf_parser::name_id::FAMILY)
+            .and_then(|n| n.to_string());
+        assert_eq!(family.as_deref(), Some("Icons"));
+
+        let square = face.glyph_index('\u{E000}').unwrap();
+        assert_eq!(face.glyph_hor_advance(square), Some(1000));
+        let mut outline = Outline::default();
+        let bbox = face.outline_glyph(square, &mut outline).unwrap();
+        assert_eq!(
+            (bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max),
+            (0, 0, 1000, 1000)
+        );
+        // The closing line is implicit in the font
+        assert_eq!(
+            outline.0,
+            ["M0 1000", "L1000 1000", "L1000 0", "L0 0", "L0 1000", "Z"]
+        );
+
+        let dot = face.glyph_index('\u{E001}').unwrap();
+        let bbox = face.outline_glyph(dot, &mut Outline::default()).unwrap();
+        assert_eq!(
+            (bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max),
+            (250, 250, 750, 750)
+        );
+    }
+
+    #[test]
+    fn woff2_holds_the_same_tables() {
+        let font = build(
+            &[icon("square", r#"<path d="M0 0h24v24H0z"/>"#)],
+            CodepointMap::default(),
+            &FontOptions::default(),
+        )
+        .unwrap();
+        let woff2 = &font.woff2;
+        assert_eq!(&woff2[..4], b"wOF2");
+        assert_eq!(woff2.len() % 4, 0);
+        let be_u32 = |at: usize| {
+            u32::from_be_bytes([woff2[at], woff2[at + 1], woff2[at + 2], woff2[at + 3]])
+        };
+        assert_eq!(be_u32(8) as usize, woff2.len());
+        assert_eq!(be_u32(16) as usize, font.ttf.len());
+
+        // Decode the directory and check the decompressed tables
+        let tables = sfnt_tables(&font.ttf);
+        let mut pos = 48;
+        let mut lengths = Vec::new();
+        for _ in &tables {
+            pos += 1;
+            let mut length = 0u32;
+            loop {
+                let byte = woff2[pos];
+                pos += 1;
+                length = (length << 7) | u32::from(byte & 0x7f);
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            lengths.push(length as usize);
+        }
+        let compressed = &woff2[pos..pos + be_u32(20) as usize];
+        let mut data = Vec::new();
+        brotli::Decompressor::new(compressed, 4096)
+            .read_to_end(&mut data)
+            .unwrap();
+        let expected: Vec<u8> = tables.iter().flat_map(|(_, t)| t.to_vec()).collect();
+        assert_eq!(data, expected);
+        assert_eq!(lengths.iter().sum::<usize>(), expected.len());
+    }
+
+    #[test]
+    fn even_odd_holes_survive() {
+        // A ring drawn with both contours in the same direction
+        let ring = r#"<path fill-rule="evenodd" d="M0 0h24v24H0zM6 6h12v12H6z"/>"#;
+        let glyph = icon_glyph(&icon("ring", ring), 1000).unwrap();
+        let areas: Vec<f64> = glyph
+            .contours
+            .iter()
+            .map(|c| {
+                let points: Vec<Point> =
+                    c.iter().map(|p| (f64::from(p.0), f64::from(p.1))).collect();
+                signed_area(&points)
+            })
+            .collect();
+        assert_eq!(areas.len(), 2);
+        assert!(areas[0] * areas[1] < 0.0, "{areas:?}");
+    }
+
+    #[test]
+    fn skips_icons_fonts_cannot_express() {
+        let icons = [
+            icon("stroked", r#"<path d="M0 0h24" stroke="currentColor"/>"#),
+            icon(
+                "colours",
+                r##"<path fill="#f00" d="M0 0h1v1z"/><path fill="#00f" d="M2 2h1v1z"/>"##,
+            ),
+            icon("text", "<text>A</text>"),
+            icon("empty", r#"<g fill="none"><path d="M0 0h1v1z"/></g>"#),
+            icon("ok", r#"<path d="M0 0h1v1z"/>"#),
+        ];
+        let font = build(&icons, CodepointMap::default(), &FontOptions::default()).unwrap();
+        let reasons: Vec<String> = font.skipped.iter().map(|s| s.reason.to_string()).collect();
+        assert_eq!(
+            reasons,
+            [
+                "drawn with strokes",
+                "uses more than one colour",
+                "contains unsupported <text>",
+                "no filled shapes"
+            ]
+        );
+        assert_eq!(font.glyphs.len(), 1);
+        assert_eq!(font.codepoints.len(), 1);
+    }
+
+    #[test]
+    fn writes_css_and_preview() {
+        let font = build(
+            &[icon("home", r#"<path d="M0 0h24v24H0z"/>"#)],
+            CodepointMap::parse("U+E010 test:home").unwrap(),
+            &FontOptions::default(),
+        )
+        .unwrap();
+        assert!(font
+            .css
+            .contains(r#"src: url("icons.woff2") format("woff2")"#));
+        assert!(font
+            .css
+            .contains(".icon-test-home::before {\n  content: \"\\e010\";\n}"));
+        assert!(font.html.contains(r#"<i class="icon-test-home"></i>"#));
+        assert!(font.html.contains("U+E010"));
+
+        let zip = font.to_zip(&FontOptions::default());
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 5);
+    }
+}
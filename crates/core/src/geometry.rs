@@ -0,0 +1,664 @@
+//! Geometric bounds of icon bodies and viewBox adjustments built on them.
+//!
+//! [`bounding_box`] measures the painted area of a body, including
+//! transforms and stroke widths. [`trim`], [`pad_to_square`] and
+//! [`center_in_grid`] use it to produce adjusted [`ResolvedIcon`]s that
+//! `build_svg` and the snippet generators consume like any other icon.
+
+use std::collections::HashMap;
+
+use crate::path::{self, Command};
+use crate::tree::{self, Element, Node, ParseError};
+use crate::types::ResolvedIcon;
+
+/// Elements whose children are never rendered directly.
+pub(crate) const NON_RENDERED: &[&str] = &[
+    "clipPath",
+    "defs",
+    "desc",
+    "filter",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "pattern",
+    "radialGradient",
+    "script",
+    "style",
+    "symbol",
+    "title",
+];
+
+/// How many nested `<use>` references are followed.
+const MAX_USE_DEPTH: usize = 8;
+
+/// An axis-aligned rectangle in user units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    fn point(x: f64, y: f64) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    #[must_use]
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    /// The smallest box containing both boxes.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    /// Grow the box by `amount` on every side.
+    #[must_use]
+    pub fn expand(self, amount: f64) -> Self {
+        Self {
+            min_x: self.min_x - amount,
+            min_y: self.min_y - amount,
+            max_x: self.max_x + amount,
+            max_y: self.max_y + amount,
+        }
+    }
+}
+
+/// A 2D affine transform `[a, b, c, d, e, f]`, as in SVG's `matrix()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform(pub [f64; 6]);
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[allow(clippy::many_single_char_names)]
+impl Transform {
+    pub const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    /// Parse a `transform` attribute. Returns `None` for malformed input.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut result = Self::IDENTITY;
+        let mut rest = value.trim();
+        while !rest.is_empty() {
+            let open = rest.find('(')?;
+            let close = rest.find(')')?;
+            let name = rest[..open].trim().trim_start_matches(',').trim();
+            let args: Vec<f64> = rest[open + 1..close]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .ok()?;
+            let next = match (name, args.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Self([a, b, c, d, e, f]),
+                ("translate", &[x]) => Self([1.0, 0.0, 0.0, 1.0, x, 0.0]),
+                ("translate", &[x, y]) => Self([1.0, 0.0, 0.0, 1.0, x, y]),
+                ("scale", &[s]) => Self([s, 0.0, 0.0, s, 0.0, 0.0]),
+                ("scale", &[x, y]) => Self([x, 0.0, 0.0, y, 0.0, 0.0]),
+                ("rotate", &[angle]) => Self::rotate(angle),
+                ("rotate", &[angle, cx, cy]) => Self([1.0, 0.0, 0.0, 1.0, cx, cy])
+                    .then(Self::rotate(angle))
+                    .then(Self([1.0, 0.0, 0.0, 1.0, -cx, -cy])),
+                ("skewX", &[angle]) => Self([1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0]),
+                ("skewY", &[angle]) => Self([1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+                _ => return None,
+            };
+            result = result.then(next);
+            rest = rest[close + 1..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+        Some(result)
+    }
+
+    fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+
+    /// `self` followed by `inner`, i.e. the matrix product `self * inner`.
+    #[must_use]
+    pub fn then(self, inner: Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = inner.0;
+        Self([
+            a * a2 + c * b2,
+            b * a2 + d * b2,
+            a * c2 + c * d2,
+            b * c2 + d * d2,
+            a * e2 + c * f2 + e,
+            b * e2 + d * f2 + f,
+        ])
+    }
+
+    /// Map a point.
+    #[must_use]
+    pub fn apply(self, x: f64, y: f64) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.0;
+        (a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// Average scale factor, used to scale stroke widths.
+    fn scale(self) -> f64 {
+        let [a, b, c, d, ..] = self.0;
+        (a * d - b * c).abs().sqrt()
+    }
+}
+
+/// Painting state inherited from ancestors.
+#[derive(Clone, Copy)]
+struct Context {
+    transform: Transform,
+    stroked: bool,
+    stroke_width: f64,
+}
+
+struct Measure<'a> {
+    ids: HashMap<&'a str, &'a Element>,
+    bbox: Option<BoundingBox>,
+}
+
+impl<'a> Measure<'a> {
+    fn add(&mut self, b: BoundingBox) {
+        self.bbox = Some(self.bbox.map_or(b, |a| a.union(b)));
+    }
+
+    fn visit(&mut self, el: &'a Element, parent: Context, depth: usize) {
+        if NON_RENDERED.contains(&el.name.as_str()) || property(el, "display") == Some("none") {
+            return;
+        }
+        let mut ctx = parent;
+        if let Some(t) = el.attr("transform").and_then(Transform::parse) {
+            ctx.transform = ctx.transform.then(t);
+        }
+        if let Some(stroke) = property(el, "stroke") {
+            ctx.stroked = stroke != "none";
+        }
+        if let Some(width) = property(el, "stroke-width").and_then(parse_length) {
+            ctx.stroke_width = width;
+        }
+
+        match el.name.as_str() {
+            "use" => {
+                let target = el
+                    .attr("href")
+                    .or_else(|| el.attr("xlink:href"))
+                    .and_then(|href| href.strip_prefix('#'))
+                    .and_then(|id| self.ids.get(id).copied());
+                if let Some(target) = target.filter(|_| depth < MAX_USE_DEPTH) {
+                    let (x, y) = (number(el, "x"), number(el, "y"));
+                    ctx.transform = ctx.transform.then(Transform([1.0, 0.0, 0.0, 1.0, x, y]));
+                    if target.name == "symbol" {
+                        for child in target.child_elements() {
+                            self.visit(child, ctx, depth + 1);
+                        }
+                    } else {
+                        self.visit(target, ctx, depth + 1);
+                    }
+                }
+            }
+            "g" | "svg" | "a" | "switch" => {
+                for child in el.child_elements() {
+                    self.visit(child, ctx, depth);
+                }
+            }
+            _ => {
+                if let Some(shape) = shape_bounds(el, ctx.transform) {
+                    let stroke = if ctx.stroked {
+                        ctx.stroke_width * ctx.transform.scale() / 2.0
+                    } else {
+                        0.0
+                    };
+                    self.add(shape.expand(stroke));
+                }
+            }
+        }
+    }
+}
+
+/// A presentation attribute or `style` declaration, the latter taking priority.
+pub(crate) fn property<'e>(el: &'e Element, name: &str) -> Option<&'e str> {
+    let from_style = el.attr("style").and_then(|style| {
+        style.split(';').rev().find_map(|declaration| {
+            let (key, value) = declaration.split_once(':')?;
+            (key.trim() == name).then(|| value.trim().trim_end_matches("!important").trim())
+        })
+    });
+    from_style.or_else(|| el.attr(name).map(str::trim))
+}
+
+/// Parse a length in user units, accepting a `px` suffix.
+fn parse_length(value: &str) -> Option<f64> {
+    value.trim().trim_end_matches("px").parse().ok()
+}
+
+fn number(el: &Element, name: &str) -> f64 {
+    el.attr(name).and_then(parse_length).unwrap_or(0.0)
+}
+
+/// Path data equivalent to a path or basic shape element, or `None` for
+/// elements that draw no geometry of their own.
+#[allow(clippy::many_single_char_names)]
+pub(crate) fn shape_path(el: &Element) -> Option<String> {
+    let n = |name| number(el, name);
+    let d = match el.name.as_str() {
+        "path" => el.attr("d")?.to_string(),
+        "rect" => {
+            let (x, y, w, h) = (n("x"), n("y"), n("width"), n("height"));
+            if w <= 0.0 || h <= 0.0 {
+                return None;
+            }
+            let rx = el
+                .attr("rx")
+                .or_else(|| el.attr("ry"))
+                .and_then(parse_length);
+            let ry = el
+                .attr("ry")
+                .or_else(|| el.attr("rx"))
+                .and_then(parse_length);
+            let (rx, ry) = (
+                rx.unwrap_or(0.0).clamp(0.0, w / 2.0),
+                ry.unwrap_or(0.0).clamp(0.0, h / 2.0),
+            );
+            if rx > 0.0 && ry > 0.0 {
+                let (iw, ih) = (w - 2.0 * rx, h - 2.0 * ry);
+                format!(
+                    "M{} {y}h{iw}a{rx} {ry} 0 0 1 {rx} {ry}v{ih}a{rx} {ry} 0 0 1 {} {ry}h{}a{rx} {ry} 0 0 1 {} {}v{}a{rx} {ry} 0 0 1 {rx} {}z",
+                    x + rx,
+                    -rx,
+                    -iw,
+                    -rx,
+                    -ry,
+                    -ih,
+                    -ry
+                )
+            } else {
+                format!("M{x} {y}h{w}v{h}h{}z", -w)
+            }
+        }
+        "circle" | "ellipse" => {
+            let (rx, ry) = if el.name == "circle" {
+                (n("r"), n("r"))
+            } else {
+                (n("rx"), n("ry"))
+            };
+            if rx <= 0.0 || ry <= 0.0 {
+                return None;
+            }
+            let (cx, cy) = (n("cx"), n("cy"));
+            format!(
+                "M{} {cy}A{rx} {ry} 0 1 0 {} {cy}A{rx} {ry} 0 1 0 {} {cy}z",
+                cx - rx,
+                cx + rx,
+                cx - rx
+            )
+        }
+        "line" => format!("M{} {}L{} {}", n("x1"), n("y1"), n("x2"), n("y2")),
+        "polyline" => format!("M{}", el.attr("points")?),
+        "polygon" => format!("M{}z", el.attr("points")?),
+        // Text and images cannot be measured without fonts or image data
+        _ => return None,
+    };
+    Some(d)
+}
+
+/// Exact bounds of a basic shape or path after transforming it.
+fn shape_bounds(el: &Element, transform: Transform) -> Option<BoundingBox> {
+    path_bounds(&path::parse(&shape_path(el)?).ok()?, transform)
+}
+
+/// Exact bounds of a path. Transforming control points first is valid
+/// because Bezier curves are invariant under affine maps.
+fn path_bounds(segments: &[path::Segment], transform: Transform) -> Option<BoundingBox> {
+    let mut bbox: Option<BoundingBox> = None;
+    let mut current = (0.0, 0.0);
+    for segment in path::to_cubics(segments) {
+        let a = &segment.args;
+        match segment.command {
+            Command::MoveTo => current = transform.apply(a[0], a[1]),
+            Command::CubicTo => {
+                let p1 = transform.apply(a[0], a[1]);
+                let p2 = transform.apply(a[2], a[3]);
+                let p3 = transform.apply(a[4], a[5]);
+                let curve = cubic_bounds(current, p1, p2, p3);
+                bbox = Some(bbox.map_or(curve, |b| b.union(curve)));
+                current = p3;
+            }
+            _ => {}
+        }
+    }
+    bbox
+}
+
+/// Exact bounds of one cubic Bezier curve.
+fn cubic_bounds(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64)) -> BoundingBox {
+    let mut bbox = BoundingBox::point(p0.0, p0.1).union(BoundingBox::point(p3.0, p3.1));
+    let at = |t: f64, a: f64, b: f64, c: f64, d: f64| {
+        let mt = 1.0 - t;
+        mt * mt * mt * a + 3.0 * mt * mt * t * b + 3.0 * mt * t * t * c + t * t * t * d
+    };
+    for t in extrema(p0.0, p1.0, p2.0, p3.0)
+        .into_iter()
+        .chain(extrema(p0.1, p1.1, p2.1, p3.1))
+    {
+        let x = at(t, p0.0, p1.0, p2.0, p3.0);
+        let y = at(t, p0.1, p1.1, p2.1, p3.1);
+        bbox = bbox.union(BoundingBox::point(x, y));
+    }
+    bbox
+}
+
+/// Parameters in `(0, 1)` where one coordinate of a cubic has a turning point.
+fn extrema(p0: f64, p1: f64, p2: f64, p3: f64) -> Vec<f64> {
+    // Derivative divided by 3: a t^2 + b t + c
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+    let roots = if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            vec![]
+        } else {
+            vec![-c / b]
+        }
+    } else {
+        let disc = b * b - 4.0 * a * c;
+        if disc < 0.0 {
+            vec![]
+        } else {
+            let sqrt = disc.sqrt();
+            vec![(-b + sqrt) / (2.0 * a), (-b - sqrt) / (2.0 * a)]
+        }
+    };
+    roots.into_iter().filter(|t| *t > 0.0 && *t < 1.0).collect()
+}
+
+/// Painted bounds of a body, including stroke widths, or `None` if nothing
+/// measurable is drawn.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn bounding_box(body: &str) -> Result<Option<BoundingBox>, ParseError> {
+    let nodes = tree::parse(body)?;
+    let mut measure = Measure {
+        ids: HashMap::new(),
+        bbox: None,
+    };
+    tree::walk(&nodes, &mut |el| {
+        if let Some(id) = el.attr("id") {
+            measure.ids.insert(id, el);
+        }
+    });
+    let ctx = Context {
+        transform: Transform::IDENTITY,
+        stroked: false,
+        stroke_width: 1.0,
+    };
+    for node in &nodes {
+        if let Node::Element(el) = node {
+            measure.visit(el, ctx, 0);
+        }
+    }
+    Ok(measure.bbox)
+}
+
+/// Painted bounds of an icon after its rotation and flips are applied.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn icon_bounds(icon: &ResolvedIcon) -> Result<Option<BoundingBox>, ParseError> {
+    bounding_box(&crate::svg::apply_transformations(icon).body)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn floor(n: f64) -> i32 {
+    n.floor() as i32
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn ceil_size(n: f64) -> u32 {
+    n.ceil().max(1.0) as u32
+}
+
+/// Round a coordinate for use in a generated transform.
+fn format(n: f64) -> String {
+    crate::svg::format_number((n * 1e4).round() / 1e4)
+}
+
+/// Crop the viewBox to the painted content plus `margin` units on each side.
+///
+/// The viewBox is rounded outwards to whole units. Icons without measurable
+/// content are returned with only their transformations applied.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn trim(icon: &ResolvedIcon, margin: u32) -> Result<ResolvedIcon, ParseError> {
+    let icon = crate::svg::apply_transformations(icon);
+    let Some(bbox) = bounding_box(&icon.body)? else {
+        return Ok(icon);
+    };
+    let bbox = bbox.expand(f64::from(margin));
+    let left = floor(bbox.min_x);
+    let top = floor(bbox.min_y);
+    Ok(ResolvedIcon {
+        left,
+        top,
+        width: ceil_size(bbox.max_x - f64::from(left)),
+        height: ceil_size(bbox.max_y - f64::from(top)),
+        ..icon
+    })
+}
+
+/// Grow the shorter side of the viewBox so the icon becomes square, keeping
+/// the content centred.
+#[must_use]
+pub fn pad_to_square(icon: &ResolvedIcon) -> ResolvedIcon {
+    let icon = crate::svg::apply_transformations(icon);
+    let size = icon.width.max(icon.height);
+    let (dx, dy) = (size - icon.width, size - icon.height);
+    // Odd differences cannot be split evenly in whole units, so the body is
+    // shifted by the remaining half unit.
+    let body = if dx % 2 == 1 || dy % 2 == 1 {
+        format!(
+            r#"<g transform="translate({} {})">{}</g>"#,
+            format(f64::from(dx % 2) / 2.0),
+            format(f64::from(dy % 2) / 2.0),
+            icon.body
+        )
+    } else {
+        icon.body.clone()
+    };
+    ResolvedIcon {
+        body,
+        left: icon.left - i32::try_from(dx / 2).unwrap_or(0),
+        top: icon.top - i32::try_from(dy / 2).unwrap_or(0),
+        width: size,
+        height: size,
+        ..icon
+    }
+}
+
+/// Scale and centre the painted content inside a `grid` x `grid` viewBox,
+/// leaving `padding` units free on every side.
+///
+/// Icons without measurable content only get a square viewBox.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn center_in_grid(
+    icon: &ResolvedIcon,
+    grid: u32,
+    padding: u32,
+) -> Result<ResolvedIcon, ParseError> {
+    let icon = crate::svg::apply_transformations(icon);
+    let bbox = bounding_box(&icon.body)?.filter(|b| b.width() > 0.0 || b.height() > 0.0);
+    let Some(bbox) = bbox else {
+        return Ok(pad_to_square(&icon));
+    };
+    let available = f64::from(grid.saturating_sub(padding.saturating_mul(2)).max(1));
+    let scale = available / bbox.width().max(bbox.height());
+    let grid_center = f64::from(grid) / 2.0;
+    let tx = grid_center - f64::midpoint(bbox.min_x, bbox.max_x) * scale;
+    let ty = grid_center - f64::midpoint(bbox.min_y, bbox.max_y) * scale;
+    let body = format!(
+        r#"<g transform="translate({} {}) scale({})">{}</g>"#,
+        format(tx),
+        format(ty),
+        format(scale),
+        icon.body
+    );
+    Ok(ResolvedIcon {
+        body,
+        left: 0,
+        top: 0,
+        width: grid,
+        height: grid,
+        ..icon
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(body: &str) -> [f64; 4] {
+        let b = bounding_box(body).unwrap().unwrap();
+        [b.min_x, b.min_y, b.max_x, b.max_y].map(|n| (n * 1000.0).round() / 1000.0)
+    }
+
+    fn icon(body: &str, width: u32, height: u32) -> ResolvedIcon {
+        ResolvedIcon {
+            body: body.into(),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn measures_basic_shapes() {
+        assert_eq!(
+            bounds(r#"<rect x="2" y="3" width="4" height="5"/>"#),
+            [2.0, 3.0, 6.0, 8.0]
+        );
+        assert_eq!(
+            bounds(r#"<rect x="2" y="3" width="4" height="5" rx="1"/>"#),
+            [2.0, 3.0, 6.0, 8.0]
+        );
+        assert_eq!(
+            bounds(r#"<circle cx="12" cy="12" r="10"/>"#),
+            [2.0, 2.0, 22.0, 22.0]
+        );
+        assert_eq!(
+            bounds(r#"<ellipse cx="5" cy="5" rx="4" ry="2"/>"#),
+            [1.0, 3.0, 9.0, 7.0]
+        );
+        assert_eq!(
+            bounds(r#"<polygon points="1,1 5,2 3,9"/>"#),
+            [1.0, 1.0, 5.0, 9.0]
+        );
+        assert_eq!(
+            bounds(r#"<line x1="4" y1="1" x2="0" y2="3"/>"#),
+            [0.0, 1.0, 4.0, 3.0]
+        );
+        assert_eq!(
+            bounding_box("<defs><rect width=\"5\" height=\"5\"/></defs>").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn curves_use_exact_bounds() {
+        // The control points reach y=10 but the curve only reaches 7.5
+        assert_eq!(
+            bounds(r#"<path d="M0 0C0 10 10 10 10 0"/>"#),
+            [0.0, 0.0, 10.0, 7.5]
+        );
+        assert_eq!(
+            bounds(r#"<path d="M0 5a5 5 0 0 0 10 0"/>"#),
+            [0.0, 5.0, 10.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn applies_transforms_and_strokes() {
+        assert_eq!(
+            bounds(r#"<g transform="translate(10 0) scale(2)"><rect width="1" height="1"/></g>"#),
+            [10.0, 0.0, 12.0, 2.0]
+        );
+        assert_eq!(
+            bounds(r#"<rect width="2" height="2" transform="rotate(90 1 1)"/>"#),
+            [0.0, 0.0, 2.0, 2.0]
+        );
+        assert_eq!(
+            bounds(r#"<g stroke="currentColor" stroke-width="2"><path d="M2 2h4"/></g>"#),
+            [1.0, 1.0, 7.0, 3.0]
+        );
+        assert_eq!(
+            bounds(r##"<defs><path id="a" d="M0 0h1v1z"/></defs><use href="#a" x="3"/>"##),
+            [3.0, 0.0, 4.0, 1.0]
+        );
+        assert_eq!(Transform::parse("skewX(foo)"), None);
+    }
+
+    #[test]
+    fn trims_to_content() {
+        let trimmed = trim(
+            &icon(r#"<rect x="4.5" y="6" width="10" height="8"/>"#, 24, 24),
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            (trimmed.left, trimmed.top, trimmed.width, trimmed.height),
+            (3, 5, 13, 10)
+        );
+        let empty = trim(&icon("<g/>", 24, 24), 0).unwrap();
+        assert_eq!((empty.width, empty.height), (24, 24));
+    }
+
+    #[test]
+    fn pads_to_square() {
+        let square = pad_to_square(&icon("<path/>", 20, 15));
+        assert_eq!(
+            (square.left, square.top, square.width, square.height),
+            (0, -2, 20, 20)
+        );
+        assert_eq!(
+            square.body,
+            r#"<g transform="translate(0 0.5)"><path/></g>"#
+        );
+        assert_eq!(pad_to_square(&icon("<path/>", 16, 16)).body, "<path/>");
+    }
+
+    #[test]
+    fn centres_in_grid() {
+        let body = r#"<rect x="10" y="10" width="20" height="10"/>"#;
+        let centred = center_in_grid(&icon(body, 48, 48), 24, 2).unwrap();
+        assert_eq!((centred.width, centred.height), (24, 24));
+        assert_eq!(bounds(&centred.body), [2.0, 7.0, 22.0, 17.0]);
+        assert!(crate::svg::build_svg(&centred).contains(r#"viewBox="0 0 24 24""#));
+        // Padding larger than the grid leaves a single unit, without overflow
+        assert!(center_in_grid(&icon(body, 48, 48), 24, 1 << 31).is_ok());
+    }
+}
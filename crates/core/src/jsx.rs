@@ -0,0 +1,280 @@
+//! SVG bodies as JSX.
+//!
+//! Attribute names, `style` attributes, text and comments are converted for
+//! the JSX dialect of each framework: React wants camelCase DOM property
+//! names, `className` and style objects, while Solid and Qwik pass attribute
+//! names through to the DOM and keep `class`.
+
+use std::fmt::Write;
+
+use crate::tree::{self, Node};
+
+/// JSX dialect of a framework.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsxFlavor {
+    /// React and plain JSX: camelCase attributes, `className`, camelCase
+    /// style objects.
+    #[default]
+    React,
+    /// Solid: attribute names as in SVG, `class`, kebab-case style objects.
+    Solid,
+    /// Qwik: attribute names as in SVG, `class`, camelCase style objects.
+    Qwik,
+}
+
+impl JsxFlavor {
+    /// Parse a flavor name, ignoring case.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "react" | "jsx" => Some(Self::React),
+            "solid" => Some(Self::Solid),
+            "qwik" => Some(Self::Qwik),
+            _ => None,
+        }
+    }
+}
+
+/// Convert `kebab-case` to `camelCase`.
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' || c == ':' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The JSX name of an SVG attribute.
+///
+/// React camelCases hyphenated and namespaced names (`stroke-width` →
+/// `strokeWidth`, `xlink:href` → `xlinkHref`) except `aria-*` and `data-*`,
+/// and renames `class` to `className`.
+#[must_use]
+pub fn attribute_name(name: &str, flavor: JsxFlavor) -> String {
+    match flavor {
+        JsxFlavor::React => match name {
+            "class" => "className".to_string(),
+            "for" => "htmlFor".to_string(),
+            "tabindex" => "tabIndex".to_string(),
+            "crossorigin" => "crossOrigin".to_string(),
+            _ if name.starts_with("aria-") || name.starts_with("data-") => name.to_string(),
+            _ => camel_case(name),
+        },
+        JsxFlavor::Solid | JsxFlavor::Qwik => name.to_string(),
+    }
+}
+
+/// Split a declaration list at `;`, ignoring semicolons inside parentheses
+/// and quotes (as in `url(data:…;base64,…)`).
+fn split_declarations(style: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in style.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                parts.push(&style[start..i]);
+                start = i + 1;
+            }
+            // Anything else, including text inside quotes
+            _ => {}
+        }
+    }
+    parts.push(&style[start..]);
+    parts
+}
+
+fn js_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A JavaScript object literal for a `style` attribute, e.g.
+/// `{ fill: "red", strokeWidth: "2" }`.
+///
+/// Custom properties keep their names. React and Qwik use camelCase keys
+/// (`-webkit-` becomes `Webkit`, `-ms-` becomes `ms`); Solid uses CSS names.
+#[must_use]
+pub fn style_object(style: &str, flavor: JsxFlavor) -> String {
+    let entries: Vec<String> = split_declarations(style)
+        .into_iter()
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let (property, value) = (property.trim(), value.trim());
+            if property.is_empty() {
+                return None;
+            }
+            let key = if property.starts_with("--") || flavor == JsxFlavor::Solid {
+                property.to_string()
+            } else if let Some(rest) = property.strip_prefix("-ms-") {
+                camel_case(&format!("ms-{rest}"))
+            } else if let Some(rest) = property.strip_prefix('-') {
+                let name = camel_case(rest);
+                let mut chars = name.chars();
+                chars.next().map_or_else(String::new, |c| {
+                    c.to_ascii_uppercase().to_string() + chars.as_str()
+                })
+            } else {
+                camel_case(&property.to_ascii_lowercase())
+            };
+            let is_identifier = key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+                && !key.starts_with(|c: char| c.is_ascii_digit());
+            let key = if is_identifier { key } else { js_string(&key) };
+            Some(format!("{key}: {}", js_string(value)))
+        })
+        .collect();
+    if entries.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", entries.join(", "))
+    }
+}
+
+/// Escape text content for JSX, where `{`, `}` and `<` are syntax and
+/// entities are decoded.
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '{' => out.push_str("{'{'}"),
+            '}' => out.push_str("{'}'}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A template literal holding raw text, for `<style>` and `<script>`.
+fn template_literal(text: &str) -> String {
+    format!(
+        "{{`{}`}}",
+        text.replace('\\', "\\\\")
+            .replace('`', "\\`")
+            .replace("${", "\\${")
+    )
+}
+
+fn write_nodes(nodes: &[Node], flavor: JsxFlavor, raw_text: bool, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Element(el) => {
+                out.push('<');
+                out.push_str(&el.name);
+                for attr in &el.attributes {
+                    let name = attribute_name(&attr.name, flavor);
+                    if attr.name == "style" {
+                        let _ = write!(out, " {name}={{{}}}", style_object(&attr.value, flavor));
+                    } else {
+                        let _ = write!(out, r#" {name}="{}""#, tree::escape_attr(&attr.value));
+                    }
+                }
+                if el.children.is_empty() {
+                    out.push_str("/>");
+                } else {
+                    out.push('>');
+                    let raw = matches!(el.name.as_str(), "style" | "script");
+                    write_nodes(&el.children, flavor, raw, out);
+                    let _ = write!(out, "</{}>", el.name);
+                }
+            }
+            Node::Text(text) | Node::CData(text) => {
+                if raw_text {
+                    out.push_str(&template_literal(text));
+                } else {
+                    out.push_str(&escape_text(text));
+                }
+            }
+            Node::Comment(text) => {
+                let _ = write!(out, "{{/*{}*/}}", text.replace("*/", "* /"));
+            }
+        }
+    }
+}
+
+/// Convert an SVG body to JSX for a framework.
+///
+/// Bodies that cannot be parsed are returned unchanged.
+#[must_use]
+pub fn body_to_jsx(body: &str, flavor: JsxFlavor) -> String {
+    let Ok(nodes) = tree::parse(body) else {
+        return body.to_string();
+    };
+    let mut out = String::with_capacity(body.len());
+    write_nodes(&nodes, flavor, false, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_attributes_for_react() {
+        let body = r##"<path class="a" stroke-dasharray="2 1" fill-rule="evenodd" clip-path="url(#clip-path)" aria-hidden="true" data-name="x"/><stop stop-color="red"/><use xlink:href="#a"/><text xml:space="preserve">a</text>"##;
+        assert_eq!(
+            body_to_jsx(body, JsxFlavor::React),
+            r##"<path className="a" strokeDasharray="2 1" fillRule="evenodd" clipPath="url(#clip-path)" aria-hidden="true" data-name="x"/><stop stopColor="red"/><use xlinkHref="#a"/><text xmlSpace="preserve">a</text>"##
+        );
+        // Solid and Qwik keep DOM attribute names
+        assert_eq!(body_to_jsx(body, JsxFlavor::Solid), body);
+        assert_eq!(body_to_jsx(body, JsxFlavor::Qwik), body);
+    }
+
+    #[test]
+    fn converts_styles_to_objects() {
+        let style = "fill: red; stroke-width:2;--accent: #f00;-webkit-mask: url(data:image/svg+xml;base64,AA==); -ms-transform: none;";
+        assert_eq!(
+            style_object(style, JsxFlavor::React),
+            r##"{ fill: "red", strokeWidth: "2", "--accent": "#f00", WebkitMask: "url(data:image/svg+xml;base64,AA==)", msTransform: "none" }"##
+        );
+        assert_eq!(
+            style_object("stroke-width: 2", JsxFlavor::Solid),
+            r#"{ "stroke-width": "2" }"#
+        );
+        assert_eq!(
+            body_to_jsx(r#"<g style="opacity:.5"/>"#, JsxFlavor::Qwik),
+            r#"<g style={{ opacity: ".5" }}/>"#
+        );
+    }
+
+    #[test]
+    fn escapes_text_and_comments() {
+        let body =
+            "<!-- a */ b --><text>{x} &lt; y</text><style>.a{fill:red}\n.b{content:'`'}</style>";
+        assert_eq!(
+            body_to_jsx(body, JsxFlavor::React),
+            "{/* a * / b */}<text>{'{'}x{'}'} &lt; y</text><style>{`.a{fill:red}\n.b{content:'\\`'}`}</style>"
+        );
+        assert_eq!(body_to_jsx("<path", JsxFlavor::React), "<path");
+    }
+}
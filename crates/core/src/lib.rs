@@ -1,4 +1,28 @@
+pub mod archive;
+pub mod color;
+pub mod css;
+pub mod data_uri;
+#[cfg(feature = "raster")]
+pub mod favicon;
+#[cfg(feature = "font")]
+pub mod font;
+pub mod geometry;
+pub mod jsx;
+pub mod optimize;
+pub mod path;
+pub mod path_code;
+#[cfg(feature = "raster")]
+pub mod raster;
+pub mod rsx;
+pub mod sanitize;
 pub mod search;
+pub mod shapes;
+pub mod slint;
 pub mod snippets;
+pub mod sprite;
 pub mod svg;
+pub mod template;
+pub mod tree;
 pub mod types;
+pub mod vector_drawable;
+pub mod view_macro;
@@ -0,0 +1,629 @@
+//! SVGO-style optimizer for icon bodies and SVG documents.
+//!
+//! Works on the element tree from [`crate::tree`] and runs a configurable set
+//! of passes, reporting how many bytes each one saved.
+
+use crate::path::{self, Command};
+use crate::tree::{self, Element, Node, ParseError};
+use crate::types::ResolvedIcon;
+
+/// Which optimization passes to run.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizeOptions {
+    /// Drop comments, except `<!--! ... -->` legal comments.
+    pub remove_comments: bool,
+    /// Drop `<metadata>` elements.
+    pub remove_metadata: bool,
+    /// Drop Inkscape, Illustrator, Sketch and similar editor namespaces.
+    pub remove_editor_data: bool,
+    /// Drop attributes that repeat an inherited or default value.
+    pub remove_default_attributes: bool,
+    /// Unwrap groups that carry no attributes or only one child.
+    pub collapse_groups: bool,
+    /// Merge adjacent, non-overlapping paths with identical styling.
+    pub merge_paths: bool,
+    /// Rewrite `d` attributes in their shortest form.
+    pub shorten_path_data: bool,
+    /// Decimal places kept for numbers; `None` keeps them as written.
+    pub precision: Option<u8>,
+}
+
+impl Default for OptimizeOptions {
+    fn default() -> Self {
+        Self {
+            remove_comments: true,
+            remove_metadata: true,
+            remove_editor_data: true,
+            remove_default_attributes: true,
+            collapse_groups: true,
+            merge_paths: true,
+            shorten_path_data: true,
+            precision: Some(3),
+        }
+    }
+}
+
+/// Bytes saved by a single pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassSaving {
+    pub pass: &'static str,
+    pub bytes: usize,
+}
+
+/// Size summary of an optimization run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptimizeReport {
+    pub original_bytes: usize,
+    pub optimized_bytes: usize,
+    /// Savings of every pass that ran, in order.
+    pub passes: Vec<PassSaving>,
+}
+
+impl OptimizeReport {
+    /// Total bytes saved.
+    #[must_use]
+    pub fn saved_bytes(&self) -> usize {
+        self.original_bytes.saturating_sub(self.optimized_bytes)
+    }
+
+    /// Percentage of the original size that was saved.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn saved_percent(&self) -> f64 {
+        if self.original_bytes == 0 {
+            return 0.0;
+        }
+        self.saved_bytes() as f64 * 100.0 / self.original_bytes as f64
+    }
+}
+
+/// Optimized markup together with its report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optimized {
+    pub markup: String,
+    pub report: OptimizeReport,
+}
+
+/// Optimize an SVG body or document.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the markup is not well-formed.
+pub fn optimize(markup: &str, options: &OptimizeOptions) -> Result<Optimized, ParseError> {
+    let mut nodes = tree::parse(markup)?;
+    let mut report = OptimizeReport {
+        original_bytes: markup.len(),
+        ..Default::default()
+    };
+    let mut size = markup.len();
+
+    let mut run = |name: &'static str, nodes: &mut Vec<Node>, pass: &dyn Fn(&mut Vec<Node>)| {
+        pass(nodes);
+        let new_size = tree::to_markup(nodes).len();
+        report.passes.push(PassSaving {
+            pass: name,
+            bytes: size.saturating_sub(new_size),
+        });
+        size = new_size;
+    };
+
+    run("whitespace", &mut nodes, &strip_whitespace);
+    if options.remove_comments {
+        run("comments", &mut nodes, &remove_comments);
+    }
+    if options.remove_metadata {
+        run("metadata", &mut nodes, &|nodes| {
+            retain_elements(nodes, &|el| el.name != "metadata");
+        });
+    }
+    if options.remove_editor_data {
+        run("editor data", &mut nodes, &remove_editor_data);
+    }
+    if options.remove_default_attributes {
+        run("default attributes", &mut nodes, &|nodes| {
+            remove_default_attributes(nodes);
+        });
+    }
+    if options.collapse_groups {
+        run("collapse groups", &mut nodes, &collapse_groups);
+    }
+    if let Some(precision) = options.precision {
+        run("precision", &mut nodes, &|nodes| {
+            round_attributes(nodes, precision);
+        });
+    }
+    if options.merge_paths {
+        run("merge paths", &mut nodes, &merge_paths);
+    }
+    if options.shorten_path_data {
+        let precision = options.precision;
+        run("path data", &mut nodes, &|nodes| {
+            shorten_path_data(nodes, precision);
+        });
+    }
+
+    let markup = tree::to_markup(&nodes);
+    report.optimized_bytes = markup.len();
+    Ok(Optimized { markup, report })
+}
+
+/// Optimize the body of an icon, leaving every other field untouched.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn optimize_icon(
+    icon: &ResolvedIcon,
+    options: &OptimizeOptions,
+) -> Result<(ResolvedIcon, OptimizeReport), ParseError> {
+    let optimized = optimize(&icon.body, options)?;
+    let icon = ResolvedIcon {
+        body: optimized.markup,
+        ..icon.clone()
+    };
+    Ok((icon, optimized.report))
+}
+
+// =============================================================================
+// Passes
+// =============================================================================
+
+/// Elements whose text content is significant.
+const TEXT_ELEMENTS: &[&str] = &["text", "tspan", "textPath", "style", "title", "desc"];
+
+fn strip_whitespace(nodes: &mut Vec<Node>) {
+    nodes.retain(|node| !matches!(node, Node::Text(t) if t.trim().is_empty()));
+    for node in nodes {
+        if let Node::Element(el) = node {
+            if !TEXT_ELEMENTS.contains(&el.name.as_str()) {
+                strip_whitespace(&mut el.children);
+            }
+        }
+    }
+}
+
+fn remove_comments(nodes: &mut Vec<Node>) {
+    nodes.retain(|node| !matches!(node, Node::Comment(c) if !c.starts_with('!')));
+    for node in nodes {
+        if let Node::Element(el) = node {
+            remove_comments(&mut el.children);
+        }
+    }
+}
+
+fn retain_elements(nodes: &mut Vec<Node>, keep: &dyn Fn(&Element) -> bool) {
+    nodes.retain(|node| match node {
+        Node::Element(el) => keep(el),
+        _ => true,
+    });
+    for node in nodes {
+        if let Node::Element(el) = node {
+            retain_elements(&mut el.children, keep);
+        }
+    }
+}
+
+/// Namespace URIs written by design tools.
+const EDITOR_NAMESPACES: &[&str] = &[
+    "http://creativecommons.org/ns#",
+    "http://inkscape.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://ns.adobe.com/AdobeIllustrator/10.0/",
+    "http://ns.adobe.com/AdobeSVGViewerExtensions/3.0/",
+    "http://ns.adobe.com/Extensibility/1.0/",
+    "http://ns.adobe.com/Flows/1.0/",
+    "http://ns.adobe.com/GenericCustomNamespace/1.0/",
+    "http://ns.adobe.com/Graphs/1.0/",
+    "http://ns.adobe.com/ImageReplacement/1.0/",
+    "http://ns.adobe.com/SaveForWeb/1.0/",
+    "http://ns.adobe.com/Variables/1.0/",
+    "http://ns.adobe.com/XPath/1.0/",
+    "http://purl.org/dc/elements/1.1/",
+    "http://schemas.microsoft.com/visio/2003/SVGExtensions/",
+    "http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://taptrix.com/vectorillustrator/svg_extensions",
+    "http://www.bohemiancoding.com/sketch/ns",
+    "http://www.figma.com/figma/ns",
+    "http://www.inkscape.org/namespaces/inkscape",
+    "http://www.serif.com/",
+    "http://www.vector.evaxdesign.sk",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+];
+
+/// Prefixes that are removed even when their declaration is outside the body.
+const EDITOR_PREFIXES: &[&str] = &["sodipodi", "inkscape", "sketch", "serif", "figma"];
+
+fn remove_editor_data(nodes: &mut Vec<Node>) {
+    let mut prefixes: Vec<String> = EDITOR_PREFIXES.iter().map(|p| (*p).to_string()).collect();
+    tree::walk(nodes, &mut |el| {
+        for attr in &el.attributes {
+            if let Some(prefix) = attr.name.strip_prefix("xmlns:") {
+                if EDITOR_NAMESPACES.contains(&attr.value.as_str()) {
+                    prefixes.push(prefix.to_string());
+                }
+            }
+        }
+    });
+
+    let is_editor = |name: &str| {
+        name.split_once(':').is_some_and(|(prefix, local)| {
+            prefixes.iter().any(|p| p == prefix)
+                || (prefix == "xmlns" && prefixes.iter().any(|p| p == local))
+        })
+    };
+    retain_elements(nodes, &|el| !is_editor(&el.name));
+    tree::walk_mut(nodes, &mut |el| {
+        el.attributes.retain(|attr| !is_editor(&attr.name));
+    });
+}
+
+/// Initial values of inherited presentation attributes.
+const INHERITED_DEFAULTS: &[(&str, &str)] = &[
+    ("clip-rule", "nonzero"),
+    ("fill-opacity", "1"),
+    ("fill-rule", "nonzero"),
+    ("stroke", "none"),
+    ("stroke-dasharray", "none"),
+    ("stroke-dashoffset", "0"),
+    ("stroke-linecap", "butt"),
+    ("stroke-linejoin", "miter"),
+    ("stroke-miterlimit", "4"),
+    ("stroke-opacity", "1"),
+    ("stroke-width", "1"),
+    ("visibility", "visible"),
+];
+
+/// Defaults of attributes that are not inherited, per element.
+const ELEMENT_DEFAULTS: &[(&str, &[&str], &str)] = &[
+    ("opacity", &[], "1"),
+    ("x", &["rect", "image", "use", "svg", "pattern"], "0"),
+    ("y", &["rect", "image", "use", "svg", "pattern"], "0"),
+    ("cx", &["circle", "ellipse"], "0"),
+    ("cy", &["circle", "ellipse"], "0"),
+    ("x1", &["line"], "0"),
+    ("y1", &["line"], "0"),
+    ("x2", &["line"], "0"),
+    ("y2", &["line"], "0"),
+];
+
+type Inherited<'a> = Vec<(&'a str, String)>;
+
+fn remove_default_attributes(nodes: &mut [Node]) {
+    // A body's surroundings are unknown, so only a root `<svg>` starts from
+    // the initial values; otherwise only repeats of an ancestor are removed.
+    let root_is_svg = nodes
+        .iter()
+        .any(|node| matches!(node, Node::Element(el) if el.name == "svg"));
+    let inherited: Inherited = if root_is_svg {
+        INHERITED_DEFAULTS
+            .iter()
+            .map(|(name, value)| (*name, (*value).to_string()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    remove_defaults_in(nodes, &inherited);
+}
+
+fn remove_defaults_in(nodes: &mut [Node], inherited: &Inherited) {
+    for node in nodes {
+        let Node::Element(el) = node else { continue };
+        let name = el.name.clone();
+        el.attributes.retain(|attr| {
+            let inherited_value = inherited
+                .iter()
+                .find(|(n, _)| *n == attr.name)
+                .map(|(_, v)| v.as_str());
+            if inherited_value == Some(attr.value.as_str()) {
+                return false;
+            }
+            !ELEMENT_DEFAULTS.iter().any(|(n, elements, value)| {
+                *n == attr.name
+                    && (elements.is_empty() || elements.contains(&name.as_str()))
+                    && *value == attr.value
+            })
+        });
+
+        let mut scope = inherited.clone();
+        for attr in &el.attributes {
+            if let Some((n, _)) = INHERITED_DEFAULTS.iter().find(|(n, _)| *n == attr.name) {
+                scope.retain(|(existing, _)| existing != n);
+                scope.push((n, attr.value.clone()));
+            }
+        }
+        remove_defaults_in(&mut el.children, &scope);
+    }
+}
+
+/// Group attributes that cannot be pushed down onto a single child.
+const NON_MOVABLE_ATTRIBUTES: &[&str] = &[
+    "id",
+    "class",
+    "style",
+    "opacity",
+    "clip-path",
+    "mask",
+    "filter",
+];
+
+fn collapse_groups(nodes: &mut Vec<Node>) {
+    for node in nodes.iter_mut() {
+        if let Node::Element(el) = node {
+            collapse_groups(&mut el.children);
+        }
+    }
+
+    let mut result = Vec::with_capacity(nodes.len());
+    for node in nodes.drain(..) {
+        match node {
+            Node::Element(mut g) if g.name == "g" => {
+                if g.attributes.is_empty() {
+                    result.extend(g.children);
+                    continue;
+                }
+                let movable = g
+                    .attributes
+                    .iter()
+                    .all(|a| !NON_MOVABLE_ATTRIBUTES.contains(&a.name.as_str()));
+                match g.children.as_mut_slice() {
+                    [Node::Element(child)] if movable && child.attr("id").is_none() => {
+                        for attr in g.attributes.drain(..) {
+                            if attr.name == "transform" {
+                                let value = match child.attr("transform") {
+                                    Some(inner) => format!("{} {inner}", attr.value),
+                                    None => attr.value,
+                                };
+                                child.set_attr("transform", value);
+                            } else if child.attr(&attr.name).is_none() {
+                                child.set_attr(&attr.name, attr.value);
+                            }
+                        }
+                        result.extend(g.children);
+                    }
+                    _ => result.push(Node::Element(g)),
+                }
+            }
+            other => result.push(other),
+        }
+    }
+    *nodes = result;
+}
+
+/// Attributes holding one number.
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "cx",
+    "cy",
+    "r",
+    "rx",
+    "ry",
+    "fx",
+    "fy",
+    "width",
+    "height",
+    "stroke-width",
+];
+
+/// Attributes holding a list of numbers.
+const NUMBER_LIST_ATTRIBUTES: &[&str] = &["points", "viewBox"];
+
+fn round_attributes(nodes: &mut [Node], precision: u8) {
+    tree::walk_mut(nodes, &mut |el| {
+        for attr in &mut el.attributes {
+            if NUMERIC_ATTRIBUTES.contains(&attr.name.as_str()) {
+                if let Ok(n) = attr.value.trim().parse::<f64>() {
+                    attr.value = path::format_number(n, Some(precision));
+                }
+            } else if NUMBER_LIST_ATTRIBUTES.contains(&attr.name.as_str()) {
+                let numbers: Option<Vec<f64>> = attr
+                    .value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse().ok())
+                    .collect();
+                if let Some(numbers) = numbers {
+                    attr.value = numbers
+                        .iter()
+                        .map(|n| path::format_number(*n, Some(precision)))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                }
+            }
+        }
+    });
+}
+
+/// Styling that makes overlapping or merged paths render differently.
+fn blocks_merge(name: &str) -> bool {
+    matches!(
+        name,
+        "id" | "style"
+            | "opacity"
+            | "fill-opacity"
+            | "stroke-opacity"
+            | "clip-path"
+            | "mask"
+            | "filter"
+    ) || name.starts_with("marker")
+}
+
+fn merge_paths(nodes: &mut Vec<Node>) {
+    let mut result: Vec<Node> = Vec::with_capacity(nodes.len());
+    for node in nodes.drain(..) {
+        let merged = match (&node, result.last_mut()) {
+            (Node::Element(next), Some(Node::Element(prev))) => try_merge(prev, next),
+            _ => false,
+        };
+        if !merged {
+            result.push(node);
+        }
+    }
+    for node in &mut result {
+        if let Node::Element(el) = node {
+            merge_paths(&mut el.children);
+        }
+    }
+    *nodes = result;
+}
+
+/// Append `next` to `prev` if both are mergeable paths that do not overlap.
+fn try_merge(prev: &mut Element, next: &Element) -> bool {
+    let mergeable = |el: &Element| {
+        el.name == "path"
+            && el.children.is_empty()
+            && el.attr("d").is_some()
+            && !el.attributes.iter().any(|a| blocks_merge(&a.name))
+    };
+    if !mergeable(prev) || !mergeable(next) {
+        return false;
+    }
+    if styling(prev) != styling(next) {
+        return false;
+    }
+
+    let (Ok(mut a), Ok(mut b)) = (
+        path::parse(prev.attr("d").unwrap_or_default()),
+        path::parse(next.attr("d").unwrap_or_default()),
+    ) else {
+        return false;
+    };
+    match (path::control_bounds(&a), path::control_bounds(&b)) {
+        (Some(box_a), Some(box_b)) if !overlaps(box_a, box_b) => {}
+        _ => return false,
+    }
+
+    if let Some(first) = b.first_mut() {
+        // The first moveto of a path is absolute even when written as `m`.
+        if first.command == Command::MoveTo {
+            first.relative = false;
+        }
+    }
+    a.extend(b);
+    prev.set_attr("d", path::write(&a, None));
+    true
+}
+
+/// Attributes other than `d`, sorted so they can be compared.
+fn styling(el: &Element) -> Vec<(&str, &str)> {
+    let mut attrs: Vec<(&str, &str)> = el
+        .attributes
+        .iter()
+        .filter(|a| a.name != "d")
+        .map(|a| (a.name.as_str(), a.value.as_str()))
+        .collect();
+    attrs.sort_unstable();
+    attrs
+}
+
+fn overlaps(a: [f64; 4], b: [f64; 4]) -> bool {
+    a[0] <= b[2] && b[0] <= a[2] && a[1] <= b[3] && b[1] <= a[3]
+}
+
+fn shorten_path_data(nodes: &mut [Node], precision: Option<u8>) {
+    tree::walk_mut(nodes, &mut |el| {
+        if let Some(d) = el.attr("d") {
+            if let Ok(short) = path::minify(d, precision) {
+                if short.len() <= d.len() {
+                    el.set_attr("d", short);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> String {
+        optimize(input, &OptimizeOptions::default()).unwrap().markup
+    }
+
+    #[test]
+    fn removes_comments_metadata_and_editor_data() {
+        let input = r#"<svg xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd" inkscape:version="1.0"><!-- drawn --><!--! keep --><metadata><rdf/></metadata><sodipodi:namedview/><path d="M0 0h1"/></svg>"#;
+        assert_eq!(run(input), r#"<svg><!--! keep --><path d="M0 0h1"/></svg>"#);
+    }
+
+    #[test]
+    fn removes_defaults_only_when_known() {
+        let doc = r#"<svg><path stroke="none" opacity="1" d="M0 0h1"/></svg>"#;
+        assert_eq!(run(doc), r#"<svg><path d="M0 0h1"/></svg>"#);
+
+        // Outside a document the inherited stroke is unknown...
+        let body = r#"<path stroke="none" d="M0 0h1"/>"#;
+        assert_eq!(run(body), body);
+        // ...but repeating an ancestor is always redundant.
+        let body = r#"<g stroke="red" id="a"><path stroke="red" d="M0 0h1"/></g>"#;
+        assert_eq!(
+            run(body),
+            r#"<g stroke="red" id="a"><path d="M0 0h1"/></g>"#
+        );
+    }
+
+    #[test]
+    fn keeps_mask_position() {
+        // A mask's region starts at -10%, so `x="0"` is not a default
+        let body = r##"<mask id="m" x="0" y="0"><rect width="24" height="24" fill="#fff"/></mask><path mask="url(#m)" d="M0 0h1"/>"##;
+        let output = run(body);
+        assert!(output.contains(r#"x="0" y="0""#), "{output}");
+    }
+
+    #[test]
+    fn collapses_groups() {
+        let input = r#"<g><g fill="red" transform="scale(2)"><path transform="rotate(9)" d="M0 0h1"/></g></g>"#;
+        assert_eq!(
+            run(input),
+            r#"<path transform="scale(2) rotate(9)" d="M0 0h1" fill="red"/>"#
+        );
+        let keep = r#"<g opacity=".5"><path d="M0 0h1"/></g>"#;
+        assert_eq!(run(keep), keep);
+    }
+
+    #[test]
+    fn rounds_and_shortens_path_data() {
+        let input =
+            r#"<path d="M 10.12345 0.5 L 20 -0.25 L 30 0.75 z"/><rect x="0" width="2.00004"/>"#;
+        assert_eq!(
+            run(input),
+            r#"<path d="M10.123.5 20-.25l10 1z"/><rect width="2"/>"#
+        );
+    }
+
+    #[test]
+    fn keeps_arc_flags() {
+        let input = r#"<path d="M0 0a5 5 0 1 1 10 0"/>"#;
+        assert_eq!(run(input), input);
+    }
+
+    #[test]
+    fn merges_only_non_overlapping_paths() {
+        let input = r#"<path fill="red" d="M0 0h2v2H0z"/><path fill="red" d="m5 5 1 1"/>"#;
+        assert_eq!(run(input), r#"<path fill="red" d="M0 0h2v2H0zM5 5l1 1"/>"#);
+
+        let overlapping = r#"<path d="M0 0h4v4H0z"/><path d="M1 1h2v2H1z"/>"#;
+        assert_eq!(run(overlapping), overlapping);
+        let different = r#"<path fill="red" d="M0 0h1"/><path fill="blue" d="M5 5h1"/>"#;
+        assert_eq!(run(different), different);
+    }
+
+    #[test]
+    fn passes_can_be_disabled_and_report_savings() {
+        let input = "<!-- a --><path d=\"M 0 0 L 10 10\"/>";
+        let options = OptimizeOptions {
+            remove_comments: false,
+            ..Default::default()
+        };
+        let result = optimize(input, &options).unwrap();
+        assert_eq!(result.markup, r#"<!-- a --><path d="M0 0 10 10"/>"#);
+        assert_eq!(result.report.original_bytes, input.len());
+        assert_eq!(result.report.optimized_bytes, result.markup.len());
+        assert_eq!(result.report.saved_bytes(), 3);
+        assert!(result.report.passes.iter().all(|p| p.pass != "comments"));
+        let path = result.report.passes.iter().find(|p| p.pass == "path data");
+        assert_eq!(path.map(|p| p.bytes), Some(3));
+    }
+}
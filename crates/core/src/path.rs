@@ -0,0 +1,779 @@
+//! SVG path data (`d` attribute) parsing, conversion and serialization.
+//!
+//! Paths are parsed into [`Segment`]s with implicit command repeats expanded,
+//! so every segment carries exactly the arguments of its own command.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt;
+
+/// A path command, independent of whether it is absolute or relative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    MoveTo,
+    LineTo,
+    HorizontalTo,
+    VerticalTo,
+    CubicTo,
+    SmoothCubicTo,
+    QuadTo,
+    SmoothQuadTo,
+    ArcTo,
+    ClosePath,
+}
+
+impl Command {
+    /// Parse a command letter, returning the command and whether it is relative.
+    #[must_use]
+    pub fn from_letter(letter: char) -> Option<(Self, bool)> {
+        let command = match letter.to_ascii_uppercase() {
+            'M' => Self::MoveTo,
+            'L' => Self::LineTo,
+            'H' => Self::HorizontalTo,
+            'V' => Self::VerticalTo,
+            'C' => Self::CubicTo,
+            'S' => Self::SmoothCubicTo,
+            'Q' => Self::QuadTo,
+            'T' => Self::SmoothQuadTo,
+            'A' => Self::ArcTo,
+            'Z' => Self::ClosePath,
+            _ => return None,
+        };
+        Some((command, letter.is_ascii_lowercase()))
+    }
+
+    /// The command letter, lowercase when relative.
+    #[must_use]
+    pub fn letter(self, relative: bool) -> char {
+        let letter = match self {
+            Self::MoveTo => 'M',
+            Self::LineTo => 'L',
+            Self::HorizontalTo => 'H',
+            Self::VerticalTo => 'V',
+            Self::CubicTo => 'C',
+            Self::SmoothCubicTo => 'S',
+            Self::QuadTo => 'Q',
+            Self::SmoothQuadTo => 'T',
+            Self::ArcTo => 'A',
+            Self::ClosePath => 'Z',
+        };
+        if relative {
+            letter.to_ascii_lowercase()
+        } else {
+            letter
+        }
+    }
+
+    /// Number of arguments the command takes.
+    #[must_use]
+    pub fn arg_count(self) -> usize {
+        match self {
+            Self::ClosePath => 0,
+            Self::HorizontalTo | Self::VerticalTo => 1,
+            Self::MoveTo | Self::LineTo | Self::SmoothQuadTo => 2,
+            Self::SmoothCubicTo | Self::QuadTo => 4,
+            Self::CubicTo => 6,
+            Self::ArcTo => 7,
+        }
+    }
+}
+
+/// One path command with its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub command: Command,
+    pub relative: bool,
+    pub args: Vec<f64>,
+}
+
+impl Segment {
+    /// Create an absolute segment.
+    #[must_use]
+    pub fn absolute(command: Command, args: Vec<f64>) -> Self {
+        Self {
+            command,
+            relative: false,
+            args,
+        }
+    }
+
+    /// Whether argument `index` is an x coordinate, a y coordinate or neither.
+    fn axis(&self, index: usize) -> Option<Axis> {
+        match self.command {
+            Command::ClosePath => None,
+            Command::HorizontalTo => Some(Axis::X),
+            Command::VerticalTo => Some(Axis::Y),
+            Command::ArcTo => match index {
+                5 => Some(Axis::X),
+                6 => Some(Axis::Y),
+                _ => None,
+            },
+            _ if index.is_multiple_of(2) => Some(Axis::X),
+            _ => Some(Axis::Y),
+        }
+    }
+
+    /// End point of the segment given the current point and subpath start.
+    fn end_point(&self, current: (f64, f64), start: (f64, f64)) -> (f64, f64) {
+        let (ox, oy) = if self.relative { current } else { (0.0, 0.0) };
+        let a = &self.args;
+        match self.command {
+            Command::ClosePath => start,
+            Command::HorizontalTo => (ox + a[0], current.1),
+            Command::VerticalTo => (current.0, oy + a[0]),
+            _ => (ox + a[a.len() - 2], oy + a[a.len() - 1]),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// Error produced for malformed path data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    pub message: String,
+    /// Byte offset into the path data.
+    pub offset: usize,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Parse path data into segments, expanding implicit command repeats.
+///
+/// Coordinates following a moveto are returned as separate linetos.
+///
+/// # Errors
+/// Returns a [`PathError`] for unknown commands, missing arguments or data
+/// that does not start with a command.
+pub fn parse(d: &str) -> Result<Vec<Segment>, PathError> {
+    let bytes = d.as_bytes();
+    let mut pos = 0;
+    let mut segments = Vec::new();
+    let mut current: Option<(Command, bool)> = None;
+    let error = |message: &str, offset: usize| PathError {
+        message: message.to_string(),
+        offset,
+    };
+    let skip_separators = |pos: &mut usize| {
+        while *pos < bytes.len() && (bytes[*pos].is_ascii_whitespace() || bytes[*pos] == b',') {
+            *pos += 1;
+        }
+    };
+
+    loop {
+        skip_separators(&mut pos);
+        let Some(&byte) = bytes.get(pos) else { break };
+        if byte.is_ascii_alphabetic() {
+            current = Some(
+                Command::from_letter(char::from(byte))
+                    .ok_or_else(|| error("unknown command", pos))?,
+            );
+            pos += 1;
+        }
+        let Some((command, relative)) = current else {
+            return Err(error("expected a command", pos));
+        };
+
+        let count = command.arg_count();
+        let mut args = Vec::with_capacity(count);
+        for i in 0..count {
+            skip_separators(&mut pos);
+            if command == Command::ArcTo && (i == 3 || i == 4) {
+                match bytes.get(pos) {
+                    Some(b'0') => args.push(0.0),
+                    Some(b'1') => args.push(1.0),
+                    _ => return Err(error("expected an arc flag", pos)),
+                }
+                pos += 1;
+            } else {
+                let (n, len) =
+                    scan_number(&d[pos..]).ok_or_else(|| error("expected a number", pos))?;
+                args.push(n);
+                pos += len;
+            }
+        }
+        segments.push(Segment {
+            command,
+            relative,
+            args,
+        });
+
+        current = match command {
+            Command::ClosePath => None,
+            Command::MoveTo => Some((Command::LineTo, relative)),
+            _ => current,
+        };
+    }
+    Ok(segments)
+}
+
+/// Read one number from the start of `s`, returning it and its length.
+fn scan_number(s: &str) -> Option<(f64, usize)> {
+    let bytes = s.as_bytes();
+    let mut end = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    let mut seen_dot = false;
+    let mut seen_digit = false;
+    while let Some(&b) = bytes.get(end) {
+        match b {
+            b'0'..=b'9' => seen_digit = true,
+            b'.' if !seen_dot => seen_dot = true,
+            _ => break,
+        }
+        end += 1;
+    }
+    if !seen_digit {
+        return None;
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exp = end + 1;
+        if matches!(bytes.get(exp), Some(b'+' | b'-')) {
+            exp += 1;
+        }
+        if bytes.get(exp).is_some_and(u8::is_ascii_digit) {
+            end = exp;
+            while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+            }
+        }
+    }
+    s[..end].parse().ok().map(|n| (n, end))
+}
+
+/// Convert every segment to absolute coordinates.
+#[must_use]
+pub fn to_absolute(segments: &[Segment]) -> Vec<Segment> {
+    convert(segments, false)
+}
+
+/// Convert every segment to relative coordinates.
+///
+/// The first moveto stays absolute, as a leading `m` is absolute anyway.
+#[must_use]
+pub fn to_relative(segments: &[Segment]) -> Vec<Segment> {
+    let mut out = convert(segments, true);
+    if let Some(first) = out.first_mut() {
+        first.relative = false;
+    }
+    out
+}
+
+fn convert(segments: &[Segment], relative: bool) -> Vec<Segment> {
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    let mut out = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let end = segment.end_point(current, start);
+        let converted = convert_one(segment, current, relative);
+        out.push(converted);
+        current = end;
+        if segment.command == Command::MoveTo {
+            start = end;
+        }
+    }
+    out
+}
+
+/// Normalise a path to absolute `M`, `C` and `Z` segments only.
+///
+/// Lines become straight cubics, quadratics are degree-elevated, smooth
+/// curves have their reflected control points made explicit and arcs are
+/// approximated with at most a quarter turn per cubic.
+#[must_use]
+pub fn to_cubics(segments: &[Segment]) -> Vec<Segment> {
+    let mut out = Vec::with_capacity(segments.len());
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    let mut last_cubic: Option<(f64, f64)> = None;
+    let mut last_quad: Option<(f64, f64)> = None;
+
+    let line = |from: (f64, f64), to: (f64, f64)| {
+        Segment::absolute(
+            Command::CubicTo,
+            vec![
+                from.0 + (to.0 - from.0) / 3.0,
+                from.1 + (to.1 - from.1) / 3.0,
+                from.0 + (to.0 - from.0) * 2.0 / 3.0,
+                from.1 + (to.1 - from.1) * 2.0 / 3.0,
+                to.0,
+                to.1,
+            ],
+        )
+    };
+
+    for segment in to_absolute(segments) {
+        let a = &segment.args;
+        let end = segment.end_point(current, start);
+        let (mut next_cubic, mut next_quad) = (None, None);
+        match segment.command {
+            Command::MoveTo => {
+                out.push(segment.clone());
+                start = end;
+            }
+            Command::ClosePath => out.push(segment.clone()),
+            Command::LineTo | Command::HorizontalTo | Command::VerticalTo => {
+                out.push(line(current, end));
+            }
+            Command::CubicTo => {
+                out.push(segment.clone());
+                next_cubic = Some((a[2], a[3]));
+            }
+            Command::SmoothCubicTo => {
+                let c1 = reflect(last_cubic, current);
+                out.push(Segment::absolute(
+                    Command::CubicTo,
+                    vec![c1.0, c1.1, a[0], a[1], a[2], a[3]],
+                ));
+                next_cubic = Some((a[0], a[1]));
+            }
+            Command::QuadTo | Command::SmoothQuadTo => {
+                let q = if segment.command == Command::QuadTo {
+                    (a[0], a[1])
+                } else {
+                    reflect(last_quad, current)
+                };
+                out.push(Segment::absolute(
+                    Command::CubicTo,
+                    vec![
+                        current.0 + (q.0 - current.0) * 2.0 / 3.0,
+                        current.1 + (q.1 - current.1) * 2.0 / 3.0,
+                        end.0 + (q.0 - end.0) * 2.0 / 3.0,
+                        end.1 + (q.1 - end.1) * 2.0 / 3.0,
+                        end.0,
+                        end.1,
+                    ],
+                ));
+                next_quad = Some(q);
+            }
+            Command::ArcTo => {
+                let arc = Arc {
+                    rx: a[0],
+                    ry: a[1],
+                    angle: a[2],
+                    large: a[3] != 0.0,
+                    sweep: a[4] != 0.0,
+                };
+                if arc.rx == 0.0 || arc.ry == 0.0 {
+                    out.push(line(current, end));
+                } else {
+                    for cubic in arc_to_cubics(current, end, arc) {
+                        out.push(Segment::absolute(Command::CubicTo, cubic.to_vec()));
+                    }
+                }
+            }
+        }
+        last_cubic = next_cubic;
+        last_quad = next_quad;
+        current = end;
+    }
+    out
+}
+
+/// Reflect the previous control point about the current point.
+fn reflect(control: Option<(f64, f64)>, current: (f64, f64)) -> (f64, f64) {
+    control.map_or(current, |(x, y)| (2.0 * current.0 - x, 2.0 * current.1 - y))
+}
+
+#[derive(Clone, Copy)]
+struct Arc {
+    rx: f64,
+    ry: f64,
+    angle: f64,
+    large: bool,
+    sweep: bool,
+}
+
+/// Approximate an elliptical arc with cubics, following SVG's endpoint to
+/// center parameterization (SVG 1.1 appendix F.6.5).
+fn arc_to_cubics(from: (f64, f64), to: (f64, f64), arc: Arc) -> Vec<[f64; 6]> {
+    if from == to {
+        return Vec::new();
+    }
+    let (sin, cos) = arc.angle.to_radians().sin_cos();
+    let (mut rx, mut ry) = (arc.rx.abs(), arc.ry.abs());
+    let dx = (from.0 - to.0) / 2.0;
+    let dy = (from.1 - to.1) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let direction = if arc.large == arc.sweep { -1.0 } else { 1.0 };
+    let coef = direction * (num / den).max(0.0).sqrt();
+    let cxp = coef * rx * y1 / ry;
+    let cyp = -coef * ry * x1 / rx;
+    let cx = cos * cxp - sin * cyp + f64::midpoint(from.0, to.0);
+    let cy = sin * cxp + cos * cyp + f64::midpoint(from.1, to.1);
+
+    let angle_between = |ux: f64, uy: f64, vx: f64, vy: f64| {
+        let a = (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+        if a.is_nan() {
+            0.0
+        } else {
+            a
+        }
+    };
+    let theta = angle_between(1.0, 0.0, (x1 - cxp) / rx, (y1 - cyp) / ry);
+    let mut delta = angle_between(
+        (x1 - cxp) / rx,
+        (y1 - cyp) / ry,
+        (-x1 - cxp) / rx,
+        (-y1 - cyp) / ry,
+    );
+    if !arc.sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if arc.sweep && delta < 0.0 {
+        delta += TAU;
+    }
+
+    let mut count = 1u32;
+    while delta.abs() / f64::from(count) > FRAC_PI_2 + 1e-9 {
+        count += 1;
+    }
+    let step = delta / f64::from(count);
+    let t = 4.0 / 3.0 * (step / 4.0).tan();
+
+    let point = |a: f64| {
+        let (s, c) = a.sin_cos();
+        (
+            cx + rx * c * cos - ry * s * sin,
+            cy + rx * c * sin + ry * s * cos,
+        )
+    };
+    let derivative = |a: f64| {
+        let (s, c) = a.sin_cos();
+        (-rx * s * cos - ry * c * sin, -rx * s * sin + ry * c * cos)
+    };
+
+    (0..count)
+        .map(|i| {
+            let a1 = theta + step * f64::from(i);
+            let a2 = a1 + step;
+            let p1 = point(a1);
+            let p2 = if i + 1 == count { to } else { point(a2) };
+            let d1 = derivative(a1);
+            let d2 = derivative(a2);
+            [
+                p1.0 + t * d1.0,
+                p1.1 + t * d1.1,
+                p2.0 - t * d2.0,
+                p2.1 - t * d2.1,
+                p2.0,
+                p2.1,
+            ]
+        })
+        .collect()
+}
+
+/// Serialize segments exactly as given, with minimal separators.
+#[must_use]
+pub fn write(segments: &[Segment], precision: Option<u8>) -> String {
+    let mut out = String::new();
+    let mut implied = None;
+    for segment in segments {
+        let letter = segment.command.letter(segment.relative);
+        append(&mut out, &mut implied, letter, &segment.args, precision);
+    }
+    out
+}
+
+/// Serialize in the shortest form, picking absolute or relative per segment
+/// and turning axis-aligned lines into `H`/`V`.
+///
+/// Relative values are computed from the rounded output so rounding errors
+/// do not accumulate along the path.
+#[must_use]
+pub fn write_shortest(segments: &[Segment], precision: Option<u8>) -> String {
+    let round = |n: f64| round_to(n, precision);
+    let mut out = String::new();
+    let mut implied = None;
+    // Current point and subpath start as a reader of the output sees them.
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    let mut real_current = (0.0, 0.0);
+    let mut real_start = (0.0, 0.0);
+
+    for (index, segment) in segments.iter().enumerate() {
+        let end = segment.end_point(real_current, real_start);
+        let absolute = convert_one(segment, real_current, false);
+
+        let mut candidates: Vec<(Command, bool, Vec<f64>)> = Vec::new();
+        let mut command = absolute.command;
+        let mut abs_args = absolute.args.clone();
+        if command == Command::LineTo {
+            if same(round(end.1), current.1) {
+                command = Command::HorizontalTo;
+                abs_args = vec![end.0];
+            } else if same(round(end.0), current.0) {
+                command = Command::VerticalTo;
+                abs_args = vec![end.1];
+            }
+        }
+        let abs_args: Vec<f64> = abs_args.into_iter().map(round).collect();
+        let probe = Segment::absolute(command, abs_args.clone());
+        let rel_args: Vec<f64> = abs_args
+            .iter()
+            .enumerate()
+            .map(|(i, n)| match probe.axis(i) {
+                Some(Axis::X) => round(n - current.0),
+                Some(Axis::Y) => round(n - current.1),
+                None => *n,
+            })
+            .collect();
+        // A leading moveto is absolute whatever its letter says.
+        if command == Command::ClosePath {
+            candidates.push((command, segment.relative, abs_args));
+        } else {
+            candidates.push((command, false, abs_args));
+            if index > 0 {
+                candidates.push((command, true, rel_args));
+            }
+        }
+
+        let mut best: Option<(String, Option<char>, bool, Vec<f64>)> = None;
+        for (cmd, relative, args) in candidates {
+            let mut piece = out.clone();
+            let mut piece_implied = implied;
+            append(
+                &mut piece,
+                &mut piece_implied,
+                cmd.letter(relative),
+                &args,
+                precision,
+            );
+            let better = best.as_ref().is_none_or(|(b, ..)| {
+                piece.len() < b.len() || (piece.len() == b.len() && relative == segment.relative)
+            });
+            if better {
+                best = Some((piece, piece_implied, relative, args));
+            }
+        }
+        let Some((text, new_implied, relative, args)) = best else {
+            continue;
+        };
+        out = text;
+        implied = new_implied;
+
+        let emitted = Segment {
+            command,
+            relative,
+            args,
+        };
+        current = emitted.end_point(current, start);
+        if command == Command::MoveTo {
+            start = current;
+            real_start = end;
+        }
+        real_current = end;
+    }
+    out
+}
+
+/// Re-express one segment as absolute or relative from `current`.
+fn convert_one(segment: &Segment, current: (f64, f64), relative: bool) -> Segment {
+    let mut converted = segment.clone();
+    if segment.relative != relative {
+        let sign = if relative { -1.0 } else { 1.0 };
+        for (i, arg) in converted.args.iter_mut().enumerate() {
+            match segment.axis(i) {
+                Some(Axis::X) => *arg += sign * current.0,
+                Some(Axis::Y) => *arg += sign * current.1,
+                None => {}
+            }
+        }
+        converted.relative = relative;
+    }
+    converted
+}
+
+/// Parse and re-serialize path data in its shortest form.
+///
+/// # Errors
+/// Returns a [`PathError`] if the path data is malformed.
+pub fn minify(d: &str, precision: Option<u8>) -> Result<String, PathError> {
+    Ok(write_shortest(&parse(d)?, precision))
+}
+
+/// Append one command to `out`, omitting the letter when it is implied by
+/// the previous command and separators where a sign or dot already splits.
+fn append(
+    out: &mut String,
+    implied: &mut Option<char>,
+    letter: char,
+    args: &[f64],
+    precision: Option<u8>,
+) {
+    let implicit = *implied == Some(letter);
+    if !implicit {
+        out.push(letter);
+    }
+    let mut needs_separator = implicit;
+    for n in args {
+        let s = format_number(*n, precision);
+        let last_is_fraction = out
+            .rsplit(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .next()
+            .is_some_and(|tail| tail.contains('.'));
+        if needs_separator && !(s.starts_with('-') || (s.starts_with('.') && last_is_fraction)) {
+            out.push(' ');
+        }
+        out.push_str(&s);
+        needs_separator = true;
+    }
+    *implied = match letter {
+        'M' => Some('L'),
+        'm' => Some('l'),
+        'Z' | 'z' => None,
+        other => Some(other),
+    };
+}
+
+/// Equality for coordinates that went through the same rounding.
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn round_to(n: f64, precision: Option<u8>) -> f64 {
+    match precision {
+        Some(p) => {
+            let factor = 10f64.powi(i32::from(p));
+            (n * factor).round() / factor
+        }
+        None => n,
+    }
+}
+
+/// Format a number, rounding to `precision` places and dropping a leading zero.
+#[must_use]
+pub fn format_number(n: f64, precision: Option<u8>) -> String {
+    let s = crate::svg::format_number(round_to(n, precision));
+    if let Some(rest) = s.strip_prefix("0.") {
+        format!(".{rest}")
+    } else if let Some(rest) = s.strip_prefix("-0.") {
+        format!("-.{rest}")
+    } else {
+        s
+    }
+}
+
+/// Bounding box of the control polygon of a normalised path, as
+/// `[min_x, min_y, max_x, max_y]`.
+///
+/// Bezier curves lie within their control points, so this always contains
+/// the path, but may be larger than its exact bounds.
+#[must_use]
+pub fn control_bounds(segments: &[Segment]) -> Option<[f64; 4]> {
+    let mut bbox: Option<[f64; 4]> = None;
+    for segment in to_cubics(segments) {
+        for pair in segment.args.chunks(2) {
+            let (x, y) = (pair[0], pair[1]);
+            let b = bbox.get_or_insert([x, y, x, y]);
+            b[0] = b[0].min(x);
+            b[1] = b[1].min(y);
+            b[2] = b[2].max(x);
+            b[3] = b[3].max(y);
+        }
+    }
+    bbox
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn parses_all_commands_and_implicit_repeats() {
+        let segments =
+            parse("M1,2 3 4m1 1zL5-6h1V2c1 1 2 2 3 3s1 1 2 2q1 1 2 2t3 3a5 5 0 0110 0").unwrap();
+        let letters: String = segments
+            .iter()
+            .map(|s| s.command.letter(s.relative))
+            .collect();
+        assert_eq!(letters, "MLmzLhVcsqta");
+        assert_eq!(segments[1].args, [3.0, 4.0]);
+        assert_eq!(segments[11].args, [5.0, 5.0, 0.0, 0.0, 1.0, 10.0, 0.0]);
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        assert!(parse("10 10").is_err());
+        assert!(parse("M10").is_err());
+        assert!(parse("M0 0X1").is_err());
+        assert!(parse("M0 0a1 1 0 2 0 1 1").is_err());
+        assert_eq!(parse("").unwrap(), []);
+    }
+
+    #[test]
+    fn absolute_relative_round_trip() {
+        let segments = parse("M10 10l5 0v5h-5zm20 0 5 5").unwrap();
+        let absolute = to_absolute(&segments);
+        assert_eq!(write(&absolute, None), "M10 10 15 10V15H10ZM30 10 35 15");
+        let relative = to_relative(&absolute);
+        assert_eq!(write(&relative, None), "M10 10l5 0v5h-5zm20 0 5 5");
+    }
+
+    #[test]
+    fn normalises_to_cubics() {
+        let cubics = to_cubics(&parse("M0 0L3 0Q6 0 6 3T6 9").unwrap());
+        assert!(cubics
+            .iter()
+            .all(|s| matches!(s.command, Command::MoveTo | Command::CubicTo) && !s.relative));
+        assert_eq!(cubics[1].args, [1.0, 0.0, 2.0, 0.0, 3.0, 0.0]);
+        assert_eq!(cubics[2].args, [5.0, 0.0, 6.0, 1.0, 6.0, 3.0]);
+        // smooth quadratic reflects (6, 0) about (6, 3)
+        assert_eq!(cubics[3].args, [6.0, 5.0, 6.0, 7.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn arcs_become_quarter_turn_cubics() {
+        let cubics = to_cubics(&parse("M0 10A10 10 0 0 1 20 10").unwrap());
+        assert_eq!(cubics.len(), 3);
+        let mid = &cubics[1].args;
+        assert!(close(mid[4], 10.0) && close(mid[5], 0.0));
+        let last = &cubics[2].args;
+        assert!(close(last[4], 20.0) && close(last[5], 10.0));
+        // control point distance for a quarter circle is ~0.5523 * r
+        assert!(close(mid[0], 0.0) && close(mid[1], 10.0 - 5.522_847_498));
+    }
+
+    #[test]
+    fn writes_shortest_form() {
+        assert_eq!(
+            minify("M 10 10 L 20 10 L 20 20 L 10.5 20.25 Z", None).unwrap(),
+            "M10 10H20V20l-9.5.25Z"
+        );
+        assert_eq!(
+            minify("M0 0 C 0.33333 0 0.66666 1 1 1", Some(2)).unwrap(),
+            "M0 0C.33 0 .67 1 1 1"
+        );
+    }
+
+    #[test]
+    fn rounding_does_not_drift() {
+        let d = "M0 0l0.4 0.4l0.4 0.4l0.4 0.4l0.4 0.4";
+        let short = minify(d, Some(0)).unwrap();
+        let end = to_absolute(&parse(&short).unwrap())
+            .last()
+            .map(|s| s.args.clone())
+            .unwrap();
+        assert_eq!(end, [2.0, 2.0]);
+    }
+}
@@ -0,0 +1,371 @@
+//! Rust source that builds an icon's paths with a 2D vector library.
+//!
+//! Flattened [`Shape`]s are written as builder calls for `tiny-skia`,
+//! `kurbo` or `lyon`, so icons can be drawn without parsing SVG at runtime.
+//! Path data is reduced to the commands every library has (move, line,
+//! quadratic, cubic and close), with arcs approximated by cubics. The
+//! viewBox transform is either baked into the coordinates for a fixed size
+//! or applied at the end from a `size` parameter.
+
+use std::fmt::Write;
+
+use crate::path::{self, Command, Segment};
+use crate::shapes::{FillRule, Flattened, Shape, ShapePaint};
+
+/// A Rust 2D vector graphics library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathLibrary {
+    /// `tiny_skia::PathBuilder`, producing `tiny_skia::Path`s.
+    TinySkia,
+    /// `kurbo::BezPath`.
+    Kurbo,
+    /// `lyon::path::Path` from its builder.
+    Lyon,
+}
+
+impl PathLibrary {
+    /// Scalar type of the library's coordinates.
+    #[must_use]
+    pub fn float(self) -> &'static str {
+        match self {
+            Self::TinySkia | Self::Lyon => "f32",
+            Self::Kurbo => "f64",
+        }
+    }
+}
+
+/// Reduce absolute or relative path data to absolute `M`, `L`, `Q`, `C` and
+/// `Z` segments.
+#[must_use]
+pub fn simplify(segments: &[Segment]) -> Vec<Segment> {
+    let mut out = Vec::with_capacity(segments.len());
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // Last control point, for reflecting into smooth curves
+    let mut last_cubic: Option<(f64, f64)> = None;
+    let mut last_quad: Option<(f64, f64)> = None;
+    let reflect = |control: Option<(f64, f64)>, (x, y): (f64, f64)| {
+        control.map_or((x, y), |(cx, cy)| (2.0 * x - cx, 2.0 * y - cy))
+    };
+
+    for segment in path::to_absolute(segments) {
+        let a = &segment.args;
+        let (command, args) = match segment.command {
+            Command::MoveTo => {
+                start = (a[0], a[1]);
+                (Command::MoveTo, a.clone())
+            }
+            Command::LineTo => (Command::LineTo, a.clone()),
+            Command::HorizontalTo => (Command::LineTo, vec![a[0], current.1]),
+            Command::VerticalTo => (Command::LineTo, vec![current.0, a[0]]),
+            Command::CubicTo => (Command::CubicTo, a.clone()),
+            Command::SmoothCubicTo => {
+                let (x, y) = reflect(last_cubic, current);
+                (Command::CubicTo, vec![x, y, a[0], a[1], a[2], a[3]])
+            }
+            Command::QuadTo => (Command::QuadTo, a.clone()),
+            Command::SmoothQuadTo => {
+                let (x, y) = reflect(last_quad, current);
+                (Command::QuadTo, vec![x, y, a[0], a[1]])
+            }
+            Command::ArcTo => {
+                let arc = [
+                    Segment::absolute(Command::MoveTo, vec![current.0, current.1]),
+                    segment.clone(),
+                ];
+                out.extend(path::to_cubics(&arc).into_iter().skip(1));
+                current = (a[5], a[6]);
+                last_cubic = None;
+                last_quad = None;
+                continue;
+            }
+            Command::ClosePath => (Command::ClosePath, Vec::new()),
+        };
+        last_cubic = (command == Command::CubicTo).then(|| (args[2], args[3]));
+        last_quad = (command == Command::QuadTo).then(|| (args[0], args[1]));
+        current = match command {
+            Command::ClosePath => start,
+            _ => (args[args.len() - 2], args[args.len() - 1]),
+        };
+        out.push(Segment::absolute(command, args));
+    }
+    out
+}
+
+/// A float literal, rounded to four decimals.
+fn float(n: f64) -> String {
+    // Adding zero turns `-0` into `0`
+    let s = crate::svg::format_number((n * 1e4).round() / 1e4 + 0.0);
+    if s.contains('.') {
+        s
+    } else {
+        format!("{s}.0")
+    }
+}
+
+fn paint_name(paint: ShapePaint) -> String {
+    match paint {
+        ShapePaint::None => "none".to_string(),
+        ShapePaint::CurrentColor => "currentColor".to_string(),
+        ShapePaint::Color(color) => color.to_hex(),
+    }
+}
+
+/// A comment line describing how a shape is painted.
+fn paint_comment(shape: &Shape, scale: f64) -> String {
+    let mut parts = Vec::new();
+    if !shape.fill.is_none() {
+        let mut fill = format!("fill {}", paint_name(shape.fill));
+        if shape.fill_rule == FillRule::EvenOdd {
+            fill.push_str(" (even-odd)");
+        }
+        parts.push(fill);
+    }
+    if !shape.stroke.is_none() {
+        parts.push(format!(
+            "stroke {} (width {})",
+            paint_name(shape.stroke),
+            crate::svg::format_number((shape.stroke_width * scale * 1e4).round() / 1e4)
+        ));
+    }
+    if shape.opacity < 1.0 {
+        parts.push(format!(
+            "opacity {}",
+            crate::svg::format_number(shape.opacity)
+        ));
+    }
+    format!("// {}", parts.join(", "))
+}
+
+/// Statements building one shape and adding it to `paths`.
+fn write_shape(
+    shape: &Shape,
+    library: PathLibrary,
+    map: &dyn Fn(f64, f64) -> (String, String),
+    out: &mut String,
+) {
+    let point = |a: &[f64], i: usize| {
+        let (x, y) = map(a[i], a[i + 1]);
+        match library {
+            PathLibrary::TinySkia => format!("{x}, {y}"),
+            PathLibrary::Kurbo => format!("({x}, {y})"),
+            PathLibrary::Lyon => format!("point({x}, {y})"),
+        }
+    };
+    let _ = writeln!(
+        out,
+        "{}",
+        match library {
+            PathLibrary::TinySkia => "let mut pb = PathBuilder::new();",
+            PathLibrary::Kurbo => "let mut path = BezPath::new();",
+            PathLibrary::Lyon => "let mut builder = Path::builder();",
+        }
+    );
+    // lyon needs explicit `begin`/`end` around every subpath
+    let mut open = false;
+    let mut start = String::new();
+    for segment in simplify(&shape.segments) {
+        let a = &segment.args;
+        if library == PathLibrary::Lyon && segment.command != Command::MoveTo && !open {
+            let _ = writeln!(out, "builder.begin({start});");
+            open = true;
+        }
+        let line = match (library, segment.command) {
+            (PathLibrary::Lyon, Command::MoveTo) => {
+                if open {
+                    let _ = writeln!(out, "builder.end(false);");
+                    open = false;
+                }
+                start = point(a, 0);
+                continue;
+            }
+            (PathLibrary::Lyon, Command::ClosePath) => {
+                open = false;
+                "builder.end(true);".to_string()
+            }
+            (PathLibrary::Lyon, Command::LineTo) => format!("builder.line_to({});", point(a, 0)),
+            (PathLibrary::Lyon, Command::QuadTo) => format!(
+                "builder.quadratic_bezier_to({}, {});",
+                point(a, 0),
+                point(a, 2)
+            ),
+            (PathLibrary::Lyon, _) => format!(
+                "builder.cubic_bezier_to({}, {}, {});",
+                point(a, 0),
+                point(a, 2),
+                point(a, 4)
+            ),
+            (PathLibrary::TinySkia, Command::MoveTo) => format!("pb.move_to({});", point(a, 0)),
+            (PathLibrary::TinySkia, Command::LineTo) => format!("pb.line_to({});", point(a, 0)),
+            (PathLibrary::TinySkia, Command::QuadTo) => {
+                format!("pb.quad_to({}, {});", point(a, 0), point(a, 2))
+            }
+            (PathLibrary::TinySkia, Command::ClosePath) => "pb.close();".to_string(),
+            (PathLibrary::TinySkia, _) => format!(
+                "pb.cubic_to({}, {}, {});",
+                point(a, 0),
+                point(a, 2),
+                point(a, 4)
+            ),
+            (PathLibrary::Kurbo, Command::MoveTo) => format!("path.move_to({});", point(a, 0)),
+            (PathLibrary::Kurbo, Command::LineTo) => format!("path.line_to({});", point(a, 0)),
+            (PathLibrary::Kurbo, Command::QuadTo) => {
+                format!("path.quad_to({}, {});", point(a, 0), point(a, 2))
+            }
+            (PathLibrary::Kurbo, Command::ClosePath) => "path.close_path();".to_string(),
+            (PathLibrary::Kurbo, _) => format!(
+                "path.curve_to({}, {}, {});",
+                point(a, 0),
+                point(a, 2),
+                point(a, 4)
+            ),
+        };
+        let _ = writeln!(out, "{line}");
+    }
+    if open {
+        let _ = writeln!(out, "builder.end(false);");
+    }
+    let _ = writeln!(
+        out,
+        "{}",
+        match library {
+            PathLibrary::TinySkia => "paths.extend(pb.finish());",
+            PathLibrary::Kurbo => "paths.push(path);",
+            PathLibrary::Lyon => "paths.push(builder.build());",
+        }
+    );
+}
+
+/// The body of a function returning the icon's paths in paint order.
+///
+/// `view_box` is `[left, top, width, height]`. With `size`, coordinates are
+/// scaled so the icon is `size` units high with its viewBox origin at zero.
+/// Without it, paths are built in viewBox units and transformed at the end
+/// using a `size` variable of the library's float type.
+#[must_use]
+pub fn function_body(
+    flattened: &Flattened,
+    library: PathLibrary,
+    view_box: [f64; 4],
+    size: Option<f64>,
+) -> String {
+    let [left, top, _, height] = view_box;
+    let scale = size.map_or(1.0, |size| size / height);
+    let map = |x: f64, y: f64| match size {
+        Some(_) => (float((x - left) * scale), float((y - top) * scale)),
+        None => (float(x), float(y)),
+    };
+
+    let mut out = String::from("let mut paths = Vec::new();\n");
+    for shape in &flattened.shapes {
+        out.push('\n');
+        let _ = writeln!(out, "{}", paint_comment(shape, scale));
+        write_shape(shape, library, &map, &mut out);
+    }
+    out.push('\n');
+    if size.is_some() {
+        out.push_str("paths");
+        return out;
+    }
+
+    let factor = format!("size / {}", float(height));
+    let translate = left != 0.0 || top != 0.0;
+    let (left, top) = (float(-left), float(-top));
+    match library {
+        PathLibrary::TinySkia => {
+            let _ = write!(
+                out,
+                "let transform = Transform::from_scale({factor}, {factor})"
+            );
+            if translate {
+                let _ = write!(out, ".pre_translate({left}, {top})");
+            }
+            out.push_str(";\npaths\n    .into_iter()\n    .filter_map(|path| path.transform(transform))\n    .collect()");
+        }
+        PathLibrary::Kurbo => {
+            let _ = write!(out, "let affine = Affine::scale({factor})");
+            if translate {
+                let _ = write!(out, " * Affine::translate(({left}, {top}))");
+            }
+            out.push_str(";\npaths.into_iter().map(|path| affine * path).collect()");
+        }
+        PathLibrary::Lyon => {
+            if translate {
+                let _ = write!(
+                    out,
+                    "let transform = Transform::translation({left}, {top}).then_scale({factor}, {factor});"
+                );
+            } else {
+                let _ = write!(out, "let transform = Transform::scale({factor}, {factor});");
+            }
+            out.push_str("\npaths\n    .into_iter()\n    .map(|path| path.transformed(&transform))\n    .collect()");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::flatten;
+
+    #[test]
+    fn simplifies_to_common_commands() {
+        let segments = path::parse("M1 1h2v2s1 1 2 0t1 1a1 1 0 0 1 1 1z").unwrap();
+        let d = path::write(&simplify(&segments), None);
+        assert!(d.starts_with("M1 1 3 1 3 3C3 3 4 4 5 3Q5 3 6 4C"), "{d}");
+        assert!(d.ends_with('Z'), "{d}");
+        assert!(!d.contains(['A', 'H', 'V', 'S', 'T']), "{d}");
+    }
+
+    #[test]
+    fn writes_library_calls() {
+        let flat = flatten(r#"<path fill-rule="evenodd" d="M2 2h4v4zM8 8q1 1 2 0"/>"#).unwrap();
+        let view_box = [0.0, 0.0, 12.0, 12.0];
+        assert_eq!(
+            function_body(&flat, PathLibrary::TinySkia, view_box, Some(24.0)),
+            "let mut paths = Vec::new();
+
+// fill #000 (even-odd)
+let mut pb = PathBuilder::new();
+pb.move_to(4.0, 4.0);
+pb.line_to(12.0, 4.0);
+pb.line_to(12.0, 12.0);
+pb.close();
+pb.move_to(16.0, 16.0);
+pb.quad_to(18.0, 18.0, 20.0, 16.0);
+paths.extend(pb.finish());
+
+paths"
+        );
+        let lyon = function_body(&flat, PathLibrary::Lyon, view_box, None);
+        assert!(
+            lyon.contains(
+                "builder.begin(point(2.0, 2.0));
+builder.line_to(point(6.0, 2.0));
+builder.line_to(point(6.0, 6.0));
+builder.end(true);
+builder.begin(point(8.0, 8.0));
+builder.quadratic_bezier_to(point(9.0, 9.0), point(10.0, 8.0));
+builder.end(false);
+paths.push(builder.build());"
+            ),
+            "{lyon}"
+        );
+        assert!(
+            lyon.contains("Transform::scale(size / 12.0, size / 12.0);"),
+            "{lyon}"
+        );
+        let kurbo = function_body(&flat, PathLibrary::Kurbo, [-1.0, 0.0, 12.0, 12.0], None);
+        assert!(
+            kurbo.contains("path.quad_to((9.0, 9.0), (10.0, 8.0));"),
+            "{kurbo}"
+        );
+        assert!(
+            kurbo.contains(
+                "let affine = Affine::scale(size / 12.0) * Affine::translate((1.0, 0.0));"
+            ),
+            "{kurbo}"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! CPU-only rasterization of icons to RGBA pixels and PNG files.
+//!
+//! Available with the `raster` feature. Rendering is done by `resvg`, which
+//! has no system dependencies and also runs on `wasm32`.
+
+use std::fmt;
+
+use resvg::{tiny_skia, usvg};
+
+use crate::color::{self, Color};
+use crate::svg::{icon_to_svg, IconCustomisations, IconSize};
+use crate::types::ResolvedIcon;
+
+/// Sizes offered for PNG downloads, in pixels.
+pub const PNG_SIZES: &[u32] = &[16, 24, 32, 48, 64, 128, 256, 512];
+
+/// How to render an icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RasterOptions {
+    /// Width and height of the square output image in pixels.
+    pub size: u32,
+    /// Colour used for `currentColor`.
+    pub foreground: Color,
+    /// Background fill, or `None` for transparent.
+    pub background: Option<Color>,
+    /// Empty pixels kept on every side of the icon.
+    pub padding: u32,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            size: 24,
+            foreground: Color::BLACK,
+            background: None,
+            padding: 0,
+        }
+    }
+}
+
+/// Error produced while rasterizing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RasterError {
+    /// The output size is zero, or the padding leaves no room for the icon.
+    InvalidSize,
+    /// The icon body could not be parsed.
+    Svg(String),
+    /// The pixels could not be encoded.
+    Encode(String),
+}
+
+impl fmt::Display for RasterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize => f.write_str("invalid image size"),
+            Self::Svg(message) => write!(f, "invalid SVG: {message}"),
+            Self::Encode(message) => write!(f, "PNG encoding failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RasterError {}
+
+/// A rendered image with straight (non-premultiplied) RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major pixels, four bytes each.
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// The pixel at `(x, y)`.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let p = &self.pixels[i..i + 4];
+        Some(Color {
+            r: p[0],
+            g: p[1],
+            b: p[2],
+            a: p[3],
+        })
+    }
+}
+
+/// Standalone, sanitized SVG with `currentColor` resolved to `foreground`.
+///
+/// This is what the rasterizer renders; it also suits places that need a
+/// self-contained coloured SVG, such as favicons.
+///
+/// # Errors
+/// Returns [`RasterError::Svg`] if the body is not well-formed.
+pub fn tinted_svg(icon: &ResolvedIcon, foreground: Color) -> Result<String, RasterError> {
+    // Strip external references so the renderer never touches the filesystem
+    let (icon, _) =
+        crate::sanitize::sanitize_icon(icon).map_err(|e| RasterError::Svg(e.to_string()))?;
+    let (icon, palette) = color::prepare_for_tinting(&crate::svg::apply_transformations(&icon));
+    let mut svg = icon_to_svg(
+        &icon,
+        &IconCustomisations {
+            width: IconSize::Auto,
+            height: IconSize::Auto,
+            ..Default::default()
+        },
+    );
+    if !palette {
+        svg.attributes
+            .push(("fill".to_string(), "currentColor".to_string()));
+    }
+    svg.attributes
+        .push(("color".to_string(), foreground.to_hex()));
+    Ok(svg.to_svg())
+}
+
+fn render_pixmap(
+    icon: &ResolvedIcon,
+    options: &RasterOptions,
+) -> Result<tiny_skia::Pixmap, RasterError> {
+    let available = options
+        .size
+        .saturating_sub(options.padding.saturating_mul(2));
+    if available == 0 {
+        return Err(RasterError::InvalidSize);
+    }
+    let mut pixmap =
+        tiny_skia::Pixmap::new(options.size, options.size).ok_or(RasterError::InvalidSize)?;
+    if let Some(bg) = options.background {
+        pixmap.fill(tiny_skia::Color::from_rgba8(bg.r, bg.g, bg.b, bg.a));
+    }
+
+    let svg = tinted_svg(icon, options.foreground)?;
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+        .map_err(|e| RasterError::Svg(e.to_string()))?;
+
+    // Fit the icon into the padded square, keeping its aspect ratio
+    #[allow(clippy::cast_precision_loss)]
+    let (size, available) = (options.size as f32, available as f32);
+    let (width, height) = (tree.size().width(), tree.size().height());
+    let scale = available / width.max(height);
+    let transform = tiny_skia::Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        scale,
+        (size - width * scale) / 2.0,
+        (size - height * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Render an icon to RGBA pixels.
+///
+/// # Errors
+/// Returns a [`RasterError`] for an unusable size or a malformed body.
+pub fn render(icon: &ResolvedIcon, options: &RasterOptions) -> Result<RgbaImage, RasterError> {
+    let pixmap = render_pixmap(icon, options)?;
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    Ok(RgbaImage {
+        width: pixmap.width(),
+        height: pixmap.height(),
+        pixels,
+    })
+}
+
+/// Render an icon to a PNG file.
+///
+/// # Errors
+/// Returns a [`RasterError`] for an unusable size, a malformed body or an
+/// encoding failure.
+pub fn render_png(icon: &ResolvedIcon, options: &RasterOptions) -> Result<Vec<u8>, RasterError> {
+    render_pixmap(icon, options)?
+        .encode_png()
+        .map_err(|e| RasterError::Encode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: "test".into(),
+            name: "square".into(),
+            body: r#"<path d="M4 4h16v16H4z"/>"#.into(),
+            width: 24,
+            height: 24,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_with_foreground_colour() {
+        let red = Color::rgb(255, 0, 0);
+        let options = RasterOptions {
+            size: 48,
+            foreground: red,
+            ..Default::default()
+        };
+        let image = render(&square(), &options).unwrap();
+        assert_eq!((image.width, image.height), (48, 48));
+        assert_eq!(image.pixels.len(), 48 * 48 * 4);
+        assert_eq!(image.pixel(24, 24), Some(red));
+        assert_eq!(image.pixel(2, 2).map(|c| c.a), Some(0));
+        assert_eq!(image.pixel(48, 0), None);
+    }
+
+    #[test]
+    fn applies_background_and_padding() {
+        let options = RasterOptions {
+            size: 32,
+            background: Some(Color::WHITE),
+            padding: 8,
+            ..Default::default()
+        };
+        let image = render(&square(), &options).unwrap();
+        // The 24 unit box maps to pixels 8..24, so the square starts at 10.7
+        assert_eq!(image.pixel(9, 9), Some(Color::WHITE));
+        assert_eq!(image.pixel(16, 16), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn keeps_palette_colours() {
+        let icon = ResolvedIcon {
+            body:
+                r##"<path fill="#00f" d="M0 0h12v24H0z"/><path fill="#0f0" d="M12 0h12v24H12z"/>"##
+                    .into(),
+            ..square()
+        };
+        let image = render(&icon, &RasterOptions::default()).unwrap();
+        assert_eq!(image.pixel(2, 12), Some(Color::rgb(0, 0, 255)));
+        assert_eq!(image.pixel(20, 12), Some(Color::rgb(0, 255, 0)));
+    }
+
+    #[test]
+    fn encodes_png() {
+        let png = render_png(&square(), &RasterOptions::default()).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn rejects_unusable_sizes() {
+        let options = RasterOptions {
+            size: 16,
+            padding: 8,
+            ..Default::default()
+        };
+        assert_eq!(render(&square(), &options), Err(RasterError::InvalidSize));
+        let options = RasterOptions {
+            padding: u32::MAX,
+            ..options
+        };
+        assert_eq!(render(&square(), &options), Err(RasterError::InvalidSize));
+    }
+}
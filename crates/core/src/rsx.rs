@@ -0,0 +1,211 @@
+//! SVG bodies as Dioxus `rsx!` markup.
+//!
+//! Each element becomes `name { attr: "value", child { … } }`. Attribute
+//! names follow `dioxus-html` (`fill-rule` → `fill_rule`, `viewBox` →
+//! `view_box`); attributes it does not define, such as `data-*` and
+//! namespaced names, are written as quoted custom attributes.
+
+use std::fmt::Write;
+
+use crate::tree::{Element, Node};
+
+/// Dioxus release a snippet targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DioxusVersion {
+    /// Dioxus 0.5: props are interpolated into format strings and optional
+    /// props are unwrapped before use.
+    V05,
+    /// Dioxus 0.6: props are passed as expressions and optional attributes
+    /// use the shorthand syntax.
+    #[default]
+    V06,
+}
+
+/// Names that need a raw identifier in `rsx!`.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "type", "unsafe", "use", "where", "while",
+];
+
+/// Convert a `camelCase`, `kebab-case` or `snake_case` name to
+/// `snake_case`.
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c == '-' {
+            out.push('_');
+        } else if c.is_ascii_uppercase() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The `rsx!` name of an SVG attribute: a `dioxus-html` identifier, or a
+/// quoted custom attribute.
+#[must_use]
+pub fn attribute_name(name: &str) -> String {
+    let known = !name.contains(':')
+        && !name.starts_with("data-")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if !known {
+        return string_literal(name);
+    }
+    let ident = snake_case(name);
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+/// A Rust string literal that is also a valid `rsx!` format string, so
+/// braces are doubled.
+#[must_use]
+pub fn string_literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '{' => out.push_str("{{"),
+            '}' => out.push_str("}}"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_element(el: &Element, depth: usize, out: &mut String) {
+    let pad = "    ".repeat(depth);
+    let attributes: Vec<String> = el
+        .attributes
+        .iter()
+        .map(|a| format!("{}: {}", attribute_name(&a.name), string_literal(&a.value)))
+        .collect();
+    let mut children = String::new();
+    write_nodes(&el.children, depth + 1, &mut children);
+
+    if children.is_empty() {
+        if attributes.is_empty() {
+            let _ = writeln!(out, "{pad}{} {{}}", el.name);
+        } else {
+            let _ = writeln!(out, "{pad}{} {{ {} }}", el.name, attributes.join(", "));
+        }
+        return;
+    }
+    let _ = writeln!(out, "{pad}{} {{", el.name);
+    for attribute in &attributes {
+        let _ = writeln!(out, "{pad}    {attribute},");
+    }
+    out.push_str(&children);
+    let _ = writeln!(out, "{pad}}}");
+}
+
+fn write_nodes(nodes: &[Node], depth: usize, out: &mut String) {
+    let pad = "    ".repeat(depth);
+    for node in nodes {
+        match node {
+            // Namespaced elements, such as editor metadata, have no
+            // `dioxus-html` counterpart
+            Node::Element(el) if el.name.contains(':') => {}
+            Node::Element(el) => write_element(el, depth, out),
+            Node::Text(text) | Node::CData(text) => {
+                if !text.trim().is_empty() {
+                    let _ = writeln!(out, "{pad}{}", string_literal(text));
+                }
+            }
+            Node::Comment(text) => {
+                for line in text.trim().lines() {
+                    let _ = writeln!(out, "{pad}// {}", line.trim());
+                }
+            }
+        }
+    }
+}
+
+/// Convert an SVG body to `rsx!` children, one node per line at the top
+/// level.
+///
+/// Bodies that cannot be parsed produce an empty string.
+#[must_use]
+pub fn body_to_rsx(body: &str) -> String {
+    let Ok(nodes) = crate::tree::parse(body) else {
+        return String::new();
+    };
+    let mut out = String::with_capacity(body.len() * 2);
+    write_nodes(&nodes, 0, &mut out);
+    out.truncate(out.trim_end().len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_nested_elements() {
+        let body = r##"<defs><linearGradient id="g" gradientUnits="userSpaceOnUse"><stop offset="0" stop-color="#fff"/></linearGradient></defs><g fill-rule="evenodd" clip-path="url(#c)"><path d="M0 0h24v24H0z"/></g>"##;
+        assert_eq!(
+            body_to_rsx(body),
+            r##"defs {
+    linearGradient {
+        id: "g",
+        gradient_units: "userSpaceOnUse",
+        stop { offset: "0", stop_color: "#fff" }
+    }
+}
+g {
+    fill_rule: "evenodd",
+    clip_path: "url(#c)",
+    path { d: "M0 0h24v24H0z" }
+}"##
+        );
+    }
+
+    #[test]
+    fn maps_attribute_names() {
+        assert_eq!(attribute_name("viewBox"), "view_box");
+        assert_eq!(attribute_name("stroke-dasharray"), "stroke_dasharray");
+        assert_eq!(attribute_name("aria-hidden"), "aria_hidden");
+        assert_eq!(attribute_name("type"), "r#type");
+        assert_eq!(attribute_name("in"), "r#in");
+        assert_eq!(attribute_name("data-name"), r#""data-name""#);
+        assert_eq!(attribute_name("xlink:href"), r#""xlink:href""#);
+    }
+
+    #[test]
+    fn escapes_text_and_values() {
+        let body = r#"<!-- layer 1 --><style>.a{fill:"red"}</style><text x="1" font-family="a\b">{ok}</text><sodipodi:namedview/>"#;
+        assert_eq!(
+            body_to_rsx(body),
+            r#"// layer 1
+style {
+    ".a{{fill:\"red\"}}"
+}
+text {
+    x: "1",
+    font_family: "a\\b",
+    "{{ok}}"
+}"#
+        );
+        assert_eq!(body_to_rsx("<path"), "");
+    }
+}
@@ -0,0 +1,460 @@
+//! Sanitizer for untrusted icon bodies.
+//!
+//! Removes anything that can run code or load external resources before a
+//! body is injected into the page or pasted into generated code, and reports
+//! each removal. Comments are dropped and CDATA sections written as plain
+//! text without being reported: HTML and XML parsers disagree on where they
+//! end, which would let markup hide inside them.
+
+use std::fmt;
+
+use crate::tree::{self, Element, Node, ParseError};
+use crate::types::ResolvedIcon;
+
+/// SVG elements that are kept. Everything else is removed with its children.
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "a",
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "set",
+    "stop",
+    "style",
+    "svg",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "title",
+    "tspan",
+    "use",
+    "view",
+];
+
+/// Why something was removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalReason {
+    /// A `<script>` element.
+    Script,
+    /// A `<foreignObject>` element, which can embed arbitrary HTML.
+    ForeignObject,
+    /// An element that is not a known SVG element.
+    UnknownElement,
+    /// An `on*` event handler attribute.
+    EventHandler,
+    /// A `javascript:` URL or script-like CSS.
+    JavascriptUrl,
+    /// A reference to a resource outside the document.
+    ExternalReference,
+    /// An animation that targets an event handler or link.
+    UnsafeAnimation,
+}
+
+impl fmt::Display for RemovalReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Script => "script",
+            Self::ForeignObject => "foreign object",
+            Self::UnknownElement => "unknown element",
+            Self::EventHandler => "event handler",
+            Self::JavascriptUrl => "javascript URL",
+            Self::ExternalReference => "external reference",
+            Self::UnsafeAnimation => "unsafe animation",
+        })
+    }
+}
+
+/// One removed element or attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Removal {
+    /// Name of the element that was removed or carried the attribute.
+    pub element: String,
+    /// Name of the removed attribute, or `None` if the element was removed.
+    pub attribute: Option<String>,
+    pub reason: RemovalReason,
+}
+
+impl fmt::Display for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.attribute {
+            Some(attr) => write!(f, "{} on <{}> ({})", attr, self.element, self.reason),
+            None => write!(f, "<{}> ({})", self.element, self.reason),
+        }
+    }
+}
+
+/// Everything the sanitizer removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    pub removals: Vec<Removal>,
+}
+
+impl SanitizeReport {
+    /// Whether the input needed no changes.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.removals.is_empty()
+    }
+}
+
+/// Sanitized markup together with its report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    pub markup: String,
+    pub report: SanitizeReport,
+}
+
+/// Sanitize an SVG body or document.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the markup is not well-formed. Such markup
+/// must not be rendered, as it cannot be checked.
+pub fn sanitize(markup: &str) -> Result<Sanitized, ParseError> {
+    let mut nodes = tree::parse(markup)?;
+    let mut report = SanitizeReport::default();
+    sanitize_nodes(&mut nodes, &mut report);
+    Ok(Sanitized {
+        markup: tree::to_markup(&nodes),
+        report,
+    })
+}
+
+/// Sanitize the body of an icon, leaving every other field untouched.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn sanitize_icon(icon: &ResolvedIcon) -> Result<(ResolvedIcon, SanitizeReport), ParseError> {
+    let sanitized = sanitize(&icon.body)?;
+    let icon = ResolvedIcon {
+        body: sanitized.markup,
+        ..icon.clone()
+    };
+    Ok((icon, sanitized.report))
+}
+
+fn sanitize_nodes(nodes: &mut Vec<Node>, report: &mut SanitizeReport) {
+    nodes.retain_mut(|node| {
+        let el = match node {
+            Node::Comment(_) => return false,
+            Node::CData(text) => {
+                let text = std::mem::take(text);
+                *node = Node::Text(text);
+                return true;
+            }
+            Node::Text(_) => return true,
+            Node::Element(el) => el,
+        };
+        if let Some(reason) = element_removal(el) {
+            report.removals.push(Removal {
+                element: el.name.clone(),
+                attribute: None,
+                reason,
+            });
+            return false;
+        }
+        sanitize_attributes(el, report);
+        sanitize_nodes(&mut el.children, report);
+        true
+    });
+}
+
+/// Decide whether a whole element has to go.
+fn element_removal(el: &Element) -> Option<RemovalReason> {
+    match el.name.as_str() {
+        "script" => Some(RemovalReason::Script),
+        "foreignObject" => Some(RemovalReason::ForeignObject),
+        "style" => {
+            let css: String = el
+                .children
+                .iter()
+                .filter_map(|node| match node {
+                    Node::Text(text) | Node::CData(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            unsafe_css(&css)
+        }
+        "animate" | "animateMotion" | "animateTransform" | "set" => {
+            let target = el.attr("attributeName").unwrap_or_default();
+            let unsafe_target = target.starts_with("on") || target.ends_with("href");
+            unsafe_target.then_some(RemovalReason::UnsafeAnimation)
+        }
+        name if !ALLOWED_ELEMENTS.contains(&name) => Some(RemovalReason::UnknownElement),
+        _ => None,
+    }
+}
+
+fn sanitize_attributes(el: &mut Element, report: &mut SanitizeReport) {
+    let element = el.name.clone();
+    el.attributes.retain(|attr| {
+        let reason = attribute_removal(&element, &attr.name, &attr.value);
+        if let Some(reason) = reason {
+            report.removals.push(Removal {
+                element: element.clone(),
+                attribute: Some(attr.name.clone()),
+                reason,
+            });
+        }
+        reason.is_none()
+    });
+}
+
+fn attribute_removal(element: &str, name: &str, value: &str) -> Option<RemovalReason> {
+    if name.to_ascii_lowercase().starts_with("on") {
+        return Some(RemovalReason::EventHandler);
+    }
+    if is_javascript_url(value) {
+        return Some(RemovalReason::JavascriptUrl);
+    }
+    if name == "href" || name.ends_with(":href") {
+        let value = value.trim();
+        let local = value.starts_with('#');
+        let inline_image = element == "image" && is_raster_data_url(value);
+        if !local && !inline_image {
+            return Some(RemovalReason::ExternalReference);
+        }
+    }
+    if name == "style" {
+        return unsafe_css(value);
+    }
+    if has_external_url(&decode_css(value)) {
+        return Some(RemovalReason::ExternalReference);
+    }
+    None
+}
+
+/// Whether a value is a `javascript:` URL, ignoring case and embedded whitespace.
+fn is_javascript_url(value: &str) -> bool {
+    let compact: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    compact.starts_with("javascript:") || compact.contains("url(javascript:")
+}
+
+fn is_raster_data_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    ["png", "jpeg", "jpg", "gif", "webp"]
+        .iter()
+        .any(|kind| lower.starts_with(&format!("data:image/{kind}")))
+}
+
+/// Whether CSS contains `url()` references to anything but local fragments.
+fn has_external_url(css: &str) -> bool {
+    css.split("url(").skip(1).any(|rest| {
+        let target = rest
+            .trim_start()
+            .trim_start_matches(['"', '\''])
+            .trim_start();
+        !target.starts_with('#')
+    })
+}
+
+/// CSS in lower case with comments removed and escapes decoded, so that
+/// escapes like `u\72l(` or `\@import` cannot hide from the checks.
+fn decode_css(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = '\0';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            '\\' => {
+                let mut hex = String::new();
+                while hex.len() < 6 && chars.peek().is_some_and(char::is_ascii_hexdigit) {
+                    hex.extend(chars.next());
+                }
+                if hex.is_empty() {
+                    // An escaped newline continues the line
+                    match chars.next() {
+                        Some('\n') | None => {}
+                        Some(c) => out.push(c),
+                    }
+                } else {
+                    if chars.peek().is_some_and(char::is_ascii_whitespace) {
+                        chars.next();
+                    }
+                    let code = u32::from_str_radix(&hex, 16).unwrap_or(0xfffd);
+                    out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.to_lowercase()
+}
+
+fn unsafe_css(css: &str) -> Option<RemovalReason> {
+    let lower = decode_css(css);
+    if lower.contains("javascript:") || lower.contains("expression(") {
+        Some(RemovalReason::JavascriptUrl)
+    } else if lower.contains("@import") || has_external_url(&lower) {
+        Some(RemovalReason::ExternalReference)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reasons(report: &SanitizeReport) -> Vec<RemovalReason> {
+        report.removals.iter().map(|r| r.reason).collect()
+    }
+
+    #[test]
+    fn clean_bodies_are_unchanged() {
+        let body = r##"<defs><linearGradient id="a"><stop offset="0"/></linearGradient></defs><path fill="url(#a)" d="M0 0h24v24H0z"/><use href="#a"/>"##;
+        let result = sanitize(body).unwrap();
+        assert_eq!(result.markup, body);
+        assert!(result.report.is_clean());
+    }
+
+    #[test]
+    fn removes_scripts_and_foreign_objects() {
+        let body = r#"<g><script>alert(1)</script><foreignObject><div/></foreignObject><path d="M0 0"/></g>"#;
+        let result = sanitize(body).unwrap();
+        assert_eq!(result.markup, r#"<g><path d="M0 0"/></g>"#);
+        assert_eq!(
+            reasons(&result.report),
+            [RemovalReason::Script, RemovalReason::ForeignObject]
+        );
+    }
+
+    #[test]
+    fn removes_handlers_and_javascript_urls() {
+        let body = r#"<a href=" java&#x0A;script:alert(1)"><path onClick="x()" d="M0 0"/></a>"#;
+        let result = sanitize(body).unwrap();
+        assert_eq!(result.markup, r#"<a><path d="M0 0"/></a>"#);
+        assert_eq!(
+            result.report.removals[0],
+            Removal {
+                element: "a".into(),
+                attribute: Some("href".into()),
+                reason: RemovalReason::JavascriptUrl,
+            }
+        );
+        assert_eq!(
+            result.report.removals[1].reason,
+            RemovalReason::EventHandler
+        );
+    }
+
+    #[test]
+    fn removes_external_references() {
+        let body = r#"<use xlink:href="https://evil.test/x.svg#a"/><image href="data:image/png;base64,AA"/><image href="data:image/svg+xml,x"/><rect style="fill:url(http://x)"/><rect fill="url('//x/y')"/>"#;
+        let result = sanitize(body).unwrap();
+        assert_eq!(
+            result.markup,
+            r#"<use/><image href="data:image/png;base64,AA"/><image/><rect/><rect/>"#
+        );
+        assert!(reasons(&result.report)
+            .iter()
+            .all(|r| *r == RemovalReason::ExternalReference));
+        assert_eq!(result.report.removals.len(), 4);
+    }
+
+    #[test]
+    fn removes_unknown_elements_and_unsafe_animations() {
+        let body = r#"<iframe src="x"/><set attributeName="href" to="javascript:x"/><animate attributeName="opacity"/><style>@import "x.css";</style>"#;
+        let result = sanitize(body).unwrap();
+        assert_eq!(result.markup, r#"<animate attributeName="opacity"/>"#);
+        assert_eq!(
+            reasons(&result.report),
+            [
+                RemovalReason::UnknownElement,
+                RemovalReason::UnsafeAnimation,
+                RemovalReason::ExternalReference
+            ]
+        );
+        assert_eq!(
+            result.report.removals[0].to_string(),
+            "<iframe> (unknown element)"
+        );
+    }
+
+    #[test]
+    fn drops_comments_and_escapes_cdata() {
+        let result = sanitize(r#"<!--><img src=x onerror=alert(1)>--><path d="M0 0h1"/>"#).unwrap();
+        assert_eq!(result.markup, r#"<path d="M0 0h1"/>"#);
+        let result = sanitize(
+            "<title><style><![CDATA[</style><img src=x onerror=alert(1)>]]></style></title>",
+        )
+        .unwrap();
+        assert_eq!(
+            result.markup,
+            "<title><style>&lt;/style&gt;&lt;img src=x onerror=alert(1)&gt;</style></title>"
+        );
+    }
+
+    #[test]
+    fn decodes_css_escapes() {
+        let body = r#"<rect style="fill:u\72l(https://evil/x)"/><rect fill="U\52 L(//x)"/><style>\@import "x.css";</style><style>@im/**/port "x.css";</style>"#;
+        let result = sanitize(body).unwrap();
+        assert_eq!(result.markup, "<rect/><rect/>");
+        assert!(reasons(&result.report)
+            .iter()
+            .all(|r| *r == RemovalReason::ExternalReference));
+        assert_eq!(result.report.removals.len(), 4);
+    }
+
+    #[test]
+    fn malformed_markup_is_an_error() {
+        assert!(sanitize("<g><script>").is_err());
+    }
+}
@@ -142,7 +142,7 @@ pub fn search_icons(icons: &[String], query: &str) -> Vec<String> {
         })
         .collect();
 
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|a| std::cmp::Reverse(a.0));
     scored.into_iter().map(|(_, name)| name.clone()).collect()
 }
 
@@ -180,7 +180,7 @@ pub fn search_collections<'a>(
         })
         .collect();
 
-    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.sort_by_key(|a| std::cmp::Reverse(a.0));
     scored.into_iter().map(|(_, c)| c).collect()
 }
 
@@ -254,6 +254,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assert_is_empty)]
     fn alias_expansion_unknown_word() {
         assert!(expand_aliases("xyzzynonexistent").is_empty());
     }
@@ -0,0 +1,419 @@
+//! Icon bodies flattened into painted paths.
+//!
+//! Targets without an SVG renderer, such as native GUI toolkits and 2D
+//! graphics libraries, draw icons as a list of paths. [`flatten`] turns every
+//! basic shape into absolute path data in viewBox coordinates and resolves
+//! the fill, stroke and transform it inherits. Constructs that cannot be
+//! expressed this way, such as text, gradients and masks, are reported in
+//! [`Flattened::unsupported`] rather than silently dropped.
+
+use std::collections::HashMap;
+
+use crate::color::{Color, Paint};
+use crate::geometry::{self, Transform};
+use crate::path::{self, Command, Segment};
+use crate::tree::{self, Element, Node, ParseError};
+
+/// How many nested `<use>` references are followed.
+const MAX_USE_DEPTH: usize = 8;
+
+/// The colour of a fill or stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapePaint {
+    None,
+    /// The colour chosen by the host, like CSS `currentColor`.
+    CurrentColor,
+    Color(Color),
+}
+
+impl ShapePaint {
+    #[must_use]
+    pub fn is_none(self) -> bool {
+        self == Self::None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// One painted path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    /// Absolute segments in viewBox coordinates.
+    pub segments: Vec<Segment>,
+    pub fill: ShapePaint,
+    pub fill_rule: FillRule,
+    pub fill_opacity: f64,
+    pub stroke: ShapePaint,
+    pub stroke_width: f64,
+    pub stroke_opacity: f64,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub miter_limit: f64,
+    /// Opacity of the shape and its groups, multiplied together.
+    pub opacity: f64,
+}
+
+impl Shape {
+    /// Path data for the segments.
+    #[must_use]
+    pub fn path_data(&self, precision: Option<u8>) -> String {
+        path::write(&self.segments, precision)
+    }
+}
+
+/// Result of [`flatten`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Flattened {
+    pub shapes: Vec<Shape>,
+    /// Descriptions of dropped or approximated constructs, each listed once.
+    pub unsupported: Vec<String>,
+}
+
+/// Painting state inherited from ancestors.
+#[derive(Clone, Copy)]
+struct Style {
+    transform: Transform,
+    fill: ShapePaint,
+    fill_rule: FillRule,
+    fill_opacity: f64,
+    stroke: ShapePaint,
+    stroke_width: f64,
+    stroke_opacity: f64,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f64,
+    opacity: f64,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            transform: Transform::IDENTITY,
+            fill: ShapePaint::Color(Color::BLACK),
+            fill_rule: FillRule::NonZero,
+            fill_opacity: 1.0,
+            stroke: ShapePaint::None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 4.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+struct Flatten<'a> {
+    ids: HashMap<&'a str, &'a Element>,
+    out: Flattened,
+}
+
+impl<'a> Flatten<'a> {
+    fn unsupported(&mut self, what: impl Into<String>) {
+        let what = what.into();
+        if !self.out.unsupported.contains(&what) {
+            self.out.unsupported.push(what);
+        }
+    }
+
+    fn paint(&mut self, value: &str, inherited: ShapePaint) -> ShapePaint {
+        match Paint::parse(value) {
+            Paint::None => ShapePaint::None,
+            Paint::CurrentColor => ShapePaint::CurrentColor,
+            Paint::Inherit => inherited,
+            Paint::Color(color) => ShapePaint::Color(color),
+            Paint::Url(_) => {
+                self.unsupported("gradient and pattern paints");
+                ShapePaint::None
+            }
+            Paint::Other(value) => {
+                self.unsupported(format!("colour `{value}`"));
+                ShapePaint::None
+            }
+        }
+    }
+
+    fn style(&mut self, el: &Element, parent: Style) -> Style {
+        let mut style = parent;
+        let number = |name| geometry::property(el, name).and_then(|v| v.parse::<f64>().ok());
+        if let Some(t) = el.attr("transform").and_then(Transform::parse) {
+            style.transform = style.transform.then(t);
+        }
+        if let Some(fill) = geometry::property(el, "fill") {
+            style.fill = self.paint(fill, parent.fill);
+        }
+        if let Some(stroke) = geometry::property(el, "stroke") {
+            style.stroke = self.paint(stroke, parent.stroke);
+        }
+        if let Some(width) = geometry::property(el, "stroke-width")
+            .and_then(|v| v.trim_end_matches("px").parse::<f64>().ok())
+        {
+            style.stroke_width = width;
+        }
+        match geometry::property(el, "fill-rule") {
+            Some("evenodd") => style.fill_rule = FillRule::EvenOdd,
+            Some("nonzero") => style.fill_rule = FillRule::NonZero,
+            _ => {}
+        }
+        match geometry::property(el, "stroke-linecap") {
+            Some("round") => style.line_cap = LineCap::Round,
+            Some("square") => style.line_cap = LineCap::Square,
+            Some("butt") => style.line_cap = LineCap::Butt,
+            _ => {}
+        }
+        match geometry::property(el, "stroke-linejoin") {
+            Some("round") => style.line_join = LineJoin::Round,
+            Some("bevel") => style.line_join = LineJoin::Bevel,
+            Some("miter" | "miter-clip" | "arcs") => style.line_join = LineJoin::Miter,
+            _ => {}
+        }
+        if let Some(limit) = number("stroke-miterlimit") {
+            style.miter_limit = limit;
+        }
+        if let Some(opacity) = number("fill-opacity") {
+            style.fill_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(opacity) = number("stroke-opacity") {
+            style.stroke_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(opacity) = number("opacity") {
+            style.opacity *= opacity.clamp(0.0, 1.0);
+        }
+        for (name, what) in [
+            ("clip-path", "clip paths"),
+            ("mask", "masks"),
+            ("filter", "filters"),
+            ("stroke-dasharray", "dashed strokes"),
+        ] {
+            if geometry::property(el, name).is_some_and(|v| v != "none") {
+                self.unsupported(what);
+            }
+        }
+        style
+    }
+
+    fn visit(&mut self, el: &'a Element, parent: Style, depth: usize) {
+        match el.name.as_str() {
+            "style" => return self.unsupported("`<style>` rules"),
+            "text" => return self.unsupported("text"),
+            "image" => return self.unsupported("images"),
+            _ => {}
+        }
+        if geometry::NON_RENDERED.contains(&el.name.as_str())
+            || geometry::property(el, "display") == Some("none")
+        {
+            return;
+        }
+        let mut style = self.style(el, parent);
+
+        match el.name.as_str() {
+            "use" => {
+                let target = el
+                    .attr("href")
+                    .or_else(|| el.attr("xlink:href"))
+                    .and_then(|href| href.strip_prefix('#'))
+                    .and_then(|id| self.ids.get(id).copied());
+                let Some(target) = target.filter(|_| depth < MAX_USE_DEPTH) else {
+                    return;
+                };
+                let number = |name| el.attr(name).and_then(|v| v.parse().ok()).unwrap_or(0.0);
+                style.transform =
+                    style
+                        .transform
+                        .then(Transform([1.0, 0.0, 0.0, 1.0, number("x"), number("y")]));
+                if target.name == "symbol" {
+                    for child in target.child_elements() {
+                        self.visit(child, style, depth + 1);
+                    }
+                } else {
+                    self.visit(target, style, depth + 1);
+                }
+            }
+            "g" | "svg" | "a" | "switch" => {
+                for child in el.child_elements() {
+                    self.visit(child, style, depth);
+                }
+            }
+            "path" | "rect" | "circle" | "ellipse" | "line" | "polyline" | "polygon" => {
+                self.shape(el, style);
+            }
+            other => self.unsupported(format!("`<{other}>` elements")),
+        }
+    }
+
+    fn shape(&mut self, el: &Element, style: Style) {
+        // Lines and polylines have no interior to fill
+        let fill = if matches!(el.name.as_str(), "line" | "polyline") {
+            ShapePaint::None
+        } else {
+            style.fill
+        };
+        if fill.is_none() && style.stroke.is_none() {
+            return;
+        }
+        let Some(segments) = geometry::shape_path(el).and_then(|d| path::parse(&d).ok()) else {
+            return;
+        };
+        let segments = if style.transform == Transform::IDENTITY {
+            path::to_absolute(&segments)
+        } else {
+            transform(&segments, style.transform)
+        };
+        if segments.is_empty() {
+            return;
+        }
+        let [a, b, c, d, ..] = style.transform.0;
+        let scale = (a * d - b * c).abs().sqrt();
+        self.out.shapes.push(Shape {
+            segments,
+            fill,
+            fill_rule: style.fill_rule,
+            fill_opacity: style.fill_opacity,
+            stroke: style.stroke,
+            stroke_width: style.stroke_width * scale,
+            stroke_opacity: style.stroke_opacity,
+            line_cap: style.line_cap,
+            line_join: style.line_join,
+            miter_limit: style.miter_limit,
+            opacity: style.opacity,
+        });
+    }
+}
+
+/// Apply an affine transform to a path, converting it to cubics first so
+/// arcs stay exact under rotation and skewing.
+fn transform(segments: &[Segment], transform: Transform) -> Vec<Segment> {
+    path::to_cubics(segments)
+        .into_iter()
+        .map(|mut segment| {
+            if segment.command != Command::ClosePath {
+                for [x, y] in segment.args.as_chunks_mut::<2>().0 {
+                    (*x, *y) = transform.apply(*x, *y);
+                }
+            }
+            segment
+        })
+        .collect()
+}
+
+/// Flatten a body into painted paths.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn flatten(body: &str) -> Result<Flattened, ParseError> {
+    flatten_with_fill(body, ShapePaint::Color(Color::BLACK))
+}
+
+/// Flatten a body whose root element sets `fill`, like the
+/// `fill="currentColor"` that component snippets put on `<svg>`.
+///
+/// # Errors
+/// Returns a [`ParseError`] if the body is not well-formed.
+pub fn flatten_with_fill(body: &str, fill: ShapePaint) -> Result<Flattened, ParseError> {
+    let nodes = tree::parse(body)?;
+    let mut flatten = Flatten {
+        ids: HashMap::new(),
+        out: Flattened::default(),
+    };
+    tree::walk(&nodes, &mut |el| {
+        if let Some(id) = el.attr("id") {
+            flatten.ids.insert(id, el);
+        }
+    });
+    for node in &nodes {
+        if let Node::Element(el) = node {
+            let style = Style {
+                fill,
+                ..Style::default()
+            };
+            flatten.visit(el, style, 0);
+        }
+    }
+    Ok(flatten.out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_from_root_fill() {
+        let body = r##"<path d="M0 0h1v1z"/><path fill="#f00" d="M0 0h1v1z"/>"##;
+        let flat = flatten_with_fill(body, ShapePaint::CurrentColor).unwrap();
+        assert_eq!(flat.shapes[0].fill, ShapePaint::CurrentColor);
+        assert_eq!(
+            flat.shapes[1].fill,
+            ShapePaint::Color(Color::rgb(255, 0, 0))
+        );
+        assert_eq!(
+            flatten(body).unwrap().shapes[0].fill,
+            ShapePaint::Color(Color::BLACK)
+        );
+    }
+
+    #[test]
+    fn resolves_inherited_paint() {
+        let body = r##"<g fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round"><path d="m0 0 h4"/><circle cx="5" cy="5" r="1" fill="#f00" stroke="none"/></g><line x1="0" y1="0" x2="1" y2="1"/>"##;
+        let flat = flatten(body).unwrap();
+        // The unstroked line outside the group draws nothing
+        assert_eq!(flat.shapes.len(), 2);
+        let line = &flat.shapes[0];
+        assert_eq!(line.path_data(None), "M0 0H4");
+        assert_eq!(line.fill, ShapePaint::None);
+        assert_eq!(line.stroke, ShapePaint::CurrentColor);
+        assert!((line.stroke_width - 2.0).abs() < 1e-9);
+        assert_eq!(line.line_cap, LineCap::Round);
+        let dot = &flat.shapes[1];
+        assert_eq!(dot.fill, ShapePaint::Color(Color::rgb(255, 0, 0)));
+        assert!(dot.stroke.is_none());
+        assert_eq!(flat.unsupported, Vec::<String>::new());
+    }
+
+    #[test]
+    fn applies_transforms_and_uses() {
+        let body = r##"<defs><rect id="r" width="2" height="2"/></defs><g transform="translate(10 0) scale(2)" stroke="#000"><use href="#r" x="1"/></g>"##;
+        let flat = flatten(body).unwrap();
+        assert_eq!(flat.shapes.len(), 1);
+        let shape = &flat.shapes[0];
+        assert!(shape.path_data(Some(3)).starts_with("M12 0C"));
+        assert!((shape.stroke_width - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reports_unsupported_constructs() {
+        let body = r#"<style>.a{fill:red}</style><text>a</text><path fill="url(#g)" d="M0 0h1"/><path mask="url(#m)" d="M0 0h1"/><path d="M0 0h1" fill="hsl(0, 50%, 50%)"/>"#;
+        let flat = flatten(body).unwrap();
+        assert_eq!(
+            flat.unsupported,
+            [
+                "`<style>` rules",
+                "text",
+                "gradient and pattern paints",
+                "masks",
+                "colour `hsl(0, 50%, 50%)`"
+            ]
+        );
+        assert_eq!(flat.shapes.len(), 1);
+    }
+}
@@ -0,0 +1,167 @@
+//! Icons as Slint `Path` elements.
+//!
+//! Each flattened [`Shape`] becomes one `Path` that fills its parent and maps
+//! the icon's viewBox onto it. `currentColor` paints use the component's
+//! `color` property, and stroke widths are scaled from viewBox units to the
+//! rendered width because Slint measures them in logical pixels.
+
+use std::fmt::Write;
+
+use crate::shapes::{FillRule, Flattened, LineCap, Shape, ShapePaint};
+
+/// Decimal places kept in path commands.
+const PRECISION: u8 = 3;
+
+/// A Slint string literal.
+#[must_use]
+pub fn string_literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn number(n: f64) -> String {
+    let scale = 10f64.powi(i32::from(PRECISION));
+    crate::svg::format_number((n * scale).round() / scale)
+}
+
+/// A brush expression for a paint at the given opacity.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn brush(paint: ShapePaint, opacity: f64) -> String {
+    match paint {
+        ShapePaint::None => "transparent".to_string(),
+        ShapePaint::CurrentColor if opacity < 1.0 => {
+            format!("root.color.with-alpha({})", number(opacity))
+        }
+        ShapePaint::CurrentColor => "root.color".to_string(),
+        ShapePaint::Color(mut color) => {
+            color.a = (f64::from(color.a) * opacity).round() as u8;
+            color.to_hex()
+        }
+    }
+}
+
+fn write_shape(shape: &Shape, view_box: [f64; 4], stroke_width: Option<&str>, out: &mut String) {
+    let [x, y, width, height] = view_box.map(number);
+    let _ = writeln!(out, "Path {{");
+    let _ = writeln!(out, "    width: 100%;");
+    let _ = writeln!(out, "    height: 100%;");
+    let _ = writeln!(out, "    viewbox-x: {x};");
+    let _ = writeln!(out, "    viewbox-y: {y};");
+    let _ = writeln!(out, "    viewbox-width: {width};");
+    let _ = writeln!(out, "    viewbox-height: {height};");
+    let _ = writeln!(
+        out,
+        "    commands: {};",
+        string_literal(&shape.path_data(Some(PRECISION)))
+    );
+    if !shape.fill.is_none() {
+        let _ = writeln!(out, "    fill: {};", brush(shape.fill, shape.fill_opacity));
+        if shape.fill_rule == FillRule::EvenOdd {
+            let _ = writeln!(out, "    fill-rule: evenodd;");
+        }
+    }
+    if !shape.stroke.is_none() {
+        let _ = writeln!(
+            out,
+            "    stroke: {};",
+            brush(shape.stroke, shape.stroke_opacity)
+        );
+        let stroke_width = stroke_width.map_or_else(|| number(shape.stroke_width), str::to_string);
+        let _ = writeln!(
+            out,
+            "    stroke-width: {stroke_width} * self.width / {width};"
+        );
+        match shape.line_cap {
+            LineCap::Butt => {}
+            LineCap::Round => {
+                let _ = writeln!(out, "    stroke-line-cap: round;");
+            }
+            LineCap::Square => {
+                let _ = writeln!(out, "    stroke-line-cap: square;");
+            }
+        }
+    }
+    if shape.opacity < 1.0 {
+        let _ = writeln!(out, "    opacity: {};", number(shape.opacity));
+    }
+    out.push_str("}\n");
+}
+
+/// `Path` elements for flattened shapes, separated by blank lines.
+///
+/// `view_box` is `[left, top, width, height]`. With `stroke_width`, every
+/// stroke uses that expression, such as a property, as its width in viewBox
+/// units.
+#[must_use]
+pub fn paths(flattened: &Flattened, view_box: [f64; 4], stroke_width: Option<&str>) -> String {
+    let mut out = String::new();
+    for shape in &flattened.shapes {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        write_shape(shape, view_box, stroke_width, &mut out);
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::flatten;
+
+    #[test]
+    fn writes_paths() {
+        let body = r#"<path fill-rule="evenodd" d="M0 0h24v24H0z"/><path fill="none" stroke="currentColor" stroke-linecap="round" stroke-width="2" stroke-opacity=".5" d="M1 1 3.3333 2"/>"#;
+        let flat = flatten(body).unwrap();
+        assert_eq!(
+            paths(&flat, [0.0, 0.0, 24.0, 24.0], None),
+            r#"Path {
+    width: 100%;
+    height: 100%;
+    viewbox-x: 0;
+    viewbox-y: 0;
+    viewbox-width: 24;
+    viewbox-height: 24;
+    commands: "M0 0H24V24H0Z";
+    fill: #000;
+    fill-rule: evenodd;
+}
+
+Path {
+    width: 100%;
+    height: 100%;
+    viewbox-x: 0;
+    viewbox-y: 0;
+    viewbox-width: 24;
+    viewbox-height: 24;
+    commands: "M1 1 3.333 2";
+    stroke: root.color.with-alpha(0.5);
+    stroke-width: 2 * self.width / 24;
+    stroke-line-cap: round;
+}"#
+        );
+        assert!(
+            paths(&flat, [0.0, 0.0, 24.0, 24.0], Some("root.stroke-width"))
+                .contains("stroke-width: root.stroke-width * self.width / 24;")
+        );
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(string_literal("a \"b\" \\ {c}\n"), r#""a \"b\" \\ {c}\n""#);
+    }
+}
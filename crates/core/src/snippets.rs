@@ -1,11 +1,26 @@
 //! Snippet generation for various output formats.
 //!
 //! Converts SVG icons to framework-specific code snippets for:
-//! - Rust frameworks: Leptos, Yew, Dioxus
+//! - Rust frameworks: Leptos, Yew, Dioxus, Sycamore
+//! - Native Rust GUIs: egui, iced, Slint
+//! - Rust vector paths: tiny-skia, kurbo, lyon
 //! - Web frameworks: Vue, React, Svelte, Solid, Qwik, Astro
-//! - Data formats: SVG, Base64, Data URL, CSS
+//! - Data formats: SVG, Android `VectorDrawable`, Base64, Data URL, CSS
+//!
+//! Every snippet is rendered from a [`Template`]; user templates loaded from
+//! files are listed next to the built-ins by a [`SnippetRegistry`].
+
+use std::cell::LazyCell;
 
-use crate::types::ResolvedIcon;
+use crate::data_uri::{self, DataUriContext};
+use crate::jsx::{self, JsxFlavor};
+use crate::optimize::{optimize, optimize_icon, OptimizeOptions};
+use crate::path_code::PathLibrary;
+use crate::rsx::{self, DioxusVersion};
+use crate::shapes::ShapePaint;
+use crate::template::{Template, TemplateError, Variables};
+use crate::types::{CollectionInfoRaw, ResolvedIcon};
+use crate::view_macro::{self, ViewMacro};
 
 /// Snippet output format category.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,12 +29,49 @@ pub enum SnippetCategory {
     Snippets,
     /// Rust framework components
     Rust,
+    /// Native Rust GUI toolkits
+    RustNative,
     /// Web framework components
     Components,
     /// URLs and links
     Links,
 }
 
+impl SnippetCategory {
+    /// Display name for UI.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Snippets => "Snippets",
+            Self::Rust => "Rust",
+            Self::RustNative => "Rust native",
+            Self::Components => "Components",
+            Self::Links => "Links",
+        }
+    }
+
+    /// All categories, in display order.
+    #[must_use]
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Rust,
+            Self::RustNative,
+            Self::Snippets,
+            Self::Components,
+            Self::Links,
+        ]
+    }
+
+    /// Parse a category name, ignoring case.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .find(|c| c.name().eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+}
+
 /// All supported snippet types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SnippetType {
@@ -28,11 +80,23 @@ pub enum SnippetType {
     SvgSymbol,
     Iconify,
     Jsx,
+    VectorDrawable,
 
     // Rust frameworks
     Leptos,
+    Leptos06,
     Yew,
     Dioxus,
+    Dioxus05,
+    Sycamore,
+
+    // Native Rust GUIs
+    Egui,
+    Iced,
+    Slint,
+    TinySkia,
+    Kurbo,
+    Lyon,
 
     // Web components
     Vue,
@@ -52,6 +116,42 @@ pub enum SnippetType {
 }
 
 impl SnippetType {
+    /// Stable identifier, e.g. `react-ts`.
+    #[must_use]
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::SvgSymbol => "svg-symbol",
+            Self::Iconify => "iconify",
+            Self::Jsx => "jsx",
+            Self::VectorDrawable => "vector-drawable",
+            Self::Leptos => "leptos",
+            Self::Leptos06 => "leptos-0.6",
+            Self::Yew => "yew",
+            Self::Dioxus => "dioxus",
+            Self::Dioxus05 => "dioxus-0.5",
+            Self::Sycamore => "sycamore",
+            Self::Egui => "egui",
+            Self::Iced => "iced",
+            Self::Slint => "slint",
+            Self::TinySkia => "tiny-skia",
+            Self::Kurbo => "kurbo",
+            Self::Lyon => "lyon",
+            Self::Vue => "vue",
+            Self::VueTs => "vue-ts",
+            Self::React => "react",
+            Self::ReactTs => "react-ts",
+            Self::Svelte => "svelte",
+            Self::Qwik => "qwik",
+            Self::Solid => "solid",
+            Self::Astro => "astro",
+            Self::Url => "url",
+            Self::DataUrl => "data-url",
+            Self::Base64 => "base64",
+            Self::CssBackground => "css-background",
+        }
+    }
+
     /// Display name for UI.
     #[must_use]
     pub fn name(&self) -> &'static str {
@@ -60,9 +160,17 @@ impl SnippetType {
             Self::SvgSymbol => "SVG Symbol",
             Self::Iconify => "Iconify",
             Self::Jsx => "JSX",
-            Self::Leptos => "Leptos",
+            Self::VectorDrawable => "VectorDrawable",
+            Self::Leptos | Self::Leptos06 => "Leptos",
             Self::Yew => "Yew",
-            Self::Dioxus => "Dioxus",
+            Self::Dioxus | Self::Dioxus05 => "Dioxus",
+            Self::Sycamore => "Sycamore",
+            Self::Egui => "egui",
+            Self::Iced => "iced",
+            Self::Slint => "Slint",
+            Self::TinySkia => "tiny-skia",
+            Self::Kurbo => "kurbo",
+            Self::Lyon => "lyon",
             Self::Vue | Self::VueTs => "Vue",
             Self::React | Self::ReactTs => "React",
             Self::Svelte => "Svelte",
@@ -81,6 +189,8 @@ impl SnippetType {
     pub fn tag(&self) -> Option<&'static str> {
         match self {
             Self::VueTs | Self::ReactTs => Some("TS"),
+            Self::Leptos06 => Some("0.6"),
+            Self::Dioxus05 => Some("0.5"),
             _ => None,
         }
     }
@@ -89,8 +199,18 @@ impl SnippetType {
     #[must_use]
     pub fn category(&self) -> SnippetCategory {
         match self {
-            Self::Svg | Self::SvgSymbol | Self::Iconify | Self::Jsx => SnippetCategory::Snippets,
-            Self::Leptos | Self::Yew | Self::Dioxus => SnippetCategory::Rust,
+            Self::Svg | Self::SvgSymbol | Self::Iconify | Self::Jsx | Self::VectorDrawable => {
+                SnippetCategory::Snippets
+            }
+            Self::Leptos
+            | Self::Leptos06
+            | Self::Yew
+            | Self::Dioxus
+            | Self::Dioxus05
+            | Self::Sycamore => SnippetCategory::Rust,
+            Self::Egui | Self::Iced | Self::Slint | Self::TinySkia | Self::Kurbo | Self::Lyon => {
+                SnippetCategory::RustNative
+            }
             Self::Vue
             | Self::VueTs
             | Self::React
@@ -105,6 +225,105 @@ impl SnippetType {
         }
     }
 
+    /// File extension used when the snippet is downloaded.
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Svg | Self::SvgSymbol => "svg",
+            Self::Iconify => "html",
+            Self::Jsx | Self::React => "jsx",
+            Self::VectorDrawable => "xml",
+            Self::Leptos
+            | Self::Leptos06
+            | Self::Yew
+            | Self::Dioxus
+            | Self::Dioxus05
+            | Self::Sycamore
+            | Self::Egui
+            | Self::Iced
+            | Self::TinySkia
+            | Self::Kurbo
+            | Self::Lyon => "rs",
+            Self::Slint => "slint",
+            Self::Vue | Self::VueTs => "vue",
+            Self::ReactTs | Self::Qwik | Self::Solid => "tsx",
+            Self::Svelte => "svelte",
+            Self::Astro => "astro",
+            Self::Url | Self::DataUrl | Self::Base64 => "txt",
+            Self::CssBackground => "css",
+        }
+    }
+
+    /// JSX dialect of the snippet's `jsx_body`, for JSX-based types.
+    #[must_use]
+    pub fn jsx_flavor(&self) -> Option<JsxFlavor> {
+        match self {
+            Self::Jsx | Self::React | Self::ReactTs => Some(JsxFlavor::React),
+            Self::Solid => Some(JsxFlavor::Solid),
+            Self::Qwik => Some(JsxFlavor::Qwik),
+            _ => None,
+        }
+    }
+
+    /// Dioxus release targeted by Dioxus snippets.
+    #[must_use]
+    pub fn dioxus_version(&self) -> Option<DioxusVersion> {
+        match self {
+            Self::Dioxus => Some(DioxusVersion::V06),
+            Self::Dioxus05 => Some(DioxusVersion::V05),
+            _ => None,
+        }
+    }
+
+    /// Flag variable set for types that share a template with another
+    /// release of their framework.
+    #[must_use]
+    pub fn variant(&self) -> Option<&'static str> {
+        match self {
+            Self::Leptos06 => Some("leptos06"),
+            Self::Dioxus05 => Some("dioxus05"),
+            _ => None,
+        }
+    }
+
+    /// Whether the snippet is TypeScript unless options say otherwise.
+    #[must_use]
+    pub fn typescript(&self) -> bool {
+        matches!(self, Self::VueTs | Self::ReactTs | Self::Qwik | Self::Solid)
+    }
+
+    /// Source of the built-in template.
+    #[must_use]
+    pub fn template(&self) -> &'static str {
+        match self {
+            Self::Svg => SVG_TEMPLATE,
+            Self::SvgSymbol => SVG_SYMBOL_TEMPLATE,
+            Self::Iconify => ICONIFY_TEMPLATE,
+            Self::Jsx => JSX_TEMPLATE,
+            Self::VectorDrawable => VECTOR_DRAWABLE_TEMPLATE,
+            Self::Leptos | Self::Leptos06 => LEPTOS_TEMPLATE,
+            Self::Yew => YEW_TEMPLATE,
+            Self::Dioxus | Self::Dioxus05 => DIOXUS_TEMPLATE,
+            Self::Sycamore => SYCAMORE_TEMPLATE,
+            Self::Egui => EGUI_TEMPLATE,
+            Self::Iced => ICED_TEMPLATE,
+            Self::Slint => SLINT_TEMPLATE,
+            Self::TinySkia => TINY_SKIA_TEMPLATE,
+            Self::Kurbo => KURBO_TEMPLATE,
+            Self::Lyon => LYON_TEMPLATE,
+            Self::Vue | Self::VueTs => VUE_TEMPLATE,
+            Self::React | Self::ReactTs => REACT_TEMPLATE,
+            Self::Svelte => SVELTE_TEMPLATE,
+            Self::Qwik => QWIK_TEMPLATE,
+            Self::Solid => SOLID_TEMPLATE,
+            Self::Astro => ASTRO_TEMPLATE,
+            Self::Url => URL_TEMPLATE,
+            Self::DataUrl => DATA_URL_TEMPLATE,
+            Self::Base64 => BASE64_TEMPLATE,
+            Self::CssBackground => CSS_BACKGROUND_TEMPLATE,
+        }
+    }
+
     /// All snippet types for iteration.
     #[must_use]
     pub fn all() -> &'static [Self] {
@@ -113,9 +332,19 @@ impl SnippetType {
             Self::SvgSymbol,
             Self::Iconify,
             Self::Jsx,
+            Self::VectorDrawable,
             Self::Leptos,
+            Self::Leptos06,
             Self::Yew,
             Self::Dioxus,
+            Self::Dioxus05,
+            Self::Sycamore,
+            Self::Egui,
+            Self::Iced,
+            Self::Slint,
+            Self::TinySkia,
+            Self::Kurbo,
+            Self::Lyon,
             Self::Vue,
             Self::VueTs,
             Self::React,
@@ -164,397 +393,1888 @@ pub fn to_component_name(icon: &str) -> String {
         .collect()
 }
 
-/// Clean SVG by keeping only essential attributes.
+/// Clean SVG by running the default optimization passes.
+///
+/// Markup that fails to parse is returned unchanged.
 #[must_use]
 pub fn clean_svg(svg: &str) -> String {
-    // Simple regex-free cleanup: keep viewBox, xmlns
-    svg.to_string()
+    optimize(svg, &OptimizeOptions::default()).map_or_else(|_| svg.to_string(), |o| o.markup)
+}
+
+/// Generate a snippet from an optimized copy of the icon body.
+///
+/// Falls back to the original body if it cannot be parsed.
+#[must_use]
+pub fn generate_optimized(
+    icon: &ResolvedIcon,
+    snippet_type: SnippetType,
+    options: &OptimizeOptions,
+) -> String {
+    match optimize_icon(icon, options) {
+        Ok((optimized, _)) => generate(&optimized, snippet_type),
+        Err(_) => generate(icon, snippet_type),
+    }
+}
+
+// =============================================================================
+// Options
+// =============================================================================
+
+/// How icons present themselves to assistive technology.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Accessibility {
+    /// No accessibility markup.
+    #[default]
+    Unspecified,
+    /// Hidden from assistive technology with `aria-hidden="true"` and
+    /// `focusable="false"`, for icons next to a text label.
+    Decorative,
+    /// Announced as an image: `role="img"`, a `<title>` with a unique id
+    /// that `aria-labelledby` points to, and an optional `<desc>`.
+    Meaningful,
+}
+
+/// Options shared by every snippet type.
+///
+/// Component snippets turn them into props and defaults; the SVG, data URL,
+/// Base64 and CSS snippets apply the size and extra attributes directly.
+/// Options that have no meaning for a type are ignored.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetOptions {
+    /// Default size in pixels.
+    pub size: u32,
+    /// Give components separate `width` and `height` props instead of a
+    /// single `size`.
+    pub separate_dimensions: bool,
+    /// Give monotone icons a `color` prop, defaulting to `currentColor`.
+    pub color_prop: bool,
+    /// Give stroke icons a `strokeWidth` prop, defaulting to the icon's own
+    /// stroke width. Ignored for icons with mixed stroke widths.
+    pub stroke_width_prop: bool,
+    /// Attributes added to the root `<svg>`, as `(name, value)`.
+    pub attributes: Vec<(String, String)>,
+    /// Component name instead of one derived from the icon id.
+    pub component_name: Option<String>,
+    /// Emit TypeScript (`Some(true)`) or JavaScript (`Some(false)`); `None`
+    /// keeps each type's own language.
+    pub typescript: Option<bool>,
+    /// Accessibility markup; see [`Accessibility`].
+    pub accessibility: Accessibility,
+    /// Title of meaningful icons; defaults to the icon name in words.
+    pub title: Option<String>,
+    /// Description of meaningful icons, written as `<desc>`.
+    pub description: Option<String>,
+    /// Give components of meaningful icons a `title` prop.
+    pub title_prop: bool,
+    /// Give path builders a `size` parameter and scale at runtime, instead
+    /// of baking the default size into their coordinates.
+    pub size_parameter: bool,
+}
+
+impl Default for SnippetOptions {
+    fn default() -> Self {
+        Self {
+            size: 24,
+            separate_dimensions: false,
+            color_prop: false,
+            stroke_width_prop: false,
+            attributes: Vec::new(),
+            component_name: None,
+            typescript: None,
+            accessibility: Accessibility::default(),
+            title: None,
+            description: None,
+            title_prop: false,
+            size_parameter: false,
+        }
+    }
 }
 
 /// Generate a snippet for the given type.
 #[must_use]
 pub fn generate(icon: &ResolvedIcon, snippet_type: SnippetType) -> String {
-    let svg = crate::svg::build_svg(icon);
-    let component_name = to_component_name(&format!("{}:{}", icon.prefix, icon.name));
-    let icon_id = format!("{}:{}", icon.prefix, icon.name);
+    generate_with(icon, snippet_type, &SnippetOptions::default())
+}
 
-    match snippet_type {
-        SnippetType::Svg => svg,
+/// Generate a snippet for the given type with options.
+///
+/// # Panics
+/// Panics if a built-in template fails to render, which the tests rule out.
+#[must_use]
+pub fn generate_with(
+    icon: &ResolvedIcon,
+    snippet_type: SnippetType,
+    options: &SnippetOptions,
+) -> String {
+    render(&SnippetTemplate::from(snippet_type), icon, None, options).expect("built-in template")
+}
 
-        SnippetType::SvgSymbol => format!(
-            r#"<symbol id="{}" viewBox="0 0 {} {}">{}</symbol>"#,
-            icon_id, icon.width, icon.height, icon.body
-        ),
+/// Render a snippet template for an icon.
+///
+/// Only the [`template_variables`] the template refers to are computed.
+///
+/// # Errors
+/// Returns a [`TemplateError`] if the template uses an unknown variable.
+pub fn render(
+    template: &SnippetTemplate,
+    icon: &ResolvedIcon,
+    info: Option<&CollectionInfoRaw>,
+    options: &SnippetOptions,
+) -> Result<String, TemplateError> {
+    let names = template.template.variables();
+    let mut variables = variables(icon, info, options, template.jsx, |name| {
+        names.contains(name)
+    });
+    variables.insert(
+        "typescript".to_string(),
+        flag(options.typescript.unwrap_or(template.typescript)),
+    );
+    if let Some(variant) = &template.variant {
+        variables.insert(variant.clone(), flag(true));
+    }
+    template.template.render(&variables)
+}
 
-        SnippetType::Iconify => format!(r#"<span class="iconify" data-icon="{icon_id}"></span>"#),
+/// An icon name in words, e.g. `Arrow left` for `arrow-left`.
+fn default_title(name: &str) -> String {
+    let words = name
+        .split(['-', '_'])
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut chars = words.chars();
+    chars.next().map_or_else(String::new, |c| {
+        c.to_uppercase().collect::<String>() + chars.as_str()
+    })
+}
 
-        SnippetType::Jsx => format!(
-            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width={{size}} height={{size}} {{...props}}>{}</svg>"#,
-            icon.width, icon.height, icon.body
-        ),
+fn flag(value: bool) -> String {
+    if value { "true" } else { "" }.to_string()
+}
 
-        // Rust frameworks
-        SnippetType::Leptos => generate_leptos(icon, &component_name),
-        SnippetType::Yew => generate_yew(icon, &component_name),
-        SnippetType::Dioxus => generate_dioxus(icon, &component_name),
-
-        // Web components
-        SnippetType::Vue => generate_vue(icon, &component_name, false),
-        SnippetType::VueTs => generate_vue(icon, &component_name, true),
-        SnippetType::React => generate_react(icon, &component_name, false),
-        SnippetType::ReactTs => generate_react(icon, &component_name, true),
-        SnippetType::Svelte => generate_svelte(icon),
-        SnippetType::Qwik => generate_qwik(icon, &component_name),
-        SnippetType::Solid => generate_solid(icon, &component_name),
-        SnippetType::Astro => generate_astro(icon),
-
-        // Links
-        SnippetType::Url => crate::svg::iconify_svg_url(&icon.prefix, &icon.name),
-        SnippetType::DataUrl => svg_to_data_url(&svg),
-        SnippetType::Base64 => svg_to_base64(&svg),
-        SnippetType::CssBackground => format!(
-            "background: url('{}') no-repeat center center / contain;",
-            svg_to_data_url(&svg)
-        ),
+/// One formatted attribute per line.
+fn attribute_lines(
+    attributes: &[(String, String)],
+    format: impl Fn(&str, &str) -> String,
+) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format(name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn jsx_attribute(name: &str, value: &str, flavor: JsxFlavor) -> String {
+    format!(
+        r#"{}="{}""#,
+        jsx::attribute_name(name, flavor),
+        crate::tree::escape_attr(value)
+    )
+}
+
+/// The uniform stroke width of a stroke icon, and its body with the
+/// `stroke-width` attributes removed so that a root value applies.
+///
+/// Returns `None` for icons without strokes, with differing stroke widths or
+/// with non-numeric ones.
+fn uniform_stroke_width(body: &str) -> Option<(String, String)> {
+    let mut nodes = crate::tree::parse(body).ok()?;
+    let mut stroked = false;
+    let mut widths: Vec<String> = Vec::new();
+    crate::tree::walk(&nodes, &mut |el| {
+        stroked |= el.attr("stroke").is_some_and(|s| s != "none");
+        if let Some(width) = el.attr("stroke-width") {
+            widths.push(width.trim().to_string());
+        }
+    });
+    widths.dedup();
+    // Rust's formatting gives a number that is also a valid Rust and
+    // JavaScript literal, e.g. `0.5` for `.5`
+    let width = match widths.as_slice() {
+        [] => 1.0,
+        [width] => width.parse::<f64>().ok().filter(|w| w.is_finite())?,
+        _ => return None,
+    }
+    .to_string();
+    if !stroked {
+        return None;
     }
+    strip_attribute(&mut nodes, "stroke-width");
+    Some((crate::tree::to_markup(&nodes), width))
+}
+
+fn strip_attribute(nodes: &mut [crate::tree::Node], name: &str) {
+    for node in nodes {
+        if let crate::tree::Node::Element(el) = node {
+            el.attributes.retain(|a| a.name != name);
+            strip_attribute(&mut el.children, name);
+        }
+    }
+}
+
+/// Variables available to snippet templates.
+///
+/// The icon's transformations are applied and monotone icons are switched to
+/// `currentColor` first. `size`, `dimensions`, `color`, `stroke_width` (the
+/// default stroke width, empty unless the prop applies) and the `attributes`
+/// variables come from the [`SnippetOptions`]; [`render`] falls back to the
+/// template's language for `typescript`. `decorative` and `meaningful` flag
+/// the [`Accessibility`] mode; meaningful icons also get `title`, `title_id`
+/// (the id prefix of the `<title>`), `title_prop` and `description`, and the
+/// `svg` variable carries the markup itself. `palette` is `true` for icons
+/// that keep their own colours and empty otherwise; collection fields are
+/// empty without `info`.
+/// `jsx_body` is the body as React JSX; [`render`] converts it for the
+/// template's [`JsxFlavor`] instead.
+/// `slint_paths` holds the body as Slint `Path` elements, `current_color`
+/// flags whether they use the `color` property, and `unsupported` lists
+/// what [`crate::shapes::flatten_with_fill`] could not convert. `tint_svg` is `svg`
+/// with `currentColor` set to white, for toolkits that tint by multiplying.
+/// `tiny_skia_body`, `kurbo_body` and `lyon_body` are function bodies
+/// returning the icon's paths (see [`crate::path_code`]), scaled at runtime
+/// when the `size_parameter` flag is set. `vector_drawable` is the icon as
+/// an Android `<vector>` document at the default size in dp. Variant flags
+/// such as `leptos06` are empty; [`render`] sets the template's own.
+#[must_use]
+pub fn template_variables(
+    icon: &ResolvedIcon,
+    info: Option<&CollectionInfoRaw>,
+    options: &SnippetOptions,
+) -> Variables {
+    variables(icon, info, options, JsxFlavor::React, |_| true)
+}
+
+/// The [`template_variables`] that `wanted` selects. Only those are
+/// computed, with `jsx_body` and `jsx_attributes` in the given dialect.
+fn variables(
+    icon: &ResolvedIcon,
+    info: Option<&CollectionInfoRaw>,
+    options: &SnippetOptions,
+    jsx_flavor: JsxFlavor,
+    wanted: impl Fn(&str) -> bool,
+) -> Variables {
+    let (icon, palette) =
+        crate::color::prepare_for_tinting(&crate::svg::apply_transformations(icon));
+    let icon_id = format!("{}:{}", icon.prefix, icon.name);
+    // Component bodies leave the stroke width to the root element's prop;
+    // standalone SVGs keep it
+    let (body, stroke_width) = options
+        .stroke_width_prop
+        .then(|| uniform_stroke_width(&icon.body))
+        .flatten()
+        .unwrap_or_else(|| (icon.body.clone(), String::new()));
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| default_title(&icon.name));
+    let title_id = format!("{}-title", crate::sprite::safe_id(&icon_id));
+    let meaningful = options.accessibility == Accessibility::Meaningful;
+    let root = LazyCell::new(|| {
+        let mut svg = crate::svg::icon_to_svg(
+            &icon,
+            &crate::svg::IconCustomisations {
+                height: crate::svg::IconSize::Number(f64::from(options.size)),
+                ..Default::default()
+            },
+        );
+        for (name, value) in &options.attributes {
+            svg.set_attribute(name, crate::tree::escape_attr(value));
+        }
+        match options.accessibility {
+            Accessibility::Unspecified => {}
+            Accessibility::Decorative => {
+                svg.set_attribute("aria-hidden", "true".to_string());
+                svg.set_attribute("focusable", "false".to_string());
+            }
+            Accessibility::Meaningful => {
+                svg.set_attribute("role", "img".to_string());
+                svg.set_attribute("aria-labelledby", title_id.clone());
+                let description = options.description.as_ref().map_or_else(String::new, |d| {
+                    format!("<desc>{}</desc>", crate::tree::escape_text(d))
+                });
+                svg.body = format!(
+                    r#"<title id="{title_id}">{}</title>{description}{}"#,
+                    crate::tree::escape_text(&title),
+                    svg.body
+                );
+            }
+        }
+        svg
+    });
+    let svg = LazyCell::new(|| root.to_svg());
+    // Toolkits that tint by multiplying need white where `currentColor` is
+    let tint_svg = || {
+        if palette {
+            svg.clone()
+        } else {
+            let mut root = root.clone();
+            root.set_attribute("color", "#fff".to_string());
+            root.to_svg()
+        }
+    };
+    // Like the component templates, unfilled shapes of tintable icons
+    // follow the text colour
+    let root_fill = if palette {
+        ShapePaint::Color(crate::color::Color::BLACK)
+    } else {
+        ShapePaint::CurrentColor
+    };
+    let flattened =
+        LazyCell::new(|| crate::shapes::flatten_with_fill(&body, root_fill).unwrap_or_default());
+    let view_box = [
+        f64::from(icon.left),
+        f64::from(icon.top),
+        f64::from(icon.width),
+        f64::from(icon.height),
+    ];
+    // Path builders and drawables keep the icon's own stroke widths
+    let geometry = LazyCell::new(|| {
+        if stroke_width.is_empty() {
+            flattened.clone()
+        } else {
+            crate::shapes::flatten_with_fill(&icon.body, root_fill).unwrap_or_default()
+        }
+    });
+    let path_body = |library| {
+        crate::path_code::function_body(
+            &geometry,
+            library,
+            view_box,
+            (!options.size_parameter).then_some(f64::from(options.size)),
+        )
+    };
+    let author = info.and_then(|i| i.author.as_ref());
+    let license = info.and_then(|i| i.license.as_ref());
+
+    let variables: &[(&str, &dyn Fn() -> String)] = &[
+        ("prefix", &|| icon.prefix.clone()),
+        ("name", &|| icon.name.clone()),
+        ("component_name", &|| {
+            options
+                .component_name
+                .clone()
+                .unwrap_or_else(|| to_component_name(&icon_id))
+        }),
+        ("size", &|| options.size.to_string()),
+        ("dimensions", &|| flag(options.separate_dimensions)),
+        ("typescript", &|| flag(options.typescript.unwrap_or(false))),
+        ("color", &|| flag(options.color_prop && !palette)),
+        ("stroke_width", &|| stroke_width.clone()),
+        ("decorative", &|| {
+            flag(options.accessibility == Accessibility::Decorative)
+        }),
+        ("meaningful", &|| flag(meaningful)),
+        ("title", &|| title.clone()),
+        ("title_id", &|| title_id.clone()),
+        ("title_prop", &|| flag(meaningful && options.title_prop)),
+        ("description", &|| {
+            options
+                .description
+                .clone()
+                .filter(|_| meaningful)
+                .unwrap_or_default()
+        }),
+        ("attributes", &|| {
+            attribute_lines(&options.attributes, |name, value| {
+                format!(r#"{name}="{}""#, crate::tree::escape_attr(value))
+            })
+        }),
+        ("jsx_attributes", &|| {
+            attribute_lines(&options.attributes, |name, value| {
+                jsx_attribute(name, value, jsx_flavor)
+            })
+        }),
+        ("view_attributes", &|| {
+            attribute_lines(&options.attributes, |name, value| {
+                format!("{name}={}", view_macro::string_literal(value))
+            })
+        }),
+        ("sycamore_attributes", &|| {
+            attribute_lines(&options.attributes, |name, value| {
+                format!("{name}={},", view_macro::string_literal(value))
+            })
+        }),
+        ("rsx_attributes", &|| {
+            attribute_lines(&options.attributes, |name, value| {
+                format!(
+                    "{}: {},",
+                    rsx::attribute_name(name),
+                    rsx::string_literal(value)
+                )
+            })
+        }),
+        ("width", &|| icon.width.to_string()),
+        ("height", &|| icon.height.to_string()),
+        ("view_box", &|| crate::svg::view_box(&icon)),
+        ("jsx_body", &|| jsx::body_to_jsx(&body, jsx_flavor)),
+        ("rsx_body", &|| rsx::body_to_rsx(&body)),
+        ("leptos_body", &|| {
+            view_macro::body_to_view(&body, ViewMacro::Leptos)
+        }),
+        ("yew_body", &|| {
+            view_macro::body_to_view(&body, ViewMacro::Yew)
+        }),
+        ("sycamore_body", &|| {
+            view_macro::body_to_view(&body, ViewMacro::Sycamore)
+        }),
+        ("slint_paths", &|| {
+            crate::slint::paths(
+                &flattened,
+                view_box,
+                (!stroke_width.is_empty()).then_some("root.stroke-width"),
+            )
+        }),
+        ("unsupported", &|| flattened.unsupported.join(", ")),
+        ("current_color", &|| {
+            flag(
+                flattened
+                    .shapes
+                    .iter()
+                    .any(|shape| [shape.fill, shape.stroke].contains(&ShapePaint::CurrentColor)),
+            )
+        }),
+        ("tiny_skia_body", &|| path_body(PathLibrary::TinySkia)),
+        ("kurbo_body", &|| path_body(PathLibrary::Kurbo)),
+        ("lyon_body", &|| path_body(PathLibrary::Lyon)),
+        ("size_parameter", &|| flag(options.size_parameter)),
+        ("vector_drawable", &|| {
+            crate::vector_drawable::vector(&geometry, view_box, f64::from(options.size))
+        }),
+        ("tint_svg", &tint_svg),
+        ("body", &|| body.clone()),
+        ("palette", &|| flag(palette)),
+        ("leptos06", &String::new),
+        ("dioxus05", &String::new),
+        ("symbol", &|| {
+            crate::sprite::symbol(&icon, &crate::sprite::safe_id(&icon_id))
+        }),
+        ("url", &|| {
+            crate::svg::iconify_svg_url(&icon.prefix, &icon.name)
+        }),
+        ("data_url", &|| svg_to_data_url(&svg)),
+        ("css_url", &|| {
+            DataUriContext::Css.quote(&data_uri::encode(&svg, DataUriContext::Css))
+        }),
+        ("base64", &|| svg_to_base64(&svg)),
+        ("svg", &|| svg.clone()),
+        ("icon_id", &|| icon_id.clone()),
+        ("collection", &|| {
+            info.map(|i| i.name.clone()).unwrap_or_default()
+        }),
+        ("author", &|| {
+            author.map(|a| a.name.clone()).unwrap_or_default()
+        }),
+        ("author_url", &|| {
+            author.and_then(|a| a.url.clone()).unwrap_or_default()
+        }),
+        ("license", &|| {
+            license.map(|l| l.title.clone()).unwrap_or_default()
+        }),
+        ("license_url", &|| {
+            license.and_then(|l| l.url.clone()).unwrap_or_default()
+        }),
+        ("license_spdx", &|| {
+            license.and_then(|l| l.spdx.clone()).unwrap_or_default()
+        }),
+    ];
+    variables
+        .iter()
+        .filter(|(name, _)| wanted(name))
+        .map(|(name, value)| (name.to_string(), value()))
+        .collect()
 }
 
 // =============================================================================
-// Rust Framework Generators
+// Templates
 // =============================================================================
 
-fn generate_leptos(icon: &ResolvedIcon, name: &str) -> String {
-    format!(
-        r#"use leptos::prelude::*;
+/// A snippet template: a built-in [`SnippetType`] or a user template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetTemplate {
+    /// Identifier, unique within a [`SnippetRegistry`].
+    pub id: String,
+    pub name: String,
+    pub tag: Option<String>,
+    pub category: SnippetCategory,
+    /// File extension used when the snippet is downloaded.
+    pub extension: String,
+    /// JSX dialect used for the `jsx_body` variable.
+    pub jsx: JsxFlavor,
+    /// Whether the template emits TypeScript unless options say otherwise.
+    pub typescript: bool,
+    /// Variant flag set when rendering; see [`SnippetType::variant`].
+    pub variant: Option<String>,
+    template: Template,
+}
+
+impl From<SnippetType> for SnippetTemplate {
+    fn from(snippet_type: SnippetType) -> Self {
+        Self {
+            id: snippet_type.id().to_string(),
+            name: snippet_type.name().to_string(),
+            tag: snippet_type.tag().map(str::to_string),
+            category: snippet_type.category(),
+            extension: snippet_type.extension().to_string(),
+            jsx: snippet_type.jsx_flavor().unwrap_or_default(),
+            typescript: snippet_type.typescript(),
+            variant: snippet_type.variant().map(str::to_string),
+            template: Template::parse(snippet_type.template()).expect("built-in template"),
+        }
+    }
+}
+
+impl SnippetTemplate {
+    /// Load a user template from a file.
+    ///
+    /// The file may start with a front matter block between `---` lines
+    /// setting `name`, `tag`, `category` (any [`SnippetCategory::name`]:
+    /// `snippets`, `rust`, `rust native`, `components` or `links`),
+    /// `extension`, `jsx` (`react`, `solid` or `qwik`, the
+    /// dialect of `jsx_body`) and `typescript` (`true` or `false`). The id
+    /// is the file name up to its first `.`; the extension defaults to the
+    /// one before a trailing `.tpl`, so `icon.tsx.tpl` produces `.tsx`
+    /// files, and `.ts` and `.tsx` templates default to TypeScript.
+    ///
+    /// # Errors
+    /// Returns a [`TemplateError`] for unknown front matter keys or
+    /// categories and for malformed templates.
+    pub fn from_file(file_name: &str, contents: &str) -> Result<Self, TemplateError> {
+        let base = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+        let base = base
+            .strip_suffix(".tpl")
+            .or_else(|| base.strip_suffix(".tmpl"))
+            .unwrap_or(base);
+        let (id, extension) = base.split_once('.').unwrap_or((base, "txt"));
+        let mut template = Self {
+            id: id.to_string(),
+            name: id.to_string(),
+            tag: None,
+            category: SnippetCategory::Snippets,
+            extension: extension.rsplit('.').next().unwrap_or("txt").to_string(),
+            jsx: JsxFlavor::default(),
+            typescript: false,
+            variant: None,
+            template: Template::default(),
+        };
+
+        template.typescript = matches!(template.extension.as_str(), "ts" | "tsx");
+
+        let mut body = contents;
+        let mut header_lines = 0;
+        if let Some(rest) = contents
+            .strip_prefix("---\n")
+            .or_else(|| contents.strip_prefix("---\r\n"))
+        {
+            let mut lines = rest.split_inclusive('\n');
+            let mut consumed = 4;
+            header_lines = 1;
+            let mut closed = false;
+            for line in lines.by_ref() {
+                consumed += line.len();
+                header_lines += 1;
+                let line = line.trim();
+                if line == "---" {
+                    closed = true;
+                    break;
+                }
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let error = |message: String| TemplateError {
+                    line: header_lines,
+                    message,
+                };
+                let (key, value) = line
+                    .split_once(':')
+                    .ok_or_else(|| error(format!("expected `key: value`, got `{line}`")))?;
+                let value = value.trim().trim_matches('"').to_string();
+                match key.trim() {
+                    "name" => template.name = value,
+                    "tag" => template.tag = Some(value),
+                    "extension" => template.extension = value.trim_start_matches('.').to_string(),
+                    "typescript" => {
+                        template.typescript = value.parse().map_err(|_| {
+                            error(format!("expected `true` or `false`, got `{value}`"))
+                        })?;
+                    }
+                    "jsx" => {
+                        template.jsx = JsxFlavor::parse(&value)
+                            .ok_or_else(|| error(format!("unknown JSX flavor `{value}`")))?;
+                    }
+                    "category" => {
+                        template.category = SnippetCategory::parse(&value)
+                            .ok_or_else(|| error(format!("unknown category `{value}`")))?;
+                    }
+                    other => return Err(error(format!("unknown key `{other}`"))),
+                }
+            }
+            if !closed {
+                return Err(TemplateError {
+                    line: 1,
+                    message: "front matter is never closed".to_string(),
+                });
+            }
+            body = &contents[consumed.min(contents.len())..];
+        }
+
+        template.template = Template::parse(body).map_err(|e| TemplateError {
+            line: e.line + header_lines,
+            ..e
+        })?;
+        Ok(template)
+    }
+
+    /// File extension for a snippet rendered with `options`, switching
+    /// between the JavaScript and TypeScript extensions when the options
+    /// override the template's language.
+    #[must_use]
+    pub fn file_extension(&self, options: &SnippetOptions) -> String {
+        match (options.typescript, self.extension.as_str()) {
+            (Some(true), "js") => "ts".to_string(),
+            (Some(true), "jsx") => "tsx".to_string(),
+            (Some(false), "ts") => "js".to_string(),
+            (Some(false), "tsx") => "jsx".to_string(),
+            (_, extension) => extension.to_string(),
+        }
+    }
+}
+
+/// Built-in snippet templates plus user templates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnippetRegistry {
+    custom: Vec<SnippetTemplate>,
+}
+
+impl SnippetRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a user template, replacing any user template with the same id.
+    pub fn register(&mut self, template: SnippetTemplate) {
+        match self.custom.iter_mut().find(|t| t.id == template.id) {
+            Some(existing) => *existing = template,
+            None => self.custom.push(template),
+        }
+    }
+
+    /// Remove a user template. Returns whether it existed.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let len = self.custom.len();
+        self.custom.retain(|t| t.id != id);
+        self.custom.len() != len
+    }
+
+    /// The user templates, in registration order.
+    #[must_use]
+    pub fn custom(&self) -> &[SnippetTemplate] {
+        &self.custom
+    }
+
+    /// Every template: built-ins in [`SnippetType::all`] order, then user
+    /// templates.
+    #[must_use]
+    pub fn all(&self) -> Vec<SnippetTemplate> {
+        SnippetType::all()
+            .iter()
+            .map(|&t| SnippetTemplate::from(t))
+            .chain(self.custom.iter().cloned())
+            .collect()
+    }
+
+    /// Templates by category.
+    #[must_use]
+    pub fn by_category(&self, category: SnippetCategory) -> Vec<SnippetTemplate> {
+        self.all()
+            .into_iter()
+            .filter(|t| t.category == category)
+            .collect()
+    }
+
+    /// A template by id. User templates may not shadow built-ins.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<SnippetTemplate> {
+        self.all().into_iter().find(|t| t.id == id)
+    }
+}
+
+const SVG_TEMPLATE: &str = "{{ svg }}";
 
+const SVG_SYMBOL_TEMPLATE: &str = "{{ symbol }}";
+
+const ICONIFY_TEMPLATE: &str = r#"<span class="iconify" data-icon="{{ icon_id }}"></span>"#;
+
+const JSX_TEMPLATE: &str = r#"<svg
+  xmlns="http://www.w3.org/2000/svg"
+  viewBox="{{ view_box }}"
+{% if dimensions %}
+  width={width}
+  height={height}
+{% else %}
+  width={size}
+  height={size}
+{% endif %}
+{% if not palette %}
+  fill="currentColor"
+{% endif %}
+{% if decorative %}
+  aria-hidden="true"
+  focusable="false"
+{% endif %}
+{% if meaningful %}
+  role="img"
+  aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+  color={color}
+{% endif %}
+{% if stroke_width %}
+  strokeWidth={strokeWidth}
+{% endif %}
+{% if jsx_attributes %}
+  {{ jsx_attributes | indent(2) }}
+{% endif %}
+  {...props}
+>
+{% if meaningful %}
+  <title id={titleId}>{% if title_prop %}{title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+  <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+  {{ jsx_body }}
+</svg>"#;
+
+const VECTOR_DRAWABLE_TEMPLATE: &str = "{% if unsupported %}
+<!-- Not converted: {{ unsupported }} -->
+{% endif %}
+{{ vector_drawable }}
+";
+
+const LEPTOS_TEMPLATE: &str = r#"{% if meaningful %}
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+{% endif %}
+{% if leptos06 %}
+use leptos::*;
+{% else %}
+use leptos::prelude::*;
+{% endif %}
+
+{% if meaningful %}
+static TITLE_IDS: AtomicUsize = AtomicUsize::new(0);
+
+{% endif %}
 #[component]
-pub fn {name}(
+pub fn {{ component_name }}(
     #[prop(optional)] class: &'static str,
-    #[prop(default = 24)] size: u32,
-) -> impl IntoView {{
-    view! {{
+{% if dimensions %}
+    #[prop(default = {{ size }})] width: u32,
+    #[prop(default = {{ size }})] height: u32,
+{% else %}
+    #[prop(default = {{ size }})] size: u32,
+{% endif %}
+{% if color %}
+    #[prop(default = "currentColor")] color: &'static str,
+{% endif %}
+{% if stroke_width %}
+    #[prop(default = {{ stroke_width }}_f64)] stroke_width: f64,
+{% endif %}
+{% if title_prop %}
+    #[prop(default = {{ title | rust }})] title: &'static str,
+{% endif %}
+) -> impl IntoView {
+{% if meaningful %}
+    let title_id = format!(
+        "{{ title_id }}-{}",
+        TITLE_IDS.fetch_add(1, Ordering::Relaxed)
+    );
+{% endif %}
+    view! {
         <svg
             xmlns="http://www.w3.org/2000/svg"
-            viewBox="0 0 {w} {h}"
+            viewBox="{{ view_box }}"
+{% if dimensions %}
+            width=width
+            height=height
+{% else %}
             width=size
             height=size
+{% endif %}
             class=class
+{% if not palette %}
             fill="currentColor"
+{% endif %}
+{% if decorative %}
+            aria-hidden="true"
+            focusable="false"
+{% endif %}
+{% if meaningful %}
+            role="img"
+            aria-labelledby=title_id.clone()
+{% endif %}
+{% if color %}
+            color=color
+{% endif %}
+{% if stroke_width %}
+            stroke-width=stroke_width
+{% endif %}
+{% if view_attributes %}
+            {{ view_attributes | indent(12) }}
+{% endif %}
         >
-            {body}
+{% if meaningful %}
+            <title id=title_id>{% if title_prop %}{title}{% else %}{{ title | rust }}{% endif %}</title>
+{% if description %}
+            <desc>{{ description | rust }}</desc>
+{% endif %}
+{% endif %}
+            {{ leptos_body | indent(12) }}
         </svg>
-    }}
-}}"#,
-        name = name,
-        w = icon.width,
-        h = icon.height,
-        body = icon.body,
-    )
-}
+    }
+}"#;
 
-fn generate_yew(icon: &ResolvedIcon, name: &str) -> String {
-    format!(
-        r#"use yew::prelude::*;
+const YEW_TEMPLATE: &str = r#"{% if meaningful %}
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+{% endif %}
+use yew::prelude::*;
+
+{% if meaningful %}
+static TITLE_IDS: AtomicUsize = AtomicUsize::new(0);
 
+{% endif %}
 #[derive(Properties, PartialEq)]
-pub struct Props {{
+pub struct Props {
     #[prop_or_default]
     pub class: Classes,
-    #[prop_or(24)]
+{% if dimensions %}
+    #[prop_or({{ size }})]
+    pub width: u32,
+    #[prop_or({{ size }})]
+    pub height: u32,
+{% else %}
+    #[prop_or({{ size }})]
     pub size: u32,
-}}
+{% endif %}
+{% if color %}
+    #[prop_or(AttrValue::Static("currentColor"))]
+    pub color: AttrValue,
+{% endif %}
+{% if stroke_width %}
+    #[prop_or({{ stroke_width }}_f64)]
+    pub stroke_width: f64,
+{% endif %}
+{% if title_prop %}
+    #[prop_or(AttrValue::Static({{ title | rust }}))]
+    pub title: AttrValue,
+{% endif %}
+}
 
 #[function_component]
-pub fn {name}(props: &Props) -> Html {{
-    html! {{
+pub fn {{ component_name }}(props: &Props) -> Html {
+{% if meaningful %}
+    let title_id = use_state(|| {
+        format!(
+            "{{ title_id }}-{}",
+            TITLE_IDS.fetch_add(1, Ordering::Relaxed)
+        )
+    });
+{% endif %}
+    html! {
         <svg
             xmlns="http://www.w3.org/2000/svg"
-            viewBox="0 0 {w} {h}"
-            width={{props.size}}
-            height={{props.size}}
-            class={{props.class.clone()}}
+            viewBox="{{ view_box }}"
+{% if dimensions %}
+            width={props.width.to_string()}
+            height={props.height.to_string()}
+{% else %}
+            width={props.size.to_string()}
+            height={props.size.to_string()}
+{% endif %}
+            class={props.class.clone()}
+{% if not palette %}
             fill="currentColor"
+{% endif %}
+{% if decorative %}
+            aria-hidden="true"
+            focusable="false"
+{% endif %}
+{% if meaningful %}
+            role="img"
+            aria-labelledby={(*title_id).clone()}
+{% endif %}
+{% if color %}
+            color={props.color.clone()}
+{% endif %}
+{% if stroke_width %}
+            stroke-width={props.stroke_width.to_string()}
+{% endif %}
+{% if view_attributes %}
+            {{ view_attributes | indent(12) }}
+{% endif %}
         >
-            {body}
+{% if meaningful %}
+            <title id={(*title_id).clone()}>{% if title_prop %}{ props.title.clone() }{% else %}{ {{ title | rust }} }{% endif %}</title>
+{% if description %}
+            <desc>{ {{ description | rust }} }</desc>
+{% endif %}
+{% endif %}
+            {{ yew_body | indent(12) }}
         </svg>
-    }}
-}}"#,
-        name = name,
-        w = icon.width,
-        h = icon.height,
-        body = icon.body,
-    )
-}
+    }
+}"#;
 
-fn generate_dioxus(icon: &ResolvedIcon, name: &str) -> String {
-    // Convert SVG body to Dioxus RSX format
-    let rsx_body = svg_body_to_dioxus_rsx(&icon.body);
+const DIOXUS_TEMPLATE: &str = r#"{% if meaningful %}
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-    format!(
-        r#"use dioxus::prelude::*;
+{% endif %}
+use dioxus::prelude::*;
+
+{% if meaningful %}
+static TITLE_IDS: AtomicUsize = AtomicUsize::new(0);
 
+{% endif %}
 #[component]
-pub fn {name}(
+pub fn {{ component_name }}(
+{% if dioxus05 %}
+    #[props(default)] class: String,
+{% else %}
     #[props(default)] class: Option<String>,
-    #[props(default = 24)] size: u32,
-) -> Element {{
-    rsx! {{
-        svg {{
+{% endif %}
+{% if dimensions %}
+    #[props(default = {{ size }})] width: u32,
+    #[props(default = {{ size }})] height: u32,
+{% else %}
+    #[props(default = {{ size }})] size: u32,
+{% endif %}
+{% if color %}
+    #[props(default = "currentColor".to_string())] color: String,
+{% endif %}
+{% if stroke_width %}
+    #[props(default = {{ stroke_width }}_f64)] stroke_width: f64,
+{% endif %}
+{% if title_prop %}
+    #[props(default = {{ title | rust }}.to_string())] title: String,
+{% endif %}
+) -> Element {
+{% if meaningful %}
+    let title_id = use_hook(|| {
+        format!(
+            "{{ title_id }}-{}",
+            TITLE_IDS.fetch_add(1, Ordering::Relaxed)
+        )
+    });
+{% endif %}
+    rsx! {
+        svg {
             xmlns: "http://www.w3.org/2000/svg",
-            view_box: "0 0 {w} {h}",
+            view_box: "{{ view_box }}",
+{% if dioxus05 %}
+{% if dimensions %}
+            width: "{width}",
+            height: "{height}",
+{% else %}
+            width: "{size}",
+            height: "{size}",
+{% endif %}
+            class: "{class}",
+{% else %}
+{% if dimensions %}
+            width,
+            height,
+{% else %}
             width: size,
             height: size,
+{% endif %}
             class,
+{% endif %}
+{% if not palette %}
             fill: "currentColor",
-            {rsx_body}
-        }}
-    }}
-}}"#,
-        name = name,
-        w = icon.width,
-        h = icon.height,
-        rsx_body = rsx_body,
-    )
+{% endif %}
+{% if decorative %}
+            aria_hidden: "true",
+            "focusable": "false",
+{% endif %}
+{% if meaningful %}
+            role: "img",
+            aria_labelledby: "{title_id}",
+{% endif %}
+{% if color %}
+            {% if dioxus05 %}color: "{color}"{% else %}color{% endif %},
+{% endif %}
+{% if stroke_width %}
+            {% if dioxus05 %}stroke_width: "{stroke_width}"{% else %}stroke_width{% endif %},
+{% endif %}
+{% if rsx_attributes %}
+            {{ rsx_attributes | indent(12) }}
+{% endif %}
+{% if meaningful %}
+            title { id: "{title_id}", {% if title_prop %}"{title}"{% else %}{ {{ title | rust }} }{% endif %} }
+{% if description %}
+            desc { { {{ description | rust }} } }
+{% endif %}
+{% endif %}
+            {{ rsx_body | indent(12) }}
+        }
+    }
+}"#;
+
+const SYCAMORE_TEMPLATE: &str = r#"{% if meaningful %}
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+{% endif %}
+use sycamore::prelude::*;
+
+{% if meaningful %}
+static TITLE_IDS: AtomicUsize = AtomicUsize::new(0);
+
+{% endif %}
+#[derive(Prop)]
+pub struct {{ component_name }}Props {
+    #[builder(default)]
+    pub class: String,
+{% if dimensions %}
+    #[builder(default = {{ size }})]
+    pub width: u32,
+    #[builder(default = {{ size }})]
+    pub height: u32,
+{% else %}
+    #[builder(default = {{ size }})]
+    pub size: u32,
+{% endif %}
+{% if color %}
+    #[builder(default = "currentColor".to_string())]
+    pub color: String,
+{% endif %}
+{% if stroke_width %}
+    #[builder(default = {{ stroke_width }}_f64)]
+    pub stroke_width: f64,
+{% endif %}
+{% if title_prop %}
+    #[builder(default = {{ title | rust }}.to_string())]
+    pub title: String,
+{% endif %}
 }
 
-/// Convert SVG inner body to Dioxus RSX syntax.
-fn svg_body_to_dioxus_rsx(body: &str) -> String {
-    // Simple conversion: replace kebab-case attrs with snake_case
-    // and convert self-closing tags to RSX format
-    let mut result = body.to_string();
-
-    // Common SVG attribute conversions
-    let replacements = [
-        ("fill-rule", "fill_rule"),
-        ("clip-rule", "clip_rule"),
-        ("stroke-width", "stroke_width"),
-        ("stroke-linecap", "stroke_linecap"),
-        ("stroke-linejoin", "stroke_linejoin"),
-        ("stroke-miterlimit", "stroke_miterlimit"),
-        ("fill-opacity", "fill_opacity"),
-        ("stroke-opacity", "stroke_opacity"),
-    ];
-
-    for (from, to) in replacements {
-        result = result.replace(from, to);
+#[component]
+pub fn {{ component_name }}<G: Html>(cx: Scope, props: {{ component_name }}Props) -> View<G> {
+{% if meaningful %}
+    let title_id = create_ref(
+        cx,
+        format!(
+            "{{ title_id }}-{}",
+            TITLE_IDS.fetch_add(1, Ordering::Relaxed)
+        ),
+    );
+{% endif %}
+    view! { cx,
+        svg(
+            xmlns="http://www.w3.org/2000/svg",
+            viewBox="{{ view_box }}",
+{% if dimensions %}
+            width=props.width.to_string(),
+            height=props.height.to_string(),
+{% else %}
+            width=props.size.to_string(),
+            height=props.size.to_string(),
+{% endif %}
+            class=props.class,
+{% if not palette %}
+            fill="currentColor",
+{% endif %}
+{% if decorative %}
+            aria-hidden="true",
+            focusable="false",
+{% endif %}
+{% if meaningful %}
+            role="img",
+            aria-labelledby=title_id,
+{% endif %}
+{% if color %}
+            color=props.color,
+{% endif %}
+{% if stroke_width %}
+            stroke-width=props.stroke_width.to_string(),
+{% endif %}
+{% if sycamore_attributes %}
+            {{ sycamore_attributes | indent(12) }}
+{% endif %}
+        ) {
+{% if meaningful %}
+            title(id=title_id) {
+{% if title_prop %}
+                (props.title)
+{% else %}
+                {{ title | rust }}
+{% endif %}
+            }
+{% if description %}
+            desc {
+                {{ description | rust }}
+            }
+{% endif %}
+{% endif %}
+            {{ sycamore_body | indent(12) }}
+        }
     }
-
-    result
+}"#;
+
+const EGUI_TEMPLATE: &str = r#"{% if color %}
+use egui::{Color32, Image, ImageSource};
+{% else %}
+use egui::{Image, ImageSource};
+{% endif %}
+
+/// SVG source of `{{ icon_id }}`.
+{% if color %}
+pub const {{ component_name | snake | upper }}_SVG: &[u8] = {{ tint_svg | rust_raw }}.as_bytes();
+{% else %}
+pub const {{ component_name | snake | upper }}_SVG: &[u8] = {{ svg | rust_raw }}.as_bytes();
+{% endif %}
+
+/// `{{ icon_id }}` as an [`Image`]. Needs the SVG image loader from
+/// `egui_extras::install_image_loaders`.
+pub fn {{ component_name | snake }}({% if color %}color: Color32{% endif %}) -> Image<'static> {
+    Image::new(ImageSource::Bytes {
+        uri: "bytes://{{ icon_id }}.svg".into(),
+        bytes: {{ component_name | snake | upper }}_SVG.into(),
+    })
+    .fit_to_exact_size(egui::vec2({{ size }}.0, {{ size }}.0))
+{% if color %}
+    .tint(color)
+{% endif %}
+{% if meaningful %}
+    .alt_text({{ title | rust }})
+{% endif %}
 }
+"#;
+
+const ICED_TEMPLATE: &str = "{% if color %}
+use iced::widget::svg::{self, Svg};
+use iced::Color;
+{% else %}
+use iced::widget::svg::{self, Svg};
+{% endif %}
+
+/// SVG source of `{{ icon_id }}`.
+pub const {{ component_name | snake | upper }}_SVG: &[u8] = {{ svg | rust_raw }}.as_bytes();
+
+/// `{{ icon_id }}` as an [`Svg`] widget.
+pub fn {{ component_name | snake }}<'a>({% if color %}color: Color{% endif %}) -> Svg<'a> {
+    Svg::new(svg::Handle::from_memory({{ component_name | snake | upper }}_SVG))
+        .width({{ size }})
+        .height({{ size }})
+{% if color %}
+        .style(move |_theme, _status| svg::Style { color: Some(color) })
+{% endif %}
+}
+";
+
+const SLINT_TEMPLATE: &str = "{% if unsupported %}
+// Not converted: {{ unsupported }}
+
+{% endif %}
+export component {{ component_name }} {
+{% if dimensions %}
+    width: {{ size }}px;
+    height: {{ size }}px;
+{% else %}
+    in property <length> size: {{ size }}px;
+    width: root.size;
+    height: root.size;
+{% endif %}
+{% if current_color %}
+    in property <brush> color: black;
+{% endif %}
+{% if stroke_width %}
+    in property <float> stroke-width: {{ stroke_width }};
+{% endif %}
+{% if decorative %}
+    accessible-role: none;
+{% endif %}
+{% if meaningful %}
+    accessible-role: image;
+{% if title_prop %}
+    in property <string> title: {{ title | slint }};
+    accessible-label: root.title;
+{% else %}
+    accessible-label: {{ title | slint }};
+{% endif %}
+{% if description %}
+    accessible-description: {{ description | slint }};
+{% endif %}
+{% endif %}
+
+    {{ slint_paths | indent(4) }}
+}
+";
+
+const TINY_SKIA_TEMPLATE: &str = "{% if unsupported %}
+// Not converted: {{ unsupported }}
+
+{% endif %}
+{% if size_parameter %}
+use tiny_skia::{Path, PathBuilder, Transform};
+{% else %}
+use tiny_skia::{Path, PathBuilder};
+{% endif %}
+
+{% if size_parameter %}
+/// Paths of `{{ icon_id }}` scaled to `size` units high, in paint order.
+pub fn {{ component_name | snake }}(size: f32) -> Vec<Path> {
+{% else %}
+/// Paths of `{{ icon_id }}` at {{ size }} units high, in paint order.
+pub fn {{ component_name | snake }}() -> Vec<Path> {
+{% endif %}
+    {{ tiny_skia_body | indent(4) }}
+}
+";
+
+const KURBO_TEMPLATE: &str = "{% if unsupported %}
+// Not converted: {{ unsupported }}
+
+{% endif %}
+{% if size_parameter %}
+use kurbo::{Affine, BezPath};
+{% else %}
+use kurbo::BezPath;
+{% endif %}
+
+{% if size_parameter %}
+/// Paths of `{{ icon_id }}` scaled to `size` units high, in paint order.
+pub fn {{ component_name | snake }}(size: f64) -> Vec<BezPath> {
+{% else %}
+/// Paths of `{{ icon_id }}` at {{ size }} units high, in paint order.
+pub fn {{ component_name | snake }}() -> Vec<BezPath> {
+{% endif %}
+    {{ kurbo_body | indent(4) }}
+}
+";
+
+const LYON_TEMPLATE: &str = "{% if unsupported %}
+// Not converted: {{ unsupported }}
+
+{% endif %}
+{% if size_parameter %}
+use lyon::path::math::{point, Transform};
+use lyon::path::Path;
+{% else %}
+use lyon::path::math::point;
+use lyon::path::Path;
+{% endif %}
+
+{% if size_parameter %}
+/// Paths of `{{ icon_id }}` scaled to `size` units high, in paint order.
+pub fn {{ component_name | snake }}(size: f32) -> Vec<Path> {
+{% else %}
+/// Paths of `{{ icon_id }}` at {{ size }} units high, in paint order.
+pub fn {{ component_name | snake }}() -> Vec<Path> {
+{% endif %}
+    {{ lyon_body | indent(4) }}
+}
+";
 
-// =============================================================================
-// Web Framework Generators
-// =============================================================================
-
-fn generate_vue(icon: &ResolvedIcon, _name: &str, typescript: bool) -> String {
-    let script_tag = if typescript {
-        "<script setup lang=\"ts\">"
-    } else {
-        "<script setup>"
-    };
-
-    format!(
-        r#"<template>
+const VUE_TEMPLATE: &str = r#"<template>
   <svg
     xmlns="http://www.w3.org/2000/svg"
-    viewBox="0 0 {w} {h}"
+    viewBox="{{ view_box }}"
+{% if dimensions %}
+    :width="width"
+    :height="height"
+{% else %}
     :width="size"
     :height="size"
+{% endif %}
     :class="className"
+{% if not palette %}
     fill="currentColor"
+{% endif %}
+{% if decorative %}
+    aria-hidden="true"
+    focusable="false"
+{% endif %}
+{% if meaningful %}
+    role="img"
+    :aria-labelledby="titleId"
+{% endif %}
+{% if color %}
+    :color="color"
+{% endif %}
+{% if stroke_width %}
+    :stroke-width="strokeWidth"
+{% endif %}
+{% if attributes %}
+    {{ attributes | indent(4) }}
+{% endif %}
   >
-    {body}
+{% if meaningful %}
+{% if title_prop %}
+    <title :id="titleId" v-text="title"></title>
+{% else %}
+    <title :id="titleId">{{ title | html }}</title>
+{% endif %}
+{% if description %}
+    <desc>{{ description | html }}</desc>
+{% endif %}
+{% endif %}
+    {{ body }}
   </svg>
 </template>
 
-{script_tag}
-defineProps<{{
-  size?: number
-  className?: string
-}}>()
+{% if typescript %}
+<script setup lang="ts">
+{% if meaningful %}
+import { useId } from 'vue'
+
+{% endif %}
+withDefaults(
+  defineProps<{
+{% if dimensions %}
+    width?: number | string
+    height?: number | string
+{% else %}
+    size?: number | string
+{% endif %}
+    className?: string
+{% if color %}
+    color?: string
+{% endif %}
+{% if stroke_width %}
+    strokeWidth?: number | string
+{% endif %}
+{% if title_prop %}
+    title?: string
+{% endif %}
+  }>(),
+  {
+{% if dimensions %}
+    width: {{ size }},
+    height: {{ size }},
+{% else %}
+    size: {{ size }},
+{% endif %}
+{% if color %}
+    color: 'currentColor',
+{% endif %}
+{% if stroke_width %}
+    strokeWidth: {{ stroke_width }},
+{% endif %}
+{% if title_prop %}
+    title: {{ title | json }},
+{% endif %}
+  },
+)
+{% if meaningful %}
+
+const titleId = useId()
+{% endif %}
 </script>
-"#,
-        w = icon.width,
-        h = icon.height,
-        body = icon.body,
-        script_tag = script_tag,
-    )
-}
-
-fn generate_react(icon: &ResolvedIcon, name: &str, typescript: bool) -> String {
-    let props_type = if typescript {
-        ": React.SVGProps<SVGSVGElement>"
-    } else {
-        ""
-    };
-
-    let import = if typescript {
-        "import React from 'react';\n\n"
-    } else {
-        ""
-    };
-
-    // Convert SVG body attributes to JSX (camelCase)
-    let jsx_body = svg_body_to_jsx(&icon.body);
-
-    format!(
-        r#"{import}export function {name}(props{props_type}) {{
+{% else %}
+<script setup>
+{% if meaningful %}
+import { useId } from 'vue'
+
+{% endif %}
+defineProps({
+{% if dimensions %}
+  width: { type: [Number, String], default: {{ size }} },
+  height: { type: [Number, String], default: {{ size }} },
+{% else %}
+  size: { type: [Number, String], default: {{ size }} },
+{% endif %}
+  className: String,
+{% if color %}
+  color: { type: String, default: 'currentColor' },
+{% endif %}
+{% if stroke_width %}
+  strokeWidth: { type: [Number, String], default: {{ stroke_width }} },
+{% endif %}
+{% if title_prop %}
+  title: { type: String, default: {{ title | json }} },
+{% endif %}
+})
+{% if meaningful %}
+
+const titleId = useId()
+{% endif %}
+</script>
+{% endif %}
+"#;
+
+const REACT_TEMPLATE: &str = r#"{% if meaningful %}
+import { useId } from 'react';
+{% if not typescript %}
+
+{% endif %}
+{% endif %}
+{% if typescript %}
+import type { SVGProps } from 'react';
+
+export type {{ component_name }}Props = SVGProps<SVGSVGElement> & {
+{% if dimensions %}
+  width?: number | string;
+  height?: number | string;
+{% else %}
+  size?: number | string;
+{% endif %}
+{% if color %}
+  color?: string;
+{% endif %}
+{% if stroke_width %}
+  strokeWidth?: number | string;
+{% endif %}
+{% if title_prop %}
+  title?: string;
+{% endif %}
+};
+
+{% endif %}
+export function {{ component_name }}({
+{% if dimensions %}
+  width = {{ size }},
+  height = {{ size }},
+{% else %}
+  size = {{ size }},
+{% endif %}
+{% if color %}
+  color = 'currentColor',
+{% endif %}
+{% if stroke_width %}
+  strokeWidth = {{ stroke_width }},
+{% endif %}
+{% if title_prop %}
+  title = {{ title | json }},
+{% endif %}
+  ...props
+}{% if typescript %}: {{ component_name }}Props{% endif %}) {
+{% if meaningful %}
+  const titleId = useId();
+{% endif %}
   return (
     <svg
       xmlns="http://www.w3.org/2000/svg"
-      viewBox="0 0 {w} {h}"
-      width={{24}}
-      height={{24}}
+      viewBox="{{ view_box }}"
+{% if dimensions %}
+      width={width}
+      height={height}
+{% else %}
+      width={size}
+      height={size}
+{% endif %}
+{% if not palette %}
       fill="currentColor"
-      {{...props}}
+{% endif %}
+{% if decorative %}
+      aria-hidden="true"
+      focusable="false"
+{% endif %}
+{% if meaningful %}
+      role="img"
+      aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+      color={color}
+{% endif %}
+{% if stroke_width %}
+      strokeWidth={strokeWidth}
+{% endif %}
+{% if jsx_attributes %}
+      {{ jsx_attributes | indent(6) }}
+{% endif %}
+      {...props}
     >
-      {jsx_body}
+{% if meaningful %}
+      <title id={titleId}>{% if title_prop %}{title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+      <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+      {{ jsx_body }}
     </svg>
   );
-}}
-
-export default {name};
-"#,
-        import = import,
-        name = name,
-        props_type = props_type,
-        w = icon.width,
-        h = icon.height,
-        jsx_body = jsx_body,
-    )
 }
 
-fn generate_svelte(icon: &ResolvedIcon) -> String {
-    format!(
-        r#"<script>
-  export let size = 24;
+export default {{ component_name }};
+"#;
+
+const SVELTE_TEMPLATE: &str = r#"{% if meaningful %}
+<script context="module"{% if typescript %} lang="ts"{% endif %}>
+  let titleCount = 0;
+</script>
+
+{% endif %}
+<script{% if typescript %} lang="ts"{% endif %}>
+{% if dimensions %}
+  export let width{% if typescript %}: number | string{% endif %} = {{ size }};
+  export let height{% if typescript %}: number | string{% endif %} = {{ size }};
+{% else %}
+  export let size{% if typescript %}: number | string{% endif %} = {{ size }};
+{% endif %}
   export let className = "";
+{% if color %}
+  export let color = "currentColor";
+{% endif %}
+{% if stroke_width %}
+  export let strokeWidth{% if typescript %}: number | string{% endif %} = {{ stroke_width }};
+{% endif %}
+{% if title_prop %}
+  export let title = {{ title | json }};
+{% endif %}
+{% if meaningful %}
+
+  const titleId = `{{ title_id }}-${titleCount++}`;
+{% endif %}
 </script>
 
 <svg
   xmlns="http://www.w3.org/2000/svg"
-  viewBox="0 0 {w} {h}"
-  width={{size}}
-  height={{size}}
-  class={{className}}
+  viewBox="{{ view_box }}"
+{% if dimensions %}
+  {width}
+  {height}
+{% else %}
+  width={size}
+  height={size}
+{% endif %}
+  class={className}
+{% if not palette %}
   fill="currentColor"
-  {{...$$restProps}}
+{% endif %}
+{% if decorative %}
+  aria-hidden="true"
+  focusable="false"
+{% endif %}
+{% if meaningful %}
+  role="img"
+  aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+  {color}
+{% endif %}
+{% if stroke_width %}
+  stroke-width={strokeWidth}
+{% endif %}
+{% if attributes %}
+  {{ attributes | indent(2) }}
+{% endif %}
+  {...$$restProps}
 >
-  {body}
+{% if meaningful %}
+  <title id={titleId}>{% if title_prop %}{title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+  <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+  {{ body }}
 </svg>
-"#,
-        w = icon.width,
-        h = icon.height,
-        body = icon.body,
-    )
-}
-
-fn generate_qwik(icon: &ResolvedIcon, name: &str) -> String {
-    let jsx_body = svg_body_to_jsx(&icon.body);
-
-    format!(
-        r#"import type {{ QwikIntrinsicElements }} from '@builder.io/qwik';
-
-export function {name}(props: QwikIntrinsicElements['svg']) {{
+"#;
+
+const QWIK_TEMPLATE: &str = r#"{% if meaningful %}
+import { useId } from '@builder.io/qwik';
+{% if not typescript %}
+
+{% endif %}
+{% endif %}
+{% if typescript %}
+import type { QwikIntrinsicElements } from '@builder.io/qwik';
+
+export type {{ component_name }}Props = QwikIntrinsicElements['svg'] & {
+{% if dimensions %}
+  width?: number | string;
+  height?: number | string;
+{% else %}
+  size?: number | string;
+{% endif %}
+{% if color %}
+  color?: string;
+{% endif %}
+{% if stroke_width %}
+  strokeWidth?: number | string;
+{% endif %}
+{% if title_prop %}
+  title?: string;
+{% endif %}
+};
+
+{% endif %}
+export function {{ component_name }}({
+{% if dimensions %}
+  width = {{ size }},
+  height = {{ size }},
+{% else %}
+  size = {{ size }},
+{% endif %}
+{% if color %}
+  color = 'currentColor',
+{% endif %}
+{% if stroke_width %}
+  strokeWidth = {{ stroke_width }},
+{% endif %}
+{% if title_prop %}
+  title = {{ title | json }},
+{% endif %}
+  ...props
+}{% if typescript %}: {{ component_name }}Props{% endif %}) {
+{% if meaningful %}
+  const titleId = useId();
+{% endif %}
   return (
     <svg
       xmlns="http://www.w3.org/2000/svg"
-      viewBox="0 0 {w} {h}"
-      width={{24}}
-      height={{24}}
+      viewBox="{{ view_box }}"
+{% if dimensions %}
+      width={width}
+      height={height}
+{% else %}
+      width={size}
+      height={size}
+{% endif %}
+{% if not palette %}
       fill="currentColor"
-      {{...props}}
+{% endif %}
+{% if decorative %}
+      aria-hidden="true"
+      focusable="false"
+{% endif %}
+{% if meaningful %}
+      role="img"
+      aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+      color={color}
+{% endif %}
+{% if stroke_width %}
+      stroke-width={strokeWidth}
+{% endif %}
+{% if jsx_attributes %}
+      {{ jsx_attributes | indent(6) }}
+{% endif %}
+      {...props}
     >
-      {jsx_body}
+{% if meaningful %}
+      <title id={titleId}>{% if title_prop %}{title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+      <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+      {{ jsx_body }}
     </svg>
   );
-}}
-
-export default {name};
-"#,
-        name = name,
-        w = icon.width,
-        h = icon.height,
-        jsx_body = jsx_body,
-    )
 }
 
-fn generate_solid(icon: &ResolvedIcon, name: &str) -> String {
-    let jsx_body = svg_body_to_jsx(&icon.body);
-
-    format!(
-        r#"import type {{ JSX }} from 'solid-js';
-
-export function {name}(props: JSX.IntrinsicElements['svg']) {{
+export default {{ component_name }};
+"#;
+
+const SOLID_TEMPLATE: &str = r#"import { {% if meaningful %}createUniqueId, {% endif %}mergeProps, splitProps } from 'solid-js';
+{% if typescript %}
+import type { JSX } from 'solid-js';
+
+export type {{ component_name }}Props = JSX.IntrinsicElements['svg'] & {
+{% if dimensions %}
+  width?: number | string;
+  height?: number | string;
+{% else %}
+  size?: number | string;
+{% endif %}
+{% if color %}
+  color?: string;
+{% endif %}
+{% if stroke_width %}
+  strokeWidth?: number | string;
+{% endif %}
+{% if title_prop %}
+  title?: string;
+{% endif %}
+};
+{% endif %}
+
+export function {{ component_name }}(props{% if typescript %}: {{ component_name }}Props{% endif %}) {
+  const merged = mergeProps(
+    {
+{% if dimensions %}
+      width: {{ size }},
+      height: {{ size }},
+{% else %}
+      size: {{ size }},
+{% endif %}
+{% if color %}
+      color: 'currentColor',
+{% endif %}
+{% if stroke_width %}
+      strokeWidth: {{ stroke_width }},
+{% endif %}
+{% if title_prop %}
+      title: {{ title | json }},
+{% endif %}
+    },
+    props,
+  );
+  const [local, others] = splitProps(merged, [
+{% if dimensions %}
+    'width',
+    'height',
+{% else %}
+    'size',
+{% endif %}
+{% if color %}
+    'color',
+{% endif %}
+{% if stroke_width %}
+    'strokeWidth',
+{% endif %}
+{% if title_prop %}
+    'title',
+{% endif %}
+  ]);
+{% if meaningful %}
+  const titleId = createUniqueId();
+{% endif %}
   return (
     <svg
       xmlns="http://www.w3.org/2000/svg"
-      viewBox="0 0 {w} {h}"
-      width={{24}}
-      height={{24}}
+      viewBox="{{ view_box }}"
+{% if dimensions %}
+      width={local.width}
+      height={local.height}
+{% else %}
+      width={local.size}
+      height={local.size}
+{% endif %}
+{% if not palette %}
       fill="currentColor"
-      {{...props}}
+{% endif %}
+{% if decorative %}
+      aria-hidden="true"
+      focusable="false"
+{% endif %}
+{% if meaningful %}
+      role="img"
+      aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+      color={local.color}
+{% endif %}
+{% if stroke_width %}
+      stroke-width={local.strokeWidth}
+{% endif %}
+{% if jsx_attributes %}
+      {{ jsx_attributes | indent(6) }}
+{% endif %}
+      {...others}
     >
-      {jsx_body}
+{% if meaningful %}
+      <title id={titleId}>{% if title_prop %}{local.title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+      <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+      {{ jsx_body }}
     </svg>
   );
-}}
-
-export default {name};
-"#,
-        name = name,
-        w = icon.width,
-        h = icon.height,
-        jsx_body = jsx_body,
-    )
 }
 
-fn generate_astro(icon: &ResolvedIcon) -> String {
-    format!(
-        r#"---
-const props = Astro.props;
+export default {{ component_name }};
+"#;
+
+const ASTRO_TEMPLATE: &str = r#"---
+{% if typescript %}
+import type { HTMLAttributes } from 'astro/types';
+
+type Props = HTMLAttributes<'svg'> & {
+{% if dimensions %}
+  width?: number | string;
+  height?: number | string;
+{% else %}
+  size?: number | string;
+{% endif %}
+{% if color %}
+  color?: string;
+{% endif %}
+{% if stroke_width %}
+  strokeWidth?: number | string;
+{% endif %}
+{% if title_prop %}
+  title?: string;
+{% endif %}
+};
+
+{% endif %}
+const {
+{% if dimensions %}
+  width = {{ size }},
+  height = {{ size }},
+{% else %}
+  size = {{ size }},
+{% endif %}
+{% if color %}
+  color = 'currentColor',
+{% endif %}
+{% if stroke_width %}
+  strokeWidth = {{ stroke_width }},
+{% endif %}
+{% if title_prop %}
+  title = {{ title | json }},
+{% endif %}
+  ...props
+} = Astro.props;
+{% if meaningful %}
+const titleId = `{{ title_id }}-${Math.random().toString(36).slice(2)}`;
+{% endif %}
 ---
 
 <svg
   xmlns="http://www.w3.org/2000/svg"
-  viewBox="0 0 {w} {h}"
-  width="24"
-  height="24"
+  viewBox="{{ view_box }}"
+{% if dimensions %}
+  width={width}
+  height={height}
+{% else %}
+  width={size}
+  height={size}
+{% endif %}
+{% if not palette %}
   fill="currentColor"
-  {{...props}}
+{% endif %}
+{% if decorative %}
+  aria-hidden="true"
+  focusable="false"
+{% endif %}
+{% if meaningful %}
+  role="img"
+  aria-labelledby={titleId}
+{% endif %}
+{% if color %}
+  color={color}
+{% endif %}
+{% if stroke_width %}
+  stroke-width={strokeWidth}
+{% endif %}
+{% if attributes %}
+  {{ attributes | indent(2) }}
+{% endif %}
+  {...props}
 >
-  {body}
+{% if meaningful %}
+  <title id={titleId}>{% if title_prop %}{title}{% else %}{ {{ title | json }} }{% endif %}</title>
+{% if description %}
+  <desc>{ {{ description | json }} }</desc>
+{% endif %}
+{% endif %}
+  {{ body }}
 </svg>
-"#,
-        w = icon.width,
-        h = icon.height,
-        body = icon.body,
-    )
-}
+"#;
 
-/// Convert SVG body attributes to JSX camelCase.
-fn svg_body_to_jsx(body: &str) -> String {
-    let mut result = body.to_string();
-
-    let replacements = [
-        ("fill-rule", "fillRule"),
-        ("clip-rule", "clipRule"),
-        ("stroke-width", "strokeWidth"),
-        ("stroke-linecap", "strokeLinecap"),
-        ("stroke-linejoin", "strokeLinejoin"),
-        ("stroke-miterlimit", "strokeMiterlimit"),
-        ("fill-opacity", "fillOpacity"),
-        ("stroke-opacity", "strokeOpacity"),
-        ("clip-path", "clipPath"),
-        ("xlink:href", "xlinkHref"),
-    ];
+const URL_TEMPLATE: &str = "{{ url }}";
 
-    for (from, to) in replacements {
-        result = result.replace(from, to);
-    }
+const DATA_URL_TEMPLATE: &str = "{{ data_url }}";
 
-    result
-}
+const BASE64_TEMPLATE: &str = "{{ base64 }}";
+
+const CSS_BACKGROUND_TEMPLATE: &str =
+    "background: {{ css_url }} no-repeat center center / contain;";
 
 // =============================================================================
 // Data URL Generators
@@ -599,27 +2319,10 @@ fn base64_encode(input: &[u8]) -> String {
     output
 }
 
-/// Encode SVG to data URL (chooses shorter of base64 or URL-encoded).
+/// Encode SVG as a compact data URL, safe to use in HTML attributes.
 #[must_use]
 pub fn svg_to_data_url(svg: &str) -> String {
-    let base64 = format!("data:image/svg+xml;base64,{}", svg_to_base64(svg));
-    let url_encoded = format!("data:image/svg+xml,{}", encode_svg_for_css(svg));
-
-    if base64.len() < url_encoded.len() {
-        base64
-    } else {
-        url_encoded
-    }
-}
-
-/// Encode SVG for use in CSS `url()`.
-fn encode_svg_for_css(svg: &str) -> String {
-    svg.replace('#', "%23")
-        .replace('<', "%3C")
-        .replace('>', "%3E")
-        .replace('"', "'")
-        .replace('\n', " ")
-        .replace('\r', "")
+    data_uri::encode(svg, DataUriContext::HtmlAttribute)
 }
 
 #[cfg(test)]
@@ -635,6 +2338,7 @@ mod tests {
                 .into(),
             width: 24,
             height: 24,
+            ..Default::default()
         }
     }
 
@@ -680,18 +2384,593 @@ mod tests {
         assert!(code.contains("rsx!"));
     }
 
+    #[test]
+    fn dioxus_snippets_nest_rsx_elements() {
+        let icon = ResolvedIcon {
+            body: r#"<g fill-rule="evenodd"><path d="M0 0h24v24H0z"/></g>"#.into(),
+            ..test_icon()
+        };
+        let code = generate(&icon, SnippetType::Dioxus);
+        assert!(code.contains(
+            "            g {\n                fill_rule: \"evenodd\",\n                path { d: \"M0 0h24v24H0z\" }\n            }\n        }"
+        ), "{code}");
+        assert!(code.contains("            width: size,\n"));
+        let legacy = generate(&icon, SnippetType::Dioxus05);
+        assert!(legacy.contains("            width: \"{size}\",\n"));
+        assert!(legacy.contains("                fill_rule: \"evenodd\",\n"));
+    }
+
+    #[test]
+    fn sycamore_and_leptos_06_snippets() {
+        let icon = ResolvedIcon {
+            body: r#"<g fill-rule="evenodd"><path d="M0 0h24v24H0z"/></g>"#.into(),
+            ..test_icon()
+        };
+        let code = generate(&icon, SnippetType::Sycamore);
+        assert!(
+            code.contains(
+                "pub fn MdiArrowLeft<G: Html>(cx: Scope, props: MdiArrowLeftProps) -> View<G> {"
+            ),
+            "{code}"
+        );
+        assert!(code.contains(
+            "            g(fill-rule=\"evenodd\") {\n                path(d=\"M0 0h24v24H0z\")\n            }\n        }"
+        ), "{code}");
+        let legacy = generate(&icon, SnippetType::Leptos06);
+        assert!(
+            legacy.starts_with("use leptos::*;\n\n#[component]"),
+            "{legacy}"
+        );
+        let code = generate(&icon, SnippetType::Leptos);
+        assert!(
+            code.starts_with("use leptos::prelude::*;\n\n#[component]"),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn attributes_replace_root_attributes() {
+        let options = SnippetOptions {
+            attributes: vec![
+                ("width".into(), "1em".into()),
+                ("class".into(), "icon".into()),
+            ],
+            ..SnippetOptions::default()
+        };
+        for snippet_type in [SnippetType::Svg, SnippetType::Egui, SnippetType::Iced] {
+            let code = generate_with(&test_icon(), snippet_type, &options);
+            assert_eq!(code.matches("width=").count(), 1, "{code}");
+            assert!(
+                code.contains(r#" width="1em" height="24" viewBox="0 0 24 24" class="icon">"#),
+                "{code}"
+            );
+        }
+    }
+
+    fn all_options() -> SnippetOptions {
+        SnippetOptions {
+            size: 32,
+            separate_dimensions: true,
+            color_prop: true,
+            stroke_width_prop: true,
+            attributes: vec![("aria-label".into(), r#"Say "hi""#.into())],
+            component_name: Some("HomeIcon".into()),
+            typescript: Some(true),
+            accessibility: Accessibility::Meaningful,
+            title: Some(r#"Go {home} "now""#.into()),
+            description: Some("Back to <start>".into()),
+            title_prop: true,
+            size_parameter: true,
+        }
+    }
+
+    #[test]
+    fn snippet_options_apply_to_every_type() {
+        let icon = ResolvedIcon {
+            body: r##"<path fill="none" stroke="#000" stroke-width="1.5" d="M0 0h24"/>"##.into(),
+            ..test_icon()
+        };
+        let options = all_options();
+        for &snippet_type in SnippetType::all() {
+            let code = generate_with(&icon, snippet_type, &options);
+            let component = snippet_type.category() == SnippetCategory::Rust
+                || snippet_type.category() == SnippetCategory::Components;
+            if component {
+                // Single-file components are named after their file
+                let named = snippet_type.template().contains("component_name");
+                assert_eq!(code.contains("HomeIcon"), named, "{snippet_type:?}");
+                for expected in ["32", "color", "aria"] {
+                    assert!(
+                        code.contains(expected),
+                        "{snippet_type:?} lacks {expected}: {code}"
+                    );
+                }
+                assert!(code.contains("1.5"), "{snippet_type:?}: {code}");
+                // The stroke width moves from the path to the root prop
+                assert!(!code.contains(r#"stroke-width="1.5""#), "{code}");
+                assert!(
+                    !code
+                        .split(|c: char| !c.is_ascii_alphanumeric())
+                        .any(|word| word == "size"),
+                    "{snippet_type:?}: {code}"
+                );
+            }
+        }
+        let svg = generate_with(&icon, SnippetType::Svg, &options);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="32" height="32" viewBox="0 0 24 24" aria-label="Say &quot;hi&quot;" role="img" aria-labelledby="mdi-arrow-left-title">"#), "{svg}");
+        assert!(svg.contains(r#"stroke-width="1.5""#));
+
+        let react = generate_with(&icon, SnippetType::React, &options);
+        assert!(react.contains("export type HomeIconProps = SVGProps<SVGSVGElement>"));
+        assert!(react.contains("  strokeWidth = 1.5,\n"));
+        let js = SnippetOptions {
+            typescript: Some(false),
+            ..options
+        };
+        assert!(!generate_with(&icon, SnippetType::ReactTs, &js).contains("Props"));
+        let template = SnippetTemplate::from(SnippetType::ReactTs);
+        assert_eq!(template.file_extension(&js), "jsx");
+        assert_eq!(template.file_extension(&SnippetOptions::default()), "tsx");
+    }
+
+    #[test]
+    fn stroke_width_prop_needs_a_uniform_width() {
+        let options = SnippetOptions {
+            stroke_width_prop: true,
+            ..Default::default()
+        };
+        let mixed = ResolvedIcon {
+            body: r##"<path stroke="#000" stroke-width="1" d="M0 0h24"/><path stroke="#000" stroke-width="2" d="M0 0v24"/>"##.into(),
+            ..test_icon()
+        };
+        let code = generate_with(&mixed, SnippetType::React, &options);
+        assert!(!code.contains("strokeWidth ="), "{code}");
+        assert!(code.contains(r#"strokeWidth="2""#));
+        // Filled icons have no stroke to configure
+        assert!(!generate_with(&test_icon(), SnippetType::React, &options).contains("strokeWidth"));
+    }
+
+    /// The tokens of the view macro returned by the snippet's component.
+    fn view_macro_tokens(code: &str) -> proc_macro2::TokenStream {
+        let file = syn::parse_file(code).unwrap_or_else(|e| panic!("{e}:\n{code}"));
+        let component = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(f) => Some(f),
+                _ => None,
+            })
+            .expect("component function");
+        match component.block.stmts.last() {
+            Some(syn::Stmt::Expr(syn::Expr::Macro(m), None)) => m.mac.tokens.clone(),
+            Some(syn::Stmt::Macro(m)) => m.mac.tokens.clone(),
+            other => panic!("expected a view macro, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn native_snippets() {
+        let icon = ResolvedIcon {
+            body: r##"<g fill="none" stroke="currentColor" stroke-width="2"><path d="M3 9l9-7"/><circle cx="12" cy="12" r="3" fill="#f00"/></g><text>x</text>"##.into(),
+            ..test_icon()
+        };
+        for options in [SnippetOptions::default(), all_options()] {
+            for snippet_type in [SnippetType::Egui, SnippetType::Iced] {
+                let code = generate_with(&icon, snippet_type, &options);
+                syn::parse_file(&code).unwrap_or_else(|e| panic!("{e}:\n{code}"));
+            }
+        }
+
+        let options = SnippetOptions {
+            color_prop: true,
+            ..SnippetOptions::default()
+        };
+        let egui = generate_with(&test_icon(), SnippetType::Egui, &options);
+        assert!(
+            egui.contains(r##"viewBox="0 0 24 24" color="#fff"><path"##),
+            "{egui}"
+        );
+        // A passed `color` is replaced rather than repeated
+        let options = SnippetOptions {
+            attributes: vec![("color".into(), "red".into())],
+            ..options
+        };
+        let egui = generate_with(&test_icon(), SnippetType::Egui, &options);
+        assert_eq!(egui.matches("color=").count(), 1, "{egui}");
+        assert!(egui.contains(r##"color="#fff""##), "{egui}");
+        assert!(egui.contains("pub fn mdi_arrow_left(color: Color32) -> Image<'static> {"));
+        let iced = generate_with(&test_icon(), SnippetType::Iced, &options);
+        assert!(
+            iced.contains("svg::Handle::from_memory(MDI_ARROW_LEFT_SVG)"),
+            "{iced}"
+        );
+
+        let slint = generate_with(&icon, SnippetType::Slint, &all_options());
+        assert!(slint.starts_with("// Not converted: text\n"), "{slint}");
+        assert!(
+            slint.contains("    in property <brush> color: black;\n"),
+            "{slint}"
+        );
+        assert!(
+            slint.contains("    in property <float> stroke-width: 2;\n"),
+            "{slint}"
+        );
+        assert!(
+            slint.contains("    in property <string> title: \"Go {home} \\\"now\\\"\";\n"),
+            "{slint}"
+        );
+        assert_eq!(slint.matches("    Path {\n").count(), 2, "{slint}");
+        assert!(
+            slint.contains(r#"        commands: "M3 9 12 2";"#),
+            "{slint}"
+        );
+    }
+
+    #[test]
+    fn unfilled_icons_follow_text_colour() {
+        let slint = generate(&test_icon(), SnippetType::Slint);
+        assert!(
+            slint.contains("    in property <brush> color: black;\n"),
+            "{slint}"
+        );
+        assert!(slint.contains("        fill: root.color;\n"), "{slint}");
+        let tiny_skia = generate(&test_icon(), SnippetType::TinySkia);
+        assert!(
+            tiny_skia.contains("    // fill currentColor\n"),
+            "{tiny_skia}"
+        );
+
+        let palette = ResolvedIcon {
+            palette: true,
+            ..test_icon()
+        };
+        let slint = generate(&palette, SnippetType::Slint);
+        assert!(!slint.contains("root.color"), "{slint}");
+        assert!(slint.contains("        fill: #000;\n"), "{slint}");
+    }
+
+    #[test]
+    fn path_builder_snippets() {
+        let icon = ResolvedIcon {
+            body: r#"<g fill="none" stroke="currentColor" stroke-width="2"><path d="M3 9l9-7"/><circle cx="12" cy="12" r="3"/></g><text>x</text>"#.into(),
+            ..test_icon()
+        };
+        let types = [SnippetType::TinySkia, SnippetType::Kurbo, SnippetType::Lyon];
+        for options in [SnippetOptions::default(), all_options()] {
+            for snippet_type in types {
+                let code = generate_with(&icon, snippet_type, &options);
+                syn::parse_file(&code).unwrap_or_else(|e| panic!("{e}:\n{code}"));
+                assert!(code.starts_with("// Not converted: text\n"), "{code}");
+                assert!(
+                    code.contains("// stroke currentColor (width 2)\n"),
+                    "{code}"
+                );
+            }
+        }
+
+        let options = SnippetOptions {
+            size: 48,
+            ..SnippetOptions::default()
+        };
+        let tiny_skia = generate_with(&icon, SnippetType::TinySkia, &options);
+        assert!(tiny_skia.contains("pub fn mdi_arrow_left() -> Vec<Path> {"));
+        assert!(tiny_skia.contains("    pb.move_to(6.0, 18.0);\n    pb.line_to(24.0, 4.0);\n"));
+        let kurbo = generate_with(&icon, SnippetType::Kurbo, &all_options());
+        assert!(kurbo.contains("pub fn home_icon(size: f64) -> Vec<BezPath> {"));
+        assert!(kurbo.contains("    path.move_to((3.0, 9.0));\n"), "{kurbo}");
+        assert!(kurbo.contains("    let affine = Affine::scale(size / 24.0);\n"));
+    }
+
+    #[test]
+    fn vector_drawable_snippet() {
+        let icon = ResolvedIcon {
+            body: r#"<g fill="none" stroke="currentColor" stroke-width="2"><path d="M3 9l9-7"/></g><text>x</text>"#.into(),
+            ..test_icon()
+        };
+        let xml = generate_with(&icon, SnippetType::VectorDrawable, &all_options());
+        assert!(
+            xml.starts_with("<!-- Not converted: text -->\n<vector "),
+            "{xml}"
+        );
+        assert!(xml.contains("    android:height=\"32dp\"\n"), "{xml}");
+        assert!(xml.contains("android:tint=\"?attr/colorControlNormal\""));
+        assert!(xml.contains("android:strokeWidth=\"2\""), "{xml}");
+        assert!(xml.ends_with("</vector>\n"), "{xml}");
+
+        // Material-style bodies without a fill are tinted too
+        let xml = generate(&test_icon(), SnippetType::VectorDrawable);
+        assert!(
+            xml.contains("    android:tint=\"?attr/colorControlNormal\">\n"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("android:fillColor=\"@android:color/white\""),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn rust_snippets_parse() {
+        let bodies = [
+            r#"<path d="M0 0h24v24H0z"/>"#,
+            r##"<g fill-rule="evenodd" style="fill:red;stroke:{x}"><use xlink:href="#a" xml:space="preserve"/><text font-family="&quot;A&quot;">a {b} "c" \ d</text><!-- note --></g>"##,
+            r##"<defs><linearGradient id="g"><stop offset="0" stop-color="#fff"/></linearGradient><filter id="f"><feOffset in="SourceGraphic" type="x"/></filter></defs><path fill="url(#g)" d=""/>"##,
+            "<style>.a{fill:red}</style><sodipodi:namedview/>",
+            r##"<path fill="none" stroke="#000" stroke-width="1.5" d="M0 0h24"/>"##,
+        ];
+        let options = [
+            SnippetOptions::default(),
+            all_options(),
+            SnippetOptions {
+                title_prop: false,
+                ..all_options()
+            },
+            SnippetOptions {
+                accessibility: Accessibility::Decorative,
+                ..SnippetOptions::default()
+            },
+        ];
+        for (body, options) in bodies
+            .into_iter()
+            .flat_map(|b| options.iter().map(move |o| (b, o)))
+        {
+            let icon = ResolvedIcon {
+                body: body.into(),
+                ..test_icon()
+            };
+            for snippet_type in SnippetType::by_category(SnippetCategory::Rust) {
+                let code = generate_with(&icon, snippet_type, options);
+                let tokens = view_macro_tokens(&code);
+                if matches!(
+                    snippet_type,
+                    SnippetType::Leptos | SnippetType::Leptos06 | SnippetType::Yew
+                ) {
+                    let nodes = rstml::parse2(tokens)
+                        .unwrap_or_else(|e| panic!("{snippet_type:?}: {e}:\n{code}"));
+                    assert_eq!(nodes.len(), 1, "{code}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn accessibility_modes() {
+        let icon = test_icon();
+        let decorative = SnippetOptions {
+            accessibility: Accessibility::Decorative,
+            ..SnippetOptions::default()
+        };
+        let svg = generate_with(&icon, SnippetType::Svg, &decorative);
+        assert!(
+            svg.contains(r#" aria-hidden="true" focusable="false""#),
+            "{svg}"
+        );
+        assert!(!svg.contains("<title"), "{svg}");
+
+        let meaningful = SnippetOptions {
+            accessibility: Accessibility::Meaningful,
+            description: Some("A & B".into()),
+            ..SnippetOptions::default()
+        };
+        let svg = generate_with(&icon, SnippetType::Svg, &meaningful);
+        assert!(
+            svg.contains(r#" role="img" aria-labelledby="mdi-arrow-left-title">"#),
+            "{svg}"
+        );
+        assert!(
+            svg.contains(
+                r#"<title id="mdi-arrow-left-title">Arrow left</title><desc>A &amp; B</desc>"#
+            ),
+            "{svg}"
+        );
+
+        let options = all_options();
+        for snippet_type in [
+            SnippetType::React,
+            SnippetType::Vue,
+            SnippetType::Svelte,
+            SnippetType::Leptos,
+            SnippetType::Yew,
+            SnippetType::Dioxus,
+        ] {
+            let code = generate_with(&icon, snippet_type, &options);
+            assert!(
+                code.contains("<title") || code.contains("title {"),
+                "{code}"
+            );
+            assert!(code.contains("\"img\""), "{code}");
+            // JSON and Rust string literals agree here
+            assert!(
+                code.contains(r#""Go {home} \"now\"""#),
+                "{snippet_type:?}:\n{code}"
+            );
+        }
+        let react = generate_with(&icon, SnippetType::React, &options);
+        assert!(react.contains("const titleId = useId();"), "{react}");
+        assert!(
+            react.contains("<title id={titleId}>{title}</title>"),
+            "{react}"
+        );
+    }
+
     #[test]
     fn generate_data_url() {
         let icon = test_icon();
         let url = generate(&icon, SnippetType::DataUrl);
-        assert!(url.starts_with("data:image/svg+xml"));
+        assert!(url.starts_with("data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg'"));
+        let css = generate(&icon, SnippetType::CssBackground);
+        assert!(css.starts_with(r#"background: url("data:image/svg+xml,%3Csvg "#));
+    }
+
+    #[test]
+    fn jsx_snippets_follow_framework_dialects() {
+        let icon = ResolvedIcon {
+            body: r#"<path class="a" stroke-dasharray="2" clip-path="url(#clip-path)" style="stroke-linecap:round"/>"#.into(),
+            ..test_icon()
+        };
+        for snippet_type in [SnippetType::Jsx, SnippetType::React, SnippetType::ReactTs] {
+            let code = generate(&icon, snippet_type);
+            assert!(
+                code.contains(r#"<path className="a" strokeDasharray="2" clipPath="url(#clip-path)" style={{ strokeLinecap: "round" }}/>"#),
+                "{snippet_type:?}: {code}"
+            );
+        }
+        let solid = generate(&icon, SnippetType::Solid);
+        assert!(solid.contains(r#"<path class="a" stroke-dasharray="2" clip-path="url(#clip-path)" style={{ "stroke-linecap": "round" }}/>"#));
+        let qwik = generate(&icon, SnippetType::Qwik);
+        assert!(qwik.contains(r#"<path class="a" stroke-dasharray="2" clip-path="url(#clip-path)" style={{ strokeLinecap: "round" }}/>"#));
     }
 
     #[test]
+    #[allow(clippy::assert_is_empty)]
     fn generate_base64() {
         let icon = test_icon();
         let b64 = generate(&icon, SnippetType::Base64);
         assert!(!b64.is_empty());
         assert!(!b64.contains("data:"));
     }
+
+    #[test]
+    fn generators_apply_icon_transformations() {
+        let mut icon = test_icon();
+        icon.h_flip = true;
+        let code = generate(&icon, SnippetType::Leptos);
+        assert!(code.contains(r#"<g transform="translate(24 0) scale(-1 1)">"#));
+        let symbol = generate(&icon, SnippetType::SvgSymbol);
+        assert!(symbol.starts_with(r#"<symbol id="mdi-arrow-left" viewBox="0 0 24 24">"#));
+    }
+
+    #[test]
+    fn generators_follow_icon_colours() {
+        let mut icon = test_icon();
+        icon.body = r##"<path fill="#000" d="M0 0h24"/>"##.into();
+        let code = generate(&icon, SnippetType::React);
+        assert!(code.contains(r#"fill="currentColor""#));
+        assert!(!code.contains("#000"));
+
+        icon.body = r##"<path fill="#f00" d="M0 0h24"/><path fill="#00f" d="M0 0v24"/>"##.into();
+        for snippet_type in [SnippetType::Leptos, SnippetType::Dioxus, SnippetType::Vue] {
+            let code = generate(&icon, snippet_type);
+            assert!(!code.contains("currentColor"), "{snippet_type:?}");
+            assert!(code.contains("#f00"));
+        }
+
+        icon.body = r#"<path d="M0 0h24"/>"#.into();
+        icon.palette = true;
+        assert!(!generate(&icon, SnippetType::Svelte).contains("currentColor"));
+    }
+
+    #[test]
+    fn clean_svg_optimizes() {
+        let svg = r#"<svg viewBox="0 0 24 24"><!-- c --><path d="M 1 1 L 2 2"/></svg>"#;
+        assert_eq!(
+            clean_svg(svg),
+            r#"<svg viewBox="0 0 24 24"><path d="M1 1 2 2"/></svg>"#
+        );
+        assert_eq!(clean_svg("<svg>"), "<svg>");
+    }
+
+    #[test]
+    fn generate_optimized_works_for_every_type() {
+        let mut icon = test_icon();
+        icon.body = r#"<g><path d="M 0.00001 0 L 10 10"/></g>"#.into();
+        let options = OptimizeOptions::default();
+        for &snippet_type in SnippetType::all() {
+            let plain = generate(&icon, snippet_type);
+            let optimized = generate_optimized(&icon, snippet_type, &options);
+            assert!(optimized.len() <= plain.len(), "{}", snippet_type.name());
+        }
+        let svg = generate_optimized(&icon, SnippetType::Svg, &options);
+        assert!(svg.contains(r#"<path d="M0 0 10 10"/>"#));
+    }
+
+    #[test]
+    fn builtin_templates_render() {
+        let icon = test_icon();
+        let variables = template_variables(&icon, None, &SnippetOptions::default());
+        for &snippet_type in SnippetType::all() {
+            let template = Template::parse(snippet_type.template()).unwrap();
+            assert_ne!(template.render(&variables).unwrap(), "", "{snippet_type:?}");
+        }
+    }
+
+    #[test]
+    fn computes_only_wanted_variables() {
+        let options = SnippetOptions::default();
+        let variables = variables(&test_icon(), None, &options, JsxFlavor::Solid, |name| {
+            ["name", "jsx_body"].contains(&name)
+        });
+        assert_eq!(
+            variables.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["jsx_body", "name"]
+        );
+    }
+
+    #[test]
+    fn loads_user_templates() {
+        let source = "---\nname: House React\ncategory: components\ntag: TS\n---\n// {{ license | default(\"unlicensed\") }}\nexport const {{ name | pascal }}Icon = () => <svg viewBox=\"{{ view_box }}\">{{ jsx_body }}</svg>;\n";
+        let template = SnippetTemplate::from_file("templates/house-react.tsx.tpl", source).unwrap();
+        assert_eq!(template.id, "house-react");
+        assert_eq!(template.name, "House React");
+        assert_eq!(template.tag.as_deref(), Some("TS"));
+        assert_eq!(template.category, SnippetCategory::Components);
+        assert_eq!(template.extension, "tsx");
+
+        let info = CollectionInfoRaw {
+            name: "Material Design Icons".into(),
+            license: Some(crate::types::License {
+                title: "Apache 2.0".into(),
+                url: None,
+                spdx: Some("Apache-2.0".into()),
+            }),
+            ..Default::default()
+        };
+        let icon = test_icon();
+        assert!(render(&template, &icon, None, &SnippetOptions::default())
+            .unwrap()
+            .starts_with(
+                "// unlicensed\nexport const ArrowLeftIcon = () => <svg viewBox=\"0 0 24 24\">"
+            ));
+        assert!(
+            render(&template, &icon, Some(&info), &SnippetOptions::default())
+                .unwrap()
+                .starts_with("// Apache 2.0\n")
+        );
+
+        // Errors point at the line in the file
+        let error = SnippetTemplate::from_file("x.tpl", "---\nname: X\n---\n\n{{ body | nope }}")
+            .unwrap_err();
+        assert_eq!(error.line, 5);
+        assert!(SnippetTemplate::from_file("x.tpl", "---\ncategory: misc\n---\n").is_err());
+        assert_eq!(
+            SnippetTemplate::from_file("x.tpl", "---\ncategory: rust native\n---\n")
+                .unwrap()
+                .category,
+            SnippetCategory::RustNative
+        );
+        assert_eq!(
+            SnippetTemplate::from_file("plain", "{{ svg }}")
+                .unwrap()
+                .extension,
+            "txt"
+        );
+    }
+
+    #[test]
+    fn registry_lists_user_templates_with_builtins() {
+        let mut registry = SnippetRegistry::new();
+        assert_eq!(registry.all().len(), SnippetType::all().len());
+        let mut template = SnippetTemplate::from_file("svelte5.svelte.tpl", "{{ body }}").unwrap();
+        template.category = SnippetCategory::Components;
+        registry.register(template.clone());
+        template.name = "Svelte 5".into();
+        registry.register(template);
+
+        let components = registry.by_category(SnippetCategory::Components);
+        assert_eq!(components.last().unwrap().name, "Svelte 5");
+        assert_eq!(registry.custom().len(), 1);
+        assert_eq!(registry.get("react-ts").unwrap().extension, "tsx");
+        assert!(registry.remove("svelte5"));
+        assert!(registry.get("svelte5").is_none());
+    }
 }
@@ -0,0 +1,367 @@
+//! SVG sprite sheets: many icons as `<symbol>`s in one file, referenced with
+//! `<use href="sprite.svg#id">`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::tree::{self, Node};
+use crate::types::ResolvedIcon;
+
+/// How symbol ids are derived from icons.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum IdScheme {
+    /// `prefix-name`, e.g. `mdi-home`.
+    #[default]
+    PrefixName,
+    /// The icon name alone, e.g. `home`.
+    Name,
+    /// A template where `{prefix}` and `{name}` are substituted, e.g.
+    /// `icon-{name}`.
+    Template(String),
+}
+
+impl IdScheme {
+    /// The raw id for an icon, before it is made safe and unique.
+    #[must_use]
+    pub fn raw_id(&self, icon: &ResolvedIcon) -> String {
+        match self {
+            Self::PrefixName => format!("{}-{}", icon.prefix, icon.name),
+            Self::Name => icon.name.clone(),
+            Self::Template(template) => template
+                .replace("{prefix}", &icon.prefix)
+                .replace("{name}", &icon.name),
+        }
+    }
+}
+
+/// Options for [`build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteOptions {
+    pub naming: IdScheme,
+    /// URL of the sprite file used in the `<use>` snippets.
+    pub href: String,
+}
+
+impl Default for SpriteOptions {
+    fn default() -> Self {
+        Self {
+            naming: IdScheme::default(),
+            href: "sprite.svg".to_string(),
+        }
+    }
+}
+
+/// One icon in a sprite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteEntry {
+    /// The icon as `prefix:name`.
+    pub icon: String,
+    /// Id of the symbol that draws it.
+    pub id: String,
+    /// Markup that displays the icon.
+    pub usage: String,
+}
+
+/// A built sprite sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sprite {
+    /// Contents of `sprite.svg`.
+    pub svg: String,
+    /// One entry per input icon, in input order.
+    pub entries: Vec<SpriteEntry>,
+}
+
+/// Turn any string into a valid, `href`-friendly XML id.
+///
+/// Characters other than ASCII letters, digits, `-` and `_` become `-`, and
+/// ids that do not start with a letter get an `i-` prefix.
+#[must_use]
+pub fn safe_id(raw: &str) -> String {
+    let mut id: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        id.insert_str(0, "i-");
+    }
+    id
+}
+
+/// `base`, or `base-2`, `base-3`, … if it is already in `used`. The returned
+/// id is added to `used`.
+pub(crate) fn unique_id(base: &str, used: &mut Vec<String>) -> String {
+    let mut id = base.to_string();
+    let mut n = 2;
+    while used.contains(&id) {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    used.push(id.clone());
+    id
+}
+
+/// A `<symbol>` for one icon, with transformations applied.
+#[must_use]
+pub fn symbol(icon: &ResolvedIcon, id: &str) -> String {
+    let icon = crate::svg::apply_transformations(icon);
+    format!(
+        r#"<symbol id="{id}" viewBox="{}">{}</symbol>"#,
+        crate::svg::view_box(&icon),
+        icon.body
+    )
+}
+
+/// Prefix every id defined in a body with `prefix-` and update references to
+/// them, so bodies from different icons cannot clash inside one document.
+///
+/// Bodies that cannot be parsed are returned unchanged.
+#[must_use]
+pub fn prefix_ids(body: &str, prefix: &str) -> String {
+    let Ok(mut nodes) = tree::parse(body) else {
+        return body.to_string();
+    };
+    let mut ids = Vec::new();
+    tree::walk(&nodes, &mut |el| {
+        if let Some(id) = el.attr("id") {
+            ids.push(id.to_string());
+        }
+    });
+    if ids.is_empty() {
+        return body.to_string();
+    }
+    let renamed: HashMap<String, String> = ids
+        .into_iter()
+        .map(|id| {
+            let new = format!("{prefix}-{id}");
+            (id, new)
+        })
+        .collect();
+
+    tree::walk_mut(&mut nodes, &mut |el| {
+        for attr in &mut el.attributes {
+            if attr.name == "id" {
+                if let Some(new) = renamed.get(&attr.value) {
+                    attr.value.clone_from(new);
+                }
+            } else if attr.name == "href" || attr.name == "xlink:href" {
+                if let Some(new) = attr.value.strip_prefix('#').and_then(|id| renamed.get(id)) {
+                    attr.value = format!("#{new}");
+                }
+            } else if attr.name == "begin" || attr.name == "end" {
+                attr.value = rename_timing_refs(&attr.value, &renamed);
+            } else if attr.value.contains("url(") {
+                attr.value = rename_url_refs(&attr.value, &renamed);
+            }
+        }
+        if el.name == "style" {
+            for child in &mut el.children {
+                if let Node::Text(css) | Node::CData(css) = child {
+                    *css = rename_url_refs(css, &renamed);
+                }
+            }
+        }
+    });
+    tree::to_markup(&nodes)
+}
+
+/// Rewrite `url(#id)` references, with or without quotes.
+fn rename_url_refs(value: &str, renamed: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("url(") {
+        out.push_str(&rest[..start + 4]);
+        rest = &rest[start + 4..];
+        let Some(end) = rest.find(')') else { break };
+        let inner = rest[..end].trim();
+        let quote = inner
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .map(String::from)
+            .unwrap_or_default();
+        let target = inner.trim_matches(['"', '\'']);
+        match target.strip_prefix('#').and_then(|id| renamed.get(id)) {
+            Some(new) => {
+                let _ = write!(out, "{quote}#{new}{quote}");
+            }
+            None => out.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rewrite the `id.` part of SMIL timing values such as `a.end+1s; b.click`.
+fn rename_timing_refs(value: &str, renamed: &HashMap<String, String>) -> String {
+    value
+        .split(';')
+        .map(|part| {
+            let trimmed = part.trim_start();
+            let leading = &part[..part.len() - trimmed.len()];
+            match trimmed
+                .split_once('.')
+                .and_then(|(id, rest)| Some((renamed.get(id)?, rest)))
+            {
+                Some((new, rest)) => format!("{leading}{new}.{rest}"),
+                None => part.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// The `<use>` snippet for a symbol.
+fn usage(icon: &ResolvedIcon, href: &str, id: &str, tinted: bool) -> String {
+    let fill = if tinted {
+        r#" fill="currentColor""#
+    } else {
+        ""
+    };
+    format!(
+        r#"<svg width="{}" height="{}"{fill}><use href="{href}#{id}"/></svg>"#,
+        icon.width, icon.height
+    )
+}
+
+/// Build a sprite sheet from many icons.
+///
+/// Ids are derived with the configured [`IdScheme`], made safe with
+/// [`safe_id`] and suffixed with `-2`, `-3`, … when they collide. Icons that
+/// render identically share one symbol, and ids inside each symbol are
+/// prefixed with the symbol id.
+#[must_use]
+pub fn build(icons: &[ResolvedIcon], options: &SpriteOptions) -> Sprite {
+    let mut svg = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg">"#);
+    let mut entries = Vec::with_capacity(icons.len());
+    let mut used_ids: Vec<String> = Vec::new();
+    // (viewBox, body) -> symbol id
+    let mut by_content: HashMap<(String, String), String> = HashMap::new();
+
+    for icon in icons {
+        let (prepared, palette) =
+            crate::color::prepare_for_tinting(&crate::svg::apply_transformations(icon));
+        let key = (crate::svg::view_box(&prepared), prepared.body.clone());
+
+        let id = if let Some(id) = by_content.get(&key) {
+            id.clone()
+        } else {
+            let id = unique_id(&safe_id(&options.naming.raw_id(icon)), &mut used_ids);
+            let body = prefix_ids(&prepared.body, &id);
+            svg.push_str(&symbol(
+                &ResolvedIcon {
+                    body,
+                    ..prepared.clone()
+                },
+                &id,
+            ));
+            by_content.insert(key, id.clone());
+            id
+        };
+
+        entries.push(SpriteEntry {
+            icon: format!("{}:{}", icon.prefix, icon.name),
+            usage: usage(&prepared, &options.href, &id, !palette),
+            id,
+        });
+    }
+    svg.push_str("</svg>");
+    Sprite { svg, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon(prefix: &str, name: &str, body: &str) -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: prefix.into(),
+            name: name.into(),
+            body: body.into(),
+            width: 24,
+            height: 24,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn makes_safe_ids() {
+        assert_eq!(safe_id("mdi:home"), "mdi-home");
+        assert_eq!(safe_id("1st place"), "i-1st-place");
+        assert_eq!(safe_id("flag_us"), "flag_us");
+    }
+
+    #[test]
+    fn naming_schemes() {
+        let home = icon("mdi", "home", "");
+        assert_eq!(IdScheme::PrefixName.raw_id(&home), "mdi-home");
+        assert_eq!(IdScheme::Name.raw_id(&home), "home");
+        assert_eq!(
+            IdScheme::Template("icon-{name}-{prefix}".into()).raw_id(&home),
+            "icon-home-mdi"
+        );
+    }
+
+    #[test]
+    fn builds_sprite_with_unique_ids() {
+        let icons = [
+            icon("mdi", "home", r#"<path d="M1 1"/>"#),
+            icon("lucide", "home", r#"<path d="M2 2"/>"#),
+            icon("mdi", "house", r#"<path d="M1 1"/>"#),
+        ];
+        let options = SpriteOptions {
+            naming: IdScheme::Name,
+            ..Default::default()
+        };
+        let sprite = build(&icons, &options);
+        let ids: Vec<&str> = sprite.entries.iter().map(|e| e.id.as_str()).collect();
+        // The duplicate body reuses the first symbol
+        assert_eq!(ids, ["home", "home-2", "home"]);
+        assert_eq!(sprite.svg.matches("<symbol").count(), 2);
+        assert_eq!(
+            sprite.entries[1].usage,
+            r#"<svg width="24" height="24" fill="currentColor"><use href="sprite.svg#home-2"/></svg>"#
+        );
+        assert!(sprite.svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><symbol id="home" viewBox="0 0 24 24">"#
+        ));
+    }
+
+    #[test]
+    fn uniquifies_internal_ids() {
+        let body = r##"<defs><linearGradient id="g"/><mask id="m"/></defs><path fill="url(#g)" mask="url('#m')"/><use xlink:href="#g"/><use href="#other"/>"##;
+        assert_eq!(
+            prefix_ids(body, "x"),
+            r##"<defs><linearGradient id="x-g"/><mask id="x-m"/></defs><path fill="url(#x-g)" mask="url('#x-m')"/><use xlink:href="#x-g"/><use href="#other"/>"##
+        );
+
+        let icons = [
+            icon("a", "one", body),
+            icon(
+                "b",
+                "two",
+                r#"<linearGradient id="g"/><path fill="url(#g)"/>"#,
+            ),
+        ];
+        let sprite = build(&icons, &SpriteOptions::default());
+        assert!(sprite.svg.contains(r#"id="a-one-g""#));
+        assert!(sprite.svg.contains(r#"id="b-two-g""#));
+        // Gradients make the icons multicolour, so no fill is forced
+        assert!(!sprite.entries[0].usage.contains("fill"));
+    }
+
+    #[test]
+    fn renames_animation_timing_refs() {
+        let body = r#"<animate id="spin1" begin="0;spin2.end" dur="1s"/><animate id="spin2" begin="spin1.end+0.5s" end="indefinite; spin1.click"/>"#;
+        assert_eq!(
+            prefix_ids(body, "x"),
+            r#"<animate id="x-spin1" begin="0;x-spin2.end" dur="1s"/><animate id="x-spin2" begin="x-spin1.end+0.5s" end="indefinite; x-spin1.click"/>"#
+        );
+    }
+}
@@ -1,12 +1,322 @@
+use std::fmt::Write;
+
 use crate::types::ResolvedIcon;
 
+/// Requested width or height of a rendered icon.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum IconSize {
+    /// Derive from the other dimension, or `1em` if neither is set.
+    #[default]
+    Default,
+    /// Use the icon's own viewBox dimension.
+    Auto,
+    /// Omit the attribute entirely.
+    Unset,
+    /// A plain number of user units.
+    Number(f64),
+    /// Any CSS length, such as `1.2em` or `24px`.
+    Css(String),
+}
+
+/// Customisations applied on top of an icon when rendering it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IconCustomisations {
+    pub width: IconSize,
+    pub height: IconSize,
+    /// Rotation in quarter turns; any integer is accepted.
+    pub rotate: i32,
+    pub h_flip: bool,
+    pub v_flip: bool,
+    pub preserve_aspect_ratio: Option<String>,
+}
+
+/// The viewBox of an icon as `[left, top, width, height]`.
+pub type ViewBox = [f64; 4];
+
+/// Output of [`icon_to_svg`]: root attributes, viewBox and transformed body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconSvg {
+    /// Attributes for the root `<svg>`, in output order.
+    pub attributes: Vec<(String, String)>,
+    pub view_box: ViewBox,
+    pub body: String,
+}
+
+impl IconSvg {
+    /// Set a root attribute, replacing any existing value in place.
+    pub fn set_attribute(&mut self, name: &str, value: String) {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+    }
+
+    /// Serialize into a standalone `<svg>` document.
+    #[must_use]
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg""#);
+        for (name, value) in &self.attributes {
+            let _ = write!(svg, r#" {name}="{value}""#);
+        }
+        svg.push('>');
+        svg.push_str(&self.body);
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// Render an icon with customisations, mirroring Iconify's `iconToSVG`.
+///
+/// The icon's own flips and rotation are applied first, then the
+/// customisations. Each step wraps the body in a `<g transform>` and updates
+/// the viewBox so the result is framed correctly.
+#[must_use]
+pub fn icon_to_svg(icon: &ResolvedIcon, customisations: &IconCustomisations) -> IconSvg {
+    let mut bbox = [
+        f64::from(icon.left),
+        f64::from(icon.top),
+        f64::from(icon.width),
+        f64::from(icon.height),
+    ];
+    let mut body = icon.body.clone();
+
+    let steps = [
+        (icon.h_flip, icon.v_flip, i32::from(icon.rotate)),
+        (
+            customisations.h_flip,
+            customisations.v_flip,
+            customisations.rotate,
+        ),
+    ];
+    for (h_flip, v_flip, rotate) in steps {
+        body = transform_body(&mut bbox, body, h_flip, v_flip, rotate);
+    }
+
+    let [left, top, box_width, box_height] = bbox;
+    let (width, height) = match (&customisations.width, &customisations.height) {
+        (IconSize::Default, h) => {
+            let height = match h {
+                IconSize::Default => IconSize::Css("1em".into()),
+                IconSize::Auto => IconSize::Number(box_height),
+                other => other.clone(),
+            };
+            (calculate_size(&height, box_width / box_height), height)
+        }
+        (w, h) => {
+            let width = match w {
+                IconSize::Auto => IconSize::Number(box_width),
+                other => other.clone(),
+            };
+            let height = match h {
+                IconSize::Default => calculate_size(&width, box_height / box_width),
+                IconSize::Auto => IconSize::Number(box_height),
+                other => other.clone(),
+            };
+            (width, height)
+        }
+    };
+
+    let mut attributes = Vec::new();
+    for (name, size) in [("width", width), ("height", height)] {
+        match size {
+            IconSize::Number(n) => attributes.push((name.to_string(), format_number(n))),
+            IconSize::Css(css) if !matches!(css.as_str(), "unset" | "undefined" | "none") => {
+                attributes.push((name.to_string(), css));
+            }
+            _ => {}
+        }
+    }
+    attributes.push(("viewBox".to_string(), format_view_box(bbox)));
+    if let Some(par) = &customisations.preserve_aspect_ratio {
+        attributes.push(("preserveAspectRatio".to_string(), par.clone()));
+    }
+
+    IconSvg {
+        attributes,
+        view_box: [left, top, box_width, box_height],
+        body,
+    }
+}
+
+/// Apply one round of flips and rotation, updating the box in place.
+fn transform_body(
+    bbox: &mut ViewBox,
+    body: String,
+    h_flip: bool,
+    v_flip: bool,
+    rotate: i32,
+) -> String {
+    let [left, top, width, height] = *bbox;
+    let mut transforms = Vec::new();
+    let mut rotation = rotate;
+
+    if h_flip && v_flip {
+        rotation += 2;
+    } else if h_flip {
+        transforms.push(format!(
+            "translate({} {})",
+            format_number(width + left),
+            format_number(-top)
+        ));
+        transforms.push("scale(-1 1)".to_string());
+        bbox[0] = 0.0;
+        bbox[1] = 0.0;
+    } else if v_flip {
+        transforms.push(format!(
+            "translate({} {})",
+            format_number(-left),
+            format_number(height + top)
+        ));
+        transforms.push("scale(1 -1)".to_string());
+        bbox[0] = 0.0;
+        bbox[1] = 0.0;
+    }
+
+    let [left, top, width, height] = *bbox;
+    match rotation.rem_euclid(4) {
+        1 => {
+            let c = format_number(height / 2.0 + top);
+            transforms.insert(0, format!("rotate(90 {c} {c})"));
+        }
+        2 => transforms.insert(
+            0,
+            format!(
+                "rotate(180 {} {})",
+                format_number(width / 2.0 + left),
+                format_number(height / 2.0 + top)
+            ),
+        ),
+        3 => {
+            let c = format_number(width / 2.0 + left);
+            transforms.insert(0, format!("rotate(-90 {c} {c})"));
+        }
+        _ => {}
+    }
+    if rotation.rem_euclid(2) == 1 {
+        *bbox = [top, left, height, width];
+    }
+
+    if transforms.is_empty() {
+        body
+    } else {
+        format!(r#"<g transform="{}">{body}</g>"#, transforms.join(" "))
+    }
+}
+
+/// Scale a size by `ratio`, rounding up to two decimals like Iconify.
+///
+/// Numbers inside CSS lengths are scaled and the units kept.
+fn calculate_size(size: &IconSize, ratio: f64) -> IconSize {
+    let scale = |n: f64| (n * ratio * 100.0).ceil() / 100.0;
+    match size {
+        IconSize::Number(n) => IconSize::Number(scale(*n)),
+        IconSize::Css(css) => {
+            let mut out = String::new();
+            let mut number = String::new();
+            let flush = |number: &mut String, out: &mut String| {
+                if !number.is_empty() {
+                    match number.parse::<f64>() {
+                        Ok(n) => out.push_str(&format_number(scale(n))),
+                        Err(_) => out.push_str(number),
+                    }
+                    number.clear();
+                }
+            };
+            for c in css.chars() {
+                if c.is_ascii_digit() || c == '.' || (c == '-' && number.is_empty()) {
+                    number.push(c);
+                } else {
+                    flush(&mut number, &mut out);
+                    out.push(c);
+                }
+            }
+            flush(&mut number, &mut out);
+            IconSize::Css(out)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Format a number the way JavaScript's `toString` would for typical values.
+#[must_use]
+pub fn format_number(n: f64) -> String {
+    // Adding zero turns -0.0 into 0.0
+    format!("{}", n + 0.0)
+}
+
+fn format_view_box(bbox: ViewBox) -> String {
+    bbox.iter()
+        .map(|n| format_number(*n))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The `viewBox` attribute value of an icon (`left top width height`).
+#[must_use]
+pub fn view_box(icon: &ResolvedIcon) -> String {
+    format!("{} {} {} {}", icon.left, icon.top, icon.width, icon.height)
+}
+
+/// Bake an icon's own rotation and flips into its body.
+///
+/// The result has no transformations left and a viewBox that frames the
+/// transformed body, so it can be emitted by generators that only know about
+/// `body` and [`view_box`].
+#[must_use]
+pub fn apply_transformations(icon: &ResolvedIcon) -> ResolvedIcon {
+    if icon.rotate.is_multiple_of(4) && !icon.h_flip && !icon.v_flip {
+        return icon.clone();
+    }
+    let mut bbox = [
+        f64::from(icon.left),
+        f64::from(icon.top),
+        f64::from(icon.width),
+        f64::from(icon.height),
+    ];
+    let body = transform_body(
+        &mut bbox,
+        icon.body.clone(),
+        icon.h_flip,
+        icon.v_flip,
+        i32::from(icon.rotate),
+    );
+    // Flips and quarter turns only ever swap or zero the integer box values.
+    let (width, height) = if icon.rotate % 2 == 1 {
+        (icon.height, icon.width)
+    } else {
+        (icon.width, icon.height)
+    };
+    let offsets_zeroed = icon.h_flip != icon.v_flip;
+    let (left, top) = match (offsets_zeroed, icon.rotate % 2 == 1) {
+        (true, _) => (0, 0),
+        (false, true) => (icon.top, icon.left),
+        (false, false) => (icon.left, icon.top),
+    };
+    ResolvedIcon {
+        body,
+        left,
+        top,
+        width,
+        height,
+        rotate: 0,
+        h_flip: false,
+        v_flip: false,
+        ..icon.clone()
+    }
+}
+
 /// Build a complete SVG string from a resolved icon.
+///
+/// The icon's own transformations are applied and it is rendered at its
+/// natural size.
 #[must_use]
 pub fn build_svg(icon: &ResolvedIcon) -> String {
-    format!(
-        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">{}</svg>"#,
-        icon.width, icon.height, icon.width, icon.height, icon.body,
-    )
+    let customisations = IconCustomisations {
+        width: IconSize::Auto,
+        height: IconSize::Auto,
+        ..Default::default()
+    };
+    icon_to_svg(icon, &customisations).to_svg()
 }
 
 /// URL for the Iconify CDN SVG endpoint.
@@ -35,6 +345,7 @@ mod tests {
             body: "<path d=\"M5 12h14\"/>".into(),
             width: 24,
             height: 24,
+            ..Default::default()
         };
         let svg = build_svg(&icon);
         assert!(svg.contains("viewBox=\"0 0 24 24\""));
@@ -46,4 +357,117 @@ mod tests {
         let url = iconify_img_url("mdi", "home");
         assert_eq!(url, "https://api.iconify.design/mdi/home.svg?height=1.2em");
     }
+
+    fn icon(width: u32, height: u32) -> ResolvedIcon {
+        ResolvedIcon {
+            prefix: "test".into(),
+            name: "icon".into(),
+            body: "<path/>".into(),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_size_is_one_em_high() {
+        let out = icon_to_svg(&icon(32, 16), &IconCustomisations::default());
+        assert_eq!(
+            out.attributes,
+            vec![
+                ("width".to_string(), "2em".to_string()),
+                ("height".to_string(), "1em".to_string()),
+                ("viewBox".to_string(), "0 0 32 16".to_string()),
+            ]
+        );
+        assert_eq!(out.body, "<path/>");
+    }
+
+    #[test]
+    fn sizes_scale_and_unset() {
+        let custom = IconCustomisations {
+            width: IconSize::Number(10.0),
+            preserve_aspect_ratio: Some("xMidYMid meet".into()),
+            ..Default::default()
+        };
+        let out = icon_to_svg(&icon(24, 20), &custom);
+        assert_eq!(out.attributes[1], ("height".into(), "8.34".into()));
+        assert_eq!(
+            out.attributes[3],
+            ("preserveAspectRatio".into(), "xMidYMid meet".into())
+        );
+
+        let custom = IconCustomisations {
+            width: IconSize::Unset,
+            height: IconSize::Css("unset".into()),
+            ..Default::default()
+        };
+        let out = icon_to_svg(&icon(24, 24), &custom);
+        assert_eq!(out.attributes.len(), 1);
+    }
+
+    #[test]
+    fn horizontal_flip_wraps_body() {
+        let custom = IconCustomisations {
+            h_flip: true,
+            ..Default::default()
+        };
+        let out = icon_to_svg(&icon(24, 24), &custom);
+        assert_eq!(
+            out.body,
+            r#"<g transform="translate(24 0) scale(-1 1)"><path/></g>"#
+        );
+    }
+
+    #[test]
+    fn rotation_swaps_viewbox() {
+        let mut i = icon(32, 16);
+        i.rotate = 1;
+        let out = icon_to_svg(
+            &i,
+            &IconCustomisations {
+                width: IconSize::Auto,
+                height: IconSize::Auto,
+                ..Default::default()
+            },
+        );
+        assert_eq!(out.body, r#"<g transform="rotate(90 8 8)"><path/></g>"#);
+        assert_eq!(out.view_box, [0.0, 0.0, 16.0, 32.0]);
+        assert!(out.to_svg().contains(r#"width="16" height="32""#));
+    }
+
+    #[test]
+    fn both_flips_become_half_turn() {
+        let custom = IconCustomisations {
+            h_flip: true,
+            v_flip: true,
+            rotate: -1,
+            ..Default::default()
+        };
+        let out = icon_to_svg(&icon(24, 24), &custom);
+        assert_eq!(out.body, r#"<g transform="rotate(90 12 12)"><path/></g>"#);
+    }
+
+    #[test]
+    fn apply_transformations_matches_renderer() {
+        let mut i = icon(32, 16);
+        i.left = -2;
+        i.top = 1;
+        i.rotate = 3;
+        i.v_flip = true;
+        let flat = apply_transformations(&i);
+        let rendered = icon_to_svg(&i, &IconCustomisations::default());
+        assert_eq!(flat.body, rendered.body);
+        assert_eq!(
+            [
+                f64::from(flat.left),
+                f64::from(flat.top),
+                f64::from(flat.width),
+                f64::from(flat.height)
+            ],
+            rendered.view_box
+        );
+        assert_eq!(flat.rotate, 0);
+        assert!(!flat.v_flip);
+    }
 }
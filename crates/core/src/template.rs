@@ -0,0 +1,664 @@
+//! A small text template engine for snippets.
+//!
+//! Syntax:
+//! - `{{ name }}` inserts a variable, `{{ "text" }}` a literal;
+//! - `{{ name | filter | filter(arg) }}` pipes it through filters;
+//! - `{% if name %}…{% else %}…{% endif %}` and `{% if not name %}` test
+//!   whether a variable is non-empty (and not `false`);
+//! - `{# … #}` is a comment.
+//!
+//! A `{% … %}` or `{# … #}` tag alone on its line removes the whole line, so
+//! conditional lines can be written without stray blank lines.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+/// Variables available to a template.
+pub type Variables = BTreeMap<String, String>;
+
+/// Filters accepted in `{{ … | filter }}`.
+pub const FILTERS: &[&str] = &[
+    "upper", "lower", "trim", "pascal", "camel", "kebab", "snake", "json", "rust", "rust_raw",
+    "slint", "html", "indent", "default",
+];
+
+/// Error produced when a template is malformed or refers to something that
+/// does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    /// 1-based line of the offending tag.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operand {
+    Variable(String),
+    Literal(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Filter {
+    name: String,
+    arg: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Output {
+        value: Operand,
+        filters: Vec<Filter>,
+        line: usize,
+    },
+    If {
+        name: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+        line: usize,
+    },
+}
+
+/// A parsed template.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parse a template.
+    ///
+    /// # Errors
+    /// Returns a [`TemplateError`] for unclosed tags, unknown filters and
+    /// unbalanced `if` blocks.
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let tokens = tokenize(source)?;
+        let mut tokens = tokens.into_iter();
+        let (nodes, end) = parse_block(&mut tokens)?;
+        match end {
+            None => Ok(Self { nodes }),
+            Some((tag, line)) => Err(TemplateError {
+                line,
+                message: format!("unexpected `{{% {tag} %}}`"),
+            }),
+        }
+    }
+
+    /// Render with the given variables.
+    ///
+    /// # Errors
+    /// Returns a [`TemplateError`] if the template uses a variable that is
+    /// not defined.
+    pub fn render(&self, variables: &Variables) -> Result<String, TemplateError> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, variables, &mut out)?;
+        Ok(out)
+    }
+
+    /// Names of the variables the template refers to, in either branch of
+    /// every `if`.
+    #[must_use]
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut names = BTreeSet::new();
+        collect_variables(&self.nodes, &mut names);
+        names
+    }
+}
+
+fn collect_variables<'a>(nodes: &'a [Node], names: &mut BTreeSet<&'a str>) {
+    for node in nodes {
+        match node {
+            Node::Text(_)
+            | Node::Output {
+                value: Operand::Literal(_),
+                ..
+            } => {}
+            Node::Output {
+                value: Operand::Variable(name),
+                ..
+            } => {
+                names.insert(name);
+            }
+            Node::If {
+                name,
+                then,
+                otherwise,
+                ..
+            } => {
+                names.insert(name);
+                collect_variables(then, names);
+                collect_variables(otherwise, names);
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+enum Token {
+    Text(String),
+    Output(String, usize),
+    Block(String, usize),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    let mut line = 1;
+    // Whether `rest` starts at the beginning of a line
+    let mut at_line_start = true;
+
+    loop {
+        let next = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|open| rest.find(open).map(|i| (i, *open)))
+            .min();
+        let Some((start, open)) = next else {
+            if !rest.is_empty() {
+                tokens.push(Token::Text(rest.to_string()));
+            }
+            return Ok(tokens);
+        };
+        let close = match open {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let mut text = &rest[..start];
+        let tag_line = line + text.matches('\n').count();
+        let inner_start = start + 2;
+        let Some(len) = rest[inner_start..].find(close) else {
+            return Err(TemplateError {
+                line: tag_line,
+                message: format!("`{open}` is never closed"),
+            });
+        };
+        let inner = rest[inner_start..inner_start + len].trim();
+        let mut after = &rest[inner_start + len + 2..];
+        line = tag_line + inner.matches('\n').count();
+
+        let standalone = open != "{{" && {
+            let before_ok = match text.rfind('\n') {
+                Some(i) => text[i + 1..].trim().is_empty(),
+                None => at_line_start && text.trim().is_empty(),
+            };
+            let eol = after.find('\n');
+            before_ok && after[..eol.unwrap_or(after.len())].trim().is_empty()
+        };
+        if standalone {
+            text = &text[..text.rfind('\n').map_or(0, |i| i + 1)];
+            match after.find('\n') {
+                Some(i) => {
+                    after = &after[i + 1..];
+                    line += 1;
+                }
+                None => after = "",
+            }
+            at_line_start = true;
+        } else {
+            at_line_start = false;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text.to_string()));
+        }
+        match open {
+            "{{" => tokens.push(Token::Output(inner.to_string(), tag_line)),
+            "{%" => tokens.push(Token::Block(inner.to_string(), tag_line)),
+            _ => {}
+        }
+        rest = after;
+    }
+}
+
+/// The `else`/`endif` tag that ended a block, with its line.
+type BlockEnd = Option<(String, usize)>;
+
+/// Parse nodes up to an `else`/`endif` tag.
+fn parse_block(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, BlockEnd), TemplateError> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Output(expr, line) => {
+                let (value, filters) =
+                    parse_expression(&expr).map_err(|message| TemplateError { line, message })?;
+                nodes.push(Node::Output {
+                    value,
+                    filters,
+                    line,
+                });
+            }
+            Token::Block(tag, line) => {
+                let mut words = tag.split_whitespace();
+                match words.next() {
+                    Some("if") => {
+                        let (negate, name) = match (words.next(), words.next()) {
+                            (Some("not"), Some(name)) => (true, name),
+                            (Some(name), None) => (false, name),
+                            _ => {
+                                return Err(TemplateError {
+                                    line,
+                                    message: "expected `if name` or `if not name`".to_string(),
+                                })
+                            }
+                        };
+                        let name = name.to_string();
+                        let (then, end) = parse_block(tokens)?;
+                        let otherwise = match end {
+                            Some((tag, _)) if tag == "endif" => Vec::new(),
+                            Some((tag, else_line)) if tag == "else" => match parse_block(tokens)? {
+                                (otherwise, Some((tag, _))) if tag == "endif" => otherwise,
+                                (_, Some((tag, line))) => {
+                                    return Err(TemplateError {
+                                        line,
+                                        message: format!("unexpected `{{% {tag} %}}`"),
+                                    })
+                                }
+                                (_, None) => {
+                                    return Err(TemplateError {
+                                        line: else_line,
+                                        message: "`else` without `endif`".to_string(),
+                                    })
+                                }
+                            },
+                            _ => {
+                                return Err(TemplateError {
+                                    line,
+                                    message: "`if` without `endif`".to_string(),
+                                })
+                            }
+                        };
+                        nodes.push(Node::If {
+                            name,
+                            negate,
+                            then,
+                            otherwise,
+                            line,
+                        });
+                    }
+                    Some(end @ ("else" | "endif")) => {
+                        return Ok((nodes, Some((end.to_string(), line))));
+                    }
+                    _ => {
+                        return Err(TemplateError {
+                            line,
+                            message: format!("unknown tag `{{% {tag} %}}`"),
+                        })
+                    }
+                }
+            }
+        }
+    }
+    Ok((nodes, None))
+}
+
+/// Parse `operand (| filter (arg)?)*`.
+fn parse_expression(expr: &str) -> Result<(Operand, Vec<Filter>), String> {
+    let mut rest = expr.trim();
+    let value = if rest.starts_with('"') {
+        let (literal, after) = parse_string(rest)?;
+        rest = after;
+        Operand::Literal(literal)
+    } else {
+        let (name, after) = parse_identifier(rest)?;
+        rest = after;
+        Operand::Variable(name.to_string())
+    };
+
+    let mut filters = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok((value, filters));
+        }
+        rest = rest
+            .strip_prefix('|')
+            .ok_or_else(|| format!("expected `|` before `{rest}`"))?
+            .trim_start();
+        let (name, after) = parse_identifier(rest)?;
+        rest = after.trim_start();
+        let arg = if let Some(inner) = rest.strip_prefix('(') {
+            let inner = inner.trim_start();
+            let (arg, after) = if inner.starts_with('"') {
+                parse_string(inner)?
+            } else {
+                let end = inner
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(inner.len());
+                (inner[..end].to_string(), &inner[end..])
+            };
+            rest = after
+                .trim_start()
+                .strip_prefix(')')
+                .ok_or_else(|| format!("expected `)` after the argument of `{name}`"))?;
+            Some(arg)
+        } else {
+            None
+        };
+        check_filter(name, arg.as_deref())?;
+        filters.push(Filter {
+            name: name.to_string(),
+            arg,
+        });
+    }
+}
+
+fn parse_identifier(s: &str) -> Result<(&str, &str), String> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(s.len());
+    if end == 0 || s.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("expected a name at `{s}`"));
+    }
+    Ok((&s[..end], &s[end..]))
+}
+
+/// Parse a double-quoted string with `\"` and `\\` escapes.
+fn parse_string(s: &str) -> Result<(String, &str), String> {
+    let mut out = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, &s[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, c)) => out.push(c),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+fn check_filter(name: &str, arg: Option<&str>) -> Result<(), String> {
+    match (name, arg) {
+        ("indent", Some(n)) if n.parse::<usize>().is_ok() => Ok(()),
+        ("indent", _) => Err("`indent` takes a number of spaces".to_string()),
+        ("default", Some(_)) => Ok(()),
+        ("default", None) => Err("`default` takes a value".to_string()),
+        (name, None) if FILTERS.contains(&name) => Ok(()),
+        (name, Some(_)) if FILTERS.contains(&name) => {
+            Err(format!("`{name}` does not take an argument"))
+        }
+        (name, _) => Err(format!("unknown filter `{name}`")),
+    }
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+fn lookup<'a>(variables: &'a Variables, name: &str, line: usize) -> Result<&'a str, TemplateError> {
+    variables
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| TemplateError {
+            line,
+            message: format!("unknown variable `{name}`"),
+        })
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    variables: &Variables,
+    out: &mut String,
+) -> Result<(), TemplateError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Output {
+                value,
+                filters,
+                line,
+            } => {
+                let mut value = match value {
+                    Operand::Variable(name) => lookup(variables, name, *line)?.to_string(),
+                    Operand::Literal(text) => text.clone(),
+                };
+                for filter in filters {
+                    value = apply_filter(&filter.name, filter.arg.as_deref(), &value);
+                }
+                out.push_str(&value);
+            }
+            Node::If {
+                name,
+                negate,
+                then,
+                otherwise,
+                line,
+            } => {
+                let value = lookup(variables, name, *line)?;
+                let truthy = !value.is_empty() && value != "false";
+                let branch = if truthy == *negate { otherwise } else { then };
+                render_nodes(branch, variables, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split text into words at anything that is not a letter or digit, and at
+/// lower-to-upper case changes.
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |c| {
+        c.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase()
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A Rust raw string literal with as few `#`s as the text allows.
+fn raw_string(s: &str) -> String {
+    let hashes = s
+        .split('"')
+        .skip(1)
+        .map(|rest| rest.len() - rest.trim_start_matches('#').len() + 1)
+        .max()
+        .unwrap_or(0);
+    let hashes = "#".repeat(hashes);
+    format!("r{hashes}\"{s}\"{hashes}")
+}
+
+fn apply_filter(name: &str, arg: Option<&str>, value: &str) -> String {
+    match name {
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        "trim" => value.trim().to_string(),
+        "pascal" => words(value).iter().map(|w| capitalize(w)).collect(),
+        "camel" => {
+            let pascal: String = words(value).iter().map(|w| capitalize(w)).collect();
+            let mut chars = pascal.chars();
+            chars.next().map_or_else(String::new, |c| {
+                c.to_lowercase().collect::<String>() + chars.as_str()
+            })
+        }
+        "kebab" => words(value).join("-").to_lowercase(),
+        "snake" => words(value).join("_").to_lowercase(),
+        "json" => json_string(value),
+        "rust" => format!("{value:?}"),
+        "rust_raw" => raw_string(value),
+        "slint" => crate::slint::string_literal(value),
+        "html" => value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;"),
+        "indent" => {
+            let pad = " ".repeat(arg.and_then(|n| n.parse().ok()).unwrap_or(0));
+            value
+                .split('\n')
+                .enumerate()
+                .map(|(i, line)| {
+                    if i == 0 || line.is_empty() {
+                        line.to_string()
+                    } else {
+                        format!("{pad}{line}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        "default" if value.is_empty() => arg.unwrap_or_default().to_string(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Variables {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    fn render(source: &str, pairs: &[(&str, &str)]) -> String {
+        Template::parse(source)
+            .unwrap()
+            .render(&vars(pairs))
+            .unwrap()
+    }
+
+    #[test]
+    fn lists_referenced_variables() {
+        let template = Template::parse(
+            "{{ a | upper }}{{ \"lit\" }}{% if not b %}{{ c }}{% else %}{% if d %}{{ a }}{% endif %}{% endif %}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.variables().into_iter().collect::<Vec<_>>(),
+            ["a", "b", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn substitutes_and_filters() {
+        let v = [("name", "arrow-left"), ("empty", "")];
+        assert_eq!(render("<{{ name }}>", &v), "<arrow-left>");
+        assert_eq!(render("{{name|pascal}}", &v), "ArrowLeft");
+        assert_eq!(render("{{ name | camel }}", &v), "arrowLeft");
+        assert_eq!(render("{{ name | snake | upper }}", &v), "ARROW_LEFT");
+        assert_eq!(render("{{ \"FooBar\" | kebab }}", &v), "foo-bar");
+        assert_eq!(render("{{ empty | default(\"none\") }}", &v), "none");
+        assert_eq!(render("{{ \"a\\\"b\" | json }}", &v), r#""a\"b""#);
+        assert_eq!(render("{{ \"a\tb\" | rust }}", &v), r#""a\tb""#);
+        assert_eq!(render("{{ \"a\" | rust_raw }}", &v), r#"r"a""#);
+        assert_eq!(
+            render("{{ color | rust_raw }}", &[("color", r##"x="#f00""##)]),
+            "r##\"x=\"#f00\"\"##"
+        );
+        assert_eq!(
+            render("{{ \"<a href='x'>\" | html }}", &v),
+            "&lt;a href=&#39;x&#39;&gt;"
+        );
+        assert_eq!(
+            render("  {{ \"a\nb\n\nc\" | indent(2) }}", &v),
+            "  a\n  b\n\n  c"
+        );
+        // Literal braces
+        assert_eq!(render("{{ \"{{\" }} x }}", &v), "{{ x }}");
+        assert_eq!(render("{# note #}style={a: 1}", &v), "style={a: 1}");
+    }
+
+    #[test]
+    fn conditionals_remove_their_lines() {
+        let source = "a\n  {% if flag %}\n  b\n  {% else %}\n  c\n  {% endif %}\nd{% if not flag %}!{% endif %}\n";
+        assert_eq!(render(source, &[("flag", "true")]), "a\n  b\nd\n");
+        assert_eq!(render(source, &[("flag", "")]), "a\n  c\nd!\n");
+        assert_eq!(render(source, &[("flag", "false")]), "a\n  c\nd!\n");
+        assert_eq!(
+            render("{% if flag %}\nx\n{% endif %}\n", &[("flag", "1")]),
+            "x\n"
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_lines() {
+        let error = |source: &str| Template::parse(source).unwrap_err();
+        assert_eq!(error("a\n{{ name").line, 2);
+        assert_eq!(
+            error("{{ name | shout }}").message,
+            "unknown filter `shout`"
+        );
+        assert_eq!(error("x\n\n{% if a %}").message, "`if` without `endif`");
+        assert_eq!(error("x\n\n{% if a %}").line, 3);
+        assert_eq!(error("{% endif %}").message, "unexpected `{% endif %}`");
+        assert_eq!(
+            error("{{ a | indent }}").message,
+            "`indent` takes a number of spaces"
+        );
+        assert_eq!(
+            error("{% for x in y %}").message,
+            "unknown tag `{% for x in y %}`"
+        );
+
+        let unknown = Template::parse("\n{{ missing }}")
+            .unwrap()
+            .render(&Variables::new());
+        assert_eq!(
+            unknown,
+            Err(TemplateError {
+                line: 2,
+                message: "unknown variable `missing`".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,487 @@
+//! Minimal SVG/XML parser and element tree.
+//!
+//! Icon bodies are small, well-formed XML fragments, so this parser only
+//! supports what they use: elements, attributes, text, comments and CDATA.
+//! XML declarations, doctypes and processing instructions are skipped.
+//! Attribute values and text are stored decoded; [`to_markup`] escapes them
+//! again when serializing.
+
+use std::fmt;
+
+/// A node in the element tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+    Comment(String),
+    CData(String),
+}
+
+/// A single attribute, kept in source order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub value: String,
+}
+
+/// An element with its attributes and children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Create an element without attributes or children.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Value of an attribute, if present.
+    #[must_use]
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Set an attribute, replacing an existing value in place.
+    pub fn set_attr(&mut self, name: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.attributes.iter_mut().find(|a| a.name == name) {
+            Some(attr) => attr.value = value,
+            None => self.attributes.push(Attribute {
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    /// Remove an attribute, returning its value.
+    pub fn remove_attr(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|a| a.name == name)?;
+        Some(self.attributes.remove(index).value)
+    }
+
+    /// Child elements, skipping text and comments.
+    pub fn child_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(el) => Some(el),
+            _ => None,
+        })
+    }
+
+    /// Name without a namespace prefix (`xlink:href` → `href`).
+    #[must_use]
+    pub fn local_name(&self) -> &str {
+        self.name.rsplit(':').next().unwrap_or(&self.name)
+    }
+}
+
+/// Error produced when a body is not well-formed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Byte offset into the input where the problem was found.
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Deepest element nesting accepted, far beyond any real icon. Deeper input
+/// is an error rather than a stack overflow.
+pub const MAX_DEPTH: usize = 256;
+
+/// Parse an SVG body (or full document) into a list of top-level nodes.
+///
+/// # Errors
+/// Returns a [`ParseError`] for unclosed or mismatched tags, malformed
+/// attributes, unterminated comments and elements nested deeper than
+/// [`MAX_DEPTH`].
+pub fn parse(input: &str) -> Result<Vec<Node>, ParseError> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+    let nodes = parser.parse_nodes(None)?;
+    Ok(nodes)
+}
+
+/// Serialize nodes back to markup. Empty elements are self-closed.
+#[must_use]
+pub fn to_markup(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        write_node(node, &mut out);
+    }
+    out
+}
+
+/// Walk every element depth-first, parents before children.
+pub fn walk<'a>(nodes: &'a [Node], f: &mut impl FnMut(&'a Element)) {
+    for node in nodes {
+        if let Node::Element(el) = node {
+            f(el);
+            walk(&el.children, f);
+        }
+    }
+}
+
+/// Mutable variant of [`walk`].
+pub fn walk_mut(nodes: &mut [Node], f: &mut impl FnMut(&mut Element)) {
+    for node in nodes {
+        if let Node::Element(el) = node {
+            f(el);
+            walk_mut(&mut el.children, f);
+        }
+    }
+}
+
+/// Escape text content.
+#[must_use]
+pub fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape a double-quoted attribute value.
+#[must_use]
+pub fn escape_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+fn write_node(node: &Node, out: &mut String) {
+    match node {
+        Node::Element(el) => {
+            out.push('<');
+            out.push_str(&el.name);
+            for attr in &el.attributes {
+                out.push(' ');
+                out.push_str(&attr.name);
+                out.push_str("=\"");
+                out.push_str(&escape_attr(&attr.value));
+                out.push('"');
+            }
+            if el.children.is_empty() {
+                out.push_str("/>");
+            } else {
+                out.push('>');
+                for child in &el.children {
+                    write_node(child, out);
+                }
+                out.push_str("</");
+                out.push_str(&el.name);
+                out.push('>');
+            }
+        }
+        Node::Text(text) => out.push_str(&escape_text(text)),
+        Node::Comment(text) => {
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->");
+        }
+        Node::CData(text) => {
+            out.push_str("<![CDATA[");
+            out.push_str(text);
+            out.push_str("]]>");
+        }
+    }
+}
+
+/// Decode XML entities; unknown entities are kept as written.
+#[must_use]
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        if let Some((c, end)) = decoded {
+            out.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    /// Number of elements currently open.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            offset: self.pos,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    /// Consume everything up to and including `end`, returning the part before it.
+    fn take_until(&mut self, end: &str, what: &str) -> Result<&'a str, ParseError> {
+        let Some(index) = self.rest().find(end) else {
+            return Err(self.error(format!("unterminated {what}")));
+        };
+        let start = self.pos;
+        self.pos += index + end.len();
+        Ok(&self.input[start..start + index])
+    }
+
+    fn parse_nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, ParseError> {
+        let mut nodes = Vec::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return match parent {
+                    Some(name) => Err(self.error(format!("unclosed <{name}>"))),
+                    None => Ok(nodes),
+                };
+            }
+            if rest.starts_with("</") {
+                let Some(name) = parent else {
+                    return Err(self.error("unexpected closing tag"));
+                };
+                self.pos += 2;
+                let closing = self.take_until(">", "closing tag")?.trim();
+                if closing != name {
+                    return Err(self.error(format!("expected </{name}>, found </{closing}>")));
+                }
+                return Ok(nodes);
+            }
+            if rest.starts_with("<!--") {
+                self.pos += 4;
+                let comment = self.take_until("-->", "comment")?;
+                nodes.push(Node::Comment(comment.to_string()));
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let data = self.take_until("]]>", "CDATA section")?;
+                nodes.push(Node::CData(data.to_string()));
+            } else if rest.starts_with("<?") {
+                self.pos += 2;
+                self.take_until("?>", "processing instruction")?;
+            } else if rest.starts_with("<!") {
+                self.pos += 2;
+                self.skip_doctype()?;
+            } else if rest.starts_with('<') {
+                nodes.push(Node::Element(self.parse_element()?));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = decode_entities(&rest[..end]);
+                self.pos += end;
+                nodes.push(Node::Text(text));
+            }
+        }
+    }
+
+    /// Skip a doctype, including an internal subset in brackets.
+    fn skip_doctype(&mut self) -> Result<(), ParseError> {
+        let mut depth = 0usize;
+        for (i, c) in self.rest().char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                '>' if depth == 0 => {
+                    self.pos += i + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        Err(self.error("unterminated doctype"))
+    }
+
+    fn parse_name(&mut self) -> Result<String, ParseError> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '<'))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += end;
+        Ok(rest[..end].to_string())
+    }
+
+    fn parse_element(&mut self) -> Result<Element, ParseError> {
+        self.pos += 1;
+        let name = self.parse_name()?;
+        let mut element = Element::new(name);
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if rest.starts_with('>') {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(format!("elements nested deeper than {MAX_DEPTH}")));
+                }
+                self.pos += 1;
+                self.depth += 1;
+                element.children = self.parse_nodes(Some(&element.name))?;
+                self.depth -= 1;
+                return Ok(element);
+            }
+            if rest.is_empty() {
+                return Err(self.error(format!("unterminated <{}>", element.name)));
+            }
+
+            let attr_name = self.parse_name()?;
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.parse_attr_value()?
+            } else {
+                // Bare attributes are not valid XML, but treat them like HTML does.
+                String::new()
+            };
+            element.attributes.push(Attribute {
+                name: attr_name,
+                value,
+            });
+        }
+    }
+
+    fn parse_attr_value(&mut self) -> Result<String, ParseError> {
+        let Some(quote @ ('"' | '\'')) = self.rest().chars().next() else {
+            return Err(self.error("expected a quoted attribute value"));
+        };
+        self.pos += 1;
+        let raw = self.take_until(&quote.to_string(), "attribute value")?;
+        Ok(decode_entities(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_elements_and_attributes() {
+        let nodes = parse(r#"<g fill="none"><path d="M0 0h24"/><circle r='2'/></g>"#).unwrap();
+        let [Node::Element(g)] = nodes.as_slice() else {
+            panic!("expected one element");
+        };
+        assert_eq!(g.name, "g");
+        assert_eq!(g.attr("fill"), Some("none"));
+        let children: Vec<&str> = g.child_elements().map(|e| e.name.as_str()).collect();
+        assert_eq!(children, ["path", "circle"]);
+        assert_eq!(g.child_elements().nth(1).unwrap().attr("r"), Some("2"));
+    }
+
+    #[test]
+    fn round_trips_comments_text_and_entities() {
+        let input = r#"<!-- note --><text x="1">a &amp; b &#x41;</text><style><![CDATA[.a{fill:red}]]></style>"#;
+        let nodes = parse(input).unwrap();
+        let Node::Element(text) = &nodes[1] else {
+            panic!("expected <text>");
+        };
+        assert_eq!(text.children, [Node::Text("a & b A".into())]);
+        assert_eq!(
+            to_markup(&nodes),
+            r#"<!-- note --><text x="1">a &amp; b A</text><style><![CDATA[.a{fill:red}]]></style>"#
+        );
+    }
+
+    #[test]
+    fn attribute_values_are_escaped() {
+        let nodes = parse(r#"<a title='say "hi" &lt;3'/>"#).unwrap();
+        assert_eq!(
+            to_markup(&nodes),
+            r#"<a title="say &quot;hi&quot; &lt;3"/>"#
+        );
+    }
+
+    #[test]
+    fn skips_declarations() {
+        let nodes =
+            parse(r#"<?xml version="1.0"?><!DOCTYPE svg [<!ENTITY x "y">]><svg/>"#).unwrap();
+        assert_eq!(to_markup(&nodes), "<svg/>");
+    }
+
+    #[test]
+    fn reports_malformed_input() {
+        assert!(parse("<g><path/>").is_err());
+        assert!(parse("<g></path>").is_err());
+        assert!(parse("<path d=M0/>").is_err());
+        assert!(parse("<!-- open").is_err());
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let nested = |depth| format!("{}{}", "<g>".repeat(depth), "</g>".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        let error = parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert!(error.message.contains("nested deeper"), "{error}");
+        assert!(parse(&"<g>".repeat(200_000)).is_err());
+    }
+
+    #[test]
+    fn walk_visits_all_elements() {
+        let mut nodes = parse("<g><g><path/></g><rect/></g>").unwrap();
+        let mut names = Vec::new();
+        walk(&nodes, &mut |el| names.push(el.name.clone()));
+        assert_eq!(names, ["g", "g", "path", "rect"]);
+        walk_mut(&mut nodes, &mut |el| el.set_attr("id", "x"));
+        assert_eq!(
+            to_markup(&nodes),
+            r#"<g id="x"><g id="x"><path id="x"/></g><rect id="x"/></g>"#
+        );
+    }
+}
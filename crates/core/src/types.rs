@@ -1,38 +1,58 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Raw collection info as returned by the Iconify `/collections` endpoint.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// This is also the `info` block of an `IconifyJSON` icon set.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CollectionInfoRaw {
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub total: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub author: Option<Author>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub samples: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<HeightValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub palette: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hidden: Option<bool>,
-    pub height: Option<HeightValue>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum HeightValue {
     Single(u32),
     Multiple(Vec<u32>),
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Author {
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct License {
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub spdx: Option<String>,
 }
 
@@ -69,14 +89,103 @@ impl CollectionInfo {
 }
 
 /// Response from `GET /{prefix}.json?icons=...` — specific icons data.
-#[derive(Debug, Clone, Deserialize)]
-pub struct IconifyResponse {
+///
+/// The API answers with a regular `IconifyJSON` document, so this is the same
+/// type as a full [`IconSet`].
+pub type IconifyResponse = IconSet;
+
+/// A complete icon set in the `IconifyJSON` format.
+///
+/// Every field of the specification is modelled so that a set can be loaded
+/// and written back without losing data. Maps are ordered to keep serialized
+/// output stable.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IconSet {
     pub prefix: String,
-    pub icons: HashMap<String, IconData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<CollectionInfoRaw>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<u64>,
+    pub icons: BTreeMap<String, IconData>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, IconAlias>,
+    /// Set-wide defaults for icons that omit them.
+    #[serde(flatten)]
+    pub defaults: IconTransform,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chars: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub categories: Option<BTreeMap<String, Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub themes: Option<BTreeMap<String, IconTheme>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefixes: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffixes: Option<BTreeMap<String, String>>,
+    /// Names requested from the API that do not exist in the set.
+    #[serde(rename = "not_found", default, skip_serializing_if = "Option::is_none")]
+    pub not_found: Option<Vec<String>>,
+}
+
+/// Optional dimensions and transformations shared by icons, aliases and the
+/// set-level defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IconTransform {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub height: Option<u32>,
-    #[serde(default)]
-    pub categories: Option<HashMap<String, Vec<String>>>,
+    /// Rotation in quarter turns (0-3).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotate: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub h_flip: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v_flip: Option<bool>,
+}
+
+impl IconTransform {
+    /// Merge a child's transformations on top of its parent, the way Iconify does.
+    ///
+    /// Dimensions and offsets set on the child win; flips are combined with XOR
+    /// and rotations are added modulo four quarter turns.
+    #[must_use]
+    pub fn merge(self, child: Self) -> Self {
+        let xor = |a: Option<bool>, b: Option<bool>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(false) != b.unwrap_or(false)),
+        };
+        Self {
+            left: child.left.or(self.left),
+            top: child.top.or(self.top),
+            width: child.width.or(self.width),
+            height: child.height.or(self.height),
+            rotate: match (self.rotate, child.rotate) {
+                (None, None) => None,
+                (a, b) => Some((a.unwrap_or(0) + b.unwrap_or(0)) % 4),
+            },
+            h_flip: xor(self.h_flip, child.h_flip),
+            v_flip: xor(self.v_flip, child.v_flip),
+        }
+    }
+}
+
+/// A legacy theme entry (`themes` in older icon sets).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IconTheme {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
 }
 
 /// Response from `GET /collection?prefix={prefix}` — list of icons in a collection.
@@ -112,34 +221,128 @@ impl CollectionResponse {
 }
 
 /// Individual icon data within an icon set.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub struct IconData {
     pub body: String,
-    pub width: Option<u32>,
-    pub height: Option<u32>,
+    #[serde(flatten)]
+    pub transform: IconTransform,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+}
+
+/// An alias pointing at another icon or alias, with optional extra transformations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IconAlias {
+    pub parent: String,
+    #[serde(flatten)]
+    pub transform: IconTransform,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
 }
 
+/// Maximum number of alias hops followed before giving up.
+pub const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Why an icon name could not be resolved from an icon set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// Neither an icon nor an alias with this name exists.
+    NotFound(String),
+    /// The alias chain loops back onto itself.
+    AliasCycle(Vec<String>),
+    /// The alias chain is longer than [`MAX_ALIAS_DEPTH`].
+    TooDeep(String),
+}
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "icon '{name}' not found"),
+            Self::AliasCycle(chain) => write!(f, "alias cycle: {}", chain.join(" -> ")),
+            Self::TooDeep(name) => write!(
+                f,
+                "alias '{name}' exceeds the maximum depth of {MAX_ALIAS_DEPTH}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
 /// Resolved icon with all fields filled in, ready to render.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ResolvedIcon {
     pub prefix: String,
     pub name: String,
     pub body: String,
+    pub left: i32,
+    pub top: i32,
     pub width: u32,
     pub height: u32,
+    /// Rotation in quarter turns (0-3).
+    pub rotate: u8,
+    pub h_flip: bool,
+    pub v_flip: bool,
+    /// Whether the icon keeps its own colours instead of following `currentColor`.
+    pub palette: bool,
 }
 
 impl ResolvedIcon {
     /// Resolve an icon from the collection response, falling back to set-level defaults.
+    ///
+    /// Aliases are followed; see [`ResolvedIcon::resolve`].
     #[must_use]
     pub fn from_response(resp: &IconifyResponse, name: &str) -> Option<Self> {
-        let data = resp.icons.get(name)?;
-        Some(Self {
-            prefix: resp.prefix.clone(),
+        Self::resolve(resp, name).ok()
+    }
+
+    /// Resolve an icon or alias by name.
+    ///
+    /// Alias chains are followed up to [`MAX_ALIAS_DEPTH`] hops and the
+    /// transformations of every hop are merged, ending with the set defaults.
+    ///
+    /// # Errors
+    /// Returns a [`ResolveError`] if the name is unknown, an alias points at a
+    /// missing parent, the chain contains a cycle or it is too deep.
+    pub fn resolve(set: &IconSet, name: &str) -> Result<Self, ResolveError> {
+        let mut chain = vec![name.to_string()];
+        let mut transform = IconTransform::default();
+        let mut current = name;
+
+        let data = loop {
+            if let Some(data) = set.icons.get(current) {
+                break data;
+            }
+            let alias = set
+                .aliases
+                .get(current)
+                .ok_or_else(|| ResolveError::NotFound(current.to_string()))?;
+            transform = alias.transform.merge(transform);
+
+            if chain.contains(&alias.parent) {
+                chain.push(alias.parent.clone());
+                return Err(ResolveError::AliasCycle(chain));
+            }
+            if chain.len() > MAX_ALIAS_DEPTH {
+                return Err(ResolveError::TooDeep(name.to_string()));
+            }
+            chain.push(alias.parent.clone());
+            current = &alias.parent;
+        };
+
+        let t = set.defaults.merge(data.transform.merge(transform));
+        Ok(Self {
+            prefix: set.prefix.clone(),
             name: name.to_string(),
             body: data.body.clone(),
-            width: data.width.or(resp.width).unwrap_or(24),
-            height: data.height.or(resp.height).unwrap_or(24),
+            left: t.left.unwrap_or(0),
+            top: t.top.unwrap_or(0),
+            width: t.width.unwrap_or(24),
+            height: t.height.unwrap_or(24),
+            rotate: t.rotate.unwrap_or(0),
+            h_flip: t.h_flip.unwrap_or(false),
+            v_flip: t.v_flip.unwrap_or(false),
+            palette: set.info.as_ref().and_then(|i| i.palette).unwrap_or(false),
         })
     }
 }
@@ -152,14 +355,7 @@ mod tests {
     fn collection_info_defaults() {
         let raw = CollectionInfoRaw {
             name: "Test".into(),
-            total: None,
-            author: None,
-            license: None,
-            samples: None,
-            category: None,
-            palette: None,
-            hidden: None,
-            height: None,
+            ..Default::default()
         };
         let info = CollectionInfo::from_raw("test".into(), raw);
         assert_eq!(info.total, 0);
@@ -170,24 +366,204 @@ mod tests {
 
     #[test]
     fn resolved_icon_falls_back_to_set_defaults() {
-        let mut icons = HashMap::new();
+        let mut icons = BTreeMap::new();
         icons.insert(
             "arrow".to_string(),
             IconData {
                 body: "<path/>".into(),
-                width: None,
-                height: None,
+                ..Default::default()
             },
         );
         let resp = IconifyResponse {
             prefix: "mdi".into(),
             icons,
-            width: Some(24),
-            height: Some(24),
-            categories: None,
+            defaults: IconTransform {
+                width: Some(24),
+                height: Some(24),
+                ..Default::default()
+            },
+            ..Default::default()
         };
         let resolved = ResolvedIcon::from_response(&resp, "arrow").unwrap();
         assert_eq!(resolved.width, 24);
         assert_eq!(resolved.height, 24);
     }
+
+    const FULL_SET: &str = r#"{
+        "prefix": "test",
+        "info": {
+            "name": "Test Icons",
+            "total": 2,
+            "version": "1.0.0",
+            "author": { "name": "Someone", "url": "https://example.com" },
+            "license": { "title": "MIT", "spdx": "MIT" },
+            "samples": ["home"],
+            "height": [16, 24],
+            "displayHeight": 24,
+            "category": "General",
+            "tags": ["Has Padding"],
+            "palette": false
+        },
+        "lastModified": 1700000000,
+        "icons": {
+            "home": { "body": "<path d=\"M0 0h1\"/>", "left": -2, "top": 1, "rotate": 1, "hFlip": true },
+            "secret": { "body": "<g/>", "width": 16, "hidden": true }
+        },
+        "aliases": {
+            "house": { "parent": "home", "vFlip": true, "hidden": true }
+        },
+        "width": 24,
+        "height": 24,
+        "chars": { "e001": "home" },
+        "categories": { "Buildings": ["home"] },
+        "themes": { "outline": { "title": "Outline", "suffix": "-outline" } },
+        "prefixes": { "baseline": "Baseline" },
+        "suffixes": { "": "Filled", "outline": "Outline" },
+        "not_found": ["missing"]
+    }"#;
+
+    #[test]
+    fn icon_set_parses_every_field() {
+        let set: IconSet = serde_json::from_str(FULL_SET).unwrap();
+        let home = &set.icons["home"];
+        assert_eq!(home.transform.left, Some(-2));
+        assert_eq!(home.transform.top, Some(1));
+        assert_eq!(home.transform.rotate, Some(1));
+        assert_eq!(home.transform.h_flip, Some(true));
+        assert_eq!(set.icons["secret"].hidden, Some(true));
+        assert_eq!(set.aliases["house"].parent, "home");
+        assert_eq!(set.aliases["house"].transform.v_flip, Some(true));
+        assert_eq!(set.defaults.width, Some(24));
+        assert_eq!(set.last_modified, Some(1_700_000_000));
+        assert_eq!(set.chars.as_ref().unwrap()["e001"], "home");
+        let info = set.info.as_ref().unwrap();
+        assert_eq!(info.display_height, Some(24));
+        assert_eq!(info.height, Some(HeightValue::Multiple(vec![16, 24])));
+        let themes = set.themes.as_ref().unwrap();
+        assert_eq!(themes["outline"].suffix.as_deref(), Some("-outline"));
+        assert_eq!(set.not_found, Some(vec!["missing".to_string()]));
+    }
+
+    #[test]
+    fn icon_set_round_trips() {
+        let set: IconSet = serde_json::from_str(FULL_SET).unwrap();
+        let json = serde_json::to_string(&set).unwrap();
+        assert!(json.contains("\"hFlip\":true"));
+        assert!(json.contains("\"lastModified\":1700000000"));
+        assert!(json.contains("\"displayHeight\":24"));
+        assert!(!json.contains("null"));
+        let again: IconSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(set, again);
+    }
+
+    fn alias_set() -> IconSet {
+        let mut set = IconSet {
+            prefix: "test".into(),
+            defaults: IconTransform {
+                width: Some(24),
+                height: Some(24),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        set.icons.insert(
+            "home".into(),
+            IconData {
+                body: "<path/>".into(),
+                transform: IconTransform {
+                    h_flip: Some(true),
+                    rotate: Some(3),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let alias = |parent: &str, transform: IconTransform| IconAlias {
+            parent: parent.into(),
+            transform,
+            hidden: None,
+        };
+        set.aliases.insert(
+            "house".into(),
+            alias(
+                "home",
+                IconTransform {
+                    h_flip: Some(true),
+                    rotate: Some(2),
+                    width: Some(20),
+                    ..Default::default()
+                },
+            ),
+        );
+        set.aliases.insert(
+            "house-variant".into(),
+            alias(
+                "house",
+                IconTransform {
+                    v_flip: Some(true),
+                    left: Some(-2),
+                    ..Default::default()
+                },
+            ),
+        );
+        set.aliases
+            .insert("loop-a".into(), alias("loop-b", IconTransform::default()));
+        set.aliases
+            .insert("loop-b".into(), alias("loop-a", IconTransform::default()));
+        set.aliases
+            .insert("broken".into(), alias("nowhere", IconTransform::default()));
+        set
+    }
+
+    #[test]
+    fn resolve_follows_alias_chain_and_merges_transforms() {
+        let set = alias_set();
+        let icon = ResolvedIcon::resolve(&set, "house-variant").unwrap();
+        assert_eq!(icon.name, "house-variant");
+        assert_eq!(icon.body, "<path/>");
+        // 3 + 2 quarter turns wrap around
+        assert_eq!(icon.rotate, 1);
+        // flipped by both the icon and the alias
+        assert!(!icon.h_flip);
+        assert!(icon.v_flip);
+        assert_eq!(icon.width, 20);
+        assert_eq!(icon.height, 24);
+        assert_eq!(icon.left, -2);
+    }
+
+    #[test]
+    fn resolve_reports_cycles_and_missing_parents() {
+        let set = alias_set();
+        assert!(matches!(
+            ResolvedIcon::resolve(&set, "loop-a"),
+            Err(ResolveError::AliasCycle(_))
+        ));
+        assert_eq!(
+            ResolvedIcon::resolve(&set, "broken"),
+            Err(ResolveError::NotFound("nowhere".into()))
+        );
+        assert!(ResolvedIcon::from_response(&set, "house").is_some());
+    }
+
+    #[test]
+    fn resolve_enforces_depth_limit() {
+        let mut set = alias_set();
+        let mut parent = "home".to_string();
+        for i in 0..=MAX_ALIAS_DEPTH {
+            let name = format!("deep-{i}");
+            set.aliases.insert(
+                name.clone(),
+                IconAlias {
+                    parent,
+                    ..Default::default()
+                },
+            );
+            parent = name;
+        }
+        assert_eq!(
+            ResolvedIcon::resolve(&set, &parent),
+            Err(ResolveError::TooDeep(parent.clone()))
+        );
+        assert!(ResolvedIcon::resolve(&set, "deep-3").is_ok());
+    }
 }
@@ -0,0 +1,184 @@
+//! Icons as Android `VectorDrawable` XML.
+//!
+//! Each flattened [`Shape`] becomes one `<path>`, with group transforms
+//! already applied to its path data. A viewBox that does not start at the
+//! origin is handled by a translating `<group>`. Icons painted only with
+//! `currentColor` are drawn white and tinted with `colorControlNormal`, the
+//! way Android Studio imports Material icons; elsewhere `currentColor` is
+//! black.
+
+use std::fmt::Write;
+
+use crate::color::Color;
+use crate::shapes::{FillRule, Flattened, LineCap, LineJoin, Shape, ShapePaint};
+
+/// Decimal places kept in path data and attributes.
+const PRECISION: u8 = 3;
+
+fn number(n: f64) -> String {
+    let scale = 10f64.powi(i32::from(PRECISION));
+    crate::svg::format_number((n * scale).round() / scale)
+}
+
+/// Whether every paint is `currentColor` or none, so the drawable can be
+/// tinted as a whole.
+#[must_use]
+pub fn tintable(flattened: &Flattened) -> bool {
+    let paints = || flattened.shapes.iter().flat_map(|s| [s.fill, s.stroke]);
+    paints().any(|paint| paint == ShapePaint::CurrentColor)
+        && paints().all(|paint| matches!(paint, ShapePaint::None | ShapePaint::CurrentColor))
+}
+
+/// An opaque colour and the alpha taken out of it.
+fn color(paint: ShapePaint, tint: bool) -> (String, f64) {
+    match paint {
+        ShapePaint::CurrentColor if tint => ("@android:color/white".to_string(), 1.0),
+        ShapePaint::None | ShapePaint::CurrentColor => ("#000".to_string(), 1.0),
+        ShapePaint::Color(color) => (
+            Color { a: 255, ..color }.to_hex(),
+            f64::from(color.a) / 255.0,
+        ),
+    }
+}
+
+fn write_shape(shape: &Shape, tint: bool, indent: &str, out: &mut String) {
+    let mut attributes = vec![("pathData", shape.path_data(Some(PRECISION)))];
+    if !shape.fill.is_none() {
+        let (fill, alpha) = color(shape.fill, tint);
+        attributes.push(("fillColor", fill));
+        let alpha = alpha * shape.fill_opacity * shape.opacity;
+        if alpha < 1.0 {
+            attributes.push(("fillAlpha", number(alpha)));
+        }
+        if shape.fill_rule == FillRule::EvenOdd {
+            attributes.push(("fillType", "evenOdd".to_string()));
+        }
+    }
+    if !shape.stroke.is_none() {
+        let (stroke, alpha) = color(shape.stroke, tint);
+        attributes.push(("strokeColor", stroke));
+        attributes.push(("strokeWidth", number(shape.stroke_width)));
+        let alpha = alpha * shape.stroke_opacity * shape.opacity;
+        if alpha < 1.0 {
+            attributes.push(("strokeAlpha", number(alpha)));
+        }
+        match shape.line_cap {
+            LineCap::Butt => {}
+            LineCap::Round => attributes.push(("strokeLineCap", "round".to_string())),
+            LineCap::Square => attributes.push(("strokeLineCap", "square".to_string())),
+        }
+        match shape.line_join {
+            LineJoin::Miter => {}
+            LineJoin::Round => attributes.push(("strokeLineJoin", "round".to_string())),
+            LineJoin::Bevel => attributes.push(("strokeLineJoin", "bevel".to_string())),
+        }
+        if (shape.miter_limit - 4.0).abs() > f64::EPSILON {
+            attributes.push(("strokeMiterLimit", number(shape.miter_limit)));
+        }
+    }
+    let _ = write!(out, "{indent}<path");
+    for (name, value) in attributes {
+        let _ = write!(out, "\n{indent}    android:{name}=\"{value}\"");
+    }
+    out.push_str(" />\n");
+}
+
+/// A `<vector>` document for flattened shapes.
+///
+/// `view_box` is `[left, top, width, height]` and `height` the drawable's
+/// height in dp; its width keeps the viewBox aspect ratio.
+#[must_use]
+pub fn vector(flattened: &Flattened, view_box: [f64; 4], height: f64) -> String {
+    let [left, top, vb_width, vb_height] = view_box;
+    let tint = tintable(flattened);
+    let mut out =
+        String::from("<vector xmlns:android=\"http://schemas.android.com/apk/res/android\"\n");
+    let _ = writeln!(
+        out,
+        "    android:width=\"{}dp\"",
+        number(height * vb_width / vb_height)
+    );
+    let _ = writeln!(out, "    android:height=\"{}dp\"", number(height));
+    let _ = writeln!(out, "    android:viewportWidth=\"{}\"", number(vb_width));
+    let _ = write!(out, "    android:viewportHeight=\"{}\"", number(vb_height));
+    if tint {
+        out.push_str("\n    android:tint=\"?attr/colorControlNormal\"");
+    }
+    out.push_str(">\n");
+
+    let translate = left != 0.0 || top != 0.0;
+    if translate {
+        let _ = writeln!(
+            out,
+            "    <group\n        android:translateX=\"{}\"\n        android:translateY=\"{}\">",
+            number(-left + 0.0),
+            number(-top + 0.0)
+        );
+    }
+    let indent = if translate { "        " } else { "    " };
+    for shape in &flattened.shapes {
+        write_shape(shape, tint, indent, &mut out);
+    }
+    if translate {
+        out.push_str("    </group>\n");
+    }
+    out.push_str("</vector>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::flatten;
+
+    #[test]
+    fn writes_tinted_vectors() {
+        let body = r#"<path fill-rule="evenodd" d="M0 0h10v10H0z"/><path fill="none" stroke="currentColor" stroke-linecap="round" stroke-linejoin="round" stroke-width="2" opacity=".5" d="M1 1 3 2"/>"#;
+        let flat = flatten(body).unwrap();
+        assert!(!tintable(&flat));
+        assert_eq!(
+            vector(&flat, [0.0, 0.0, 24.0, 12.0], 24.0),
+            r##"<vector xmlns:android="http://schemas.android.com/apk/res/android"
+    android:width="48dp"
+    android:height="24dp"
+    android:viewportWidth="24"
+    android:viewportHeight="12">
+    <path
+        android:pathData="M0 0H10V10H0Z"
+        android:fillColor="#000"
+        android:fillType="evenOdd" />
+    <path
+        android:pathData="M1 1 3 2"
+        android:strokeColor="#000"
+        android:strokeWidth="2"
+        android:strokeAlpha="0.5"
+        android:strokeLineCap="round"
+        android:strokeLineJoin="round" />
+</vector>"##
+        );
+    }
+
+    #[test]
+    fn tints_current_color_and_offsets_view_box() {
+        let flat = flatten(r#"<path fill="currentColor" d="M0 0h2v2z"/>"#).unwrap();
+        assert!(tintable(&flat));
+        let xml = vector(&flat, [-2.0, 0.0, 4.0, 4.0], 24.0);
+        assert!(
+            xml.contains("android:tint=\"?attr/colorControlNormal\">"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("android:translateX=\"2\"\n        android:translateY=\"0\">"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("android:fillColor=\"@android:color/white\""),
+            "{xml}"
+        );
+
+        let flat = flatten(r##"<path fill="#ff000080" d="M0 0h2v2z"/>"##).unwrap();
+        let xml = vector(&flat, [0.0, 0.0, 4.0, 4.0], 24.0);
+        assert!(xml.contains("android:fillColor=\"#f00\"\n"), "{xml}");
+        assert!(xml.contains("android:fillAlpha=\"0.502\""), "{xml}");
+    }
+}
@@ -0,0 +1,171 @@
+//! SVG bodies for the view macros of Leptos (`view!`), Yew (`html!`) and
+//! Sycamore (`view!`).
+//!
+//! The macros tokenize their input as Rust, so markup has to be rewritten:
+//! attribute values become Rust string literals, text becomes quoted string
+//! nodes, childless elements self-close, and comments and namespaced names
+//! (which the macros cannot parse) are dropped, except `xlink:href`, which
+//! becomes the SVG 2 `href`. Sycamore uses its own builder-like syntax,
+//! `name(attr="value") { children }`, instead of tags.
+
+use std::fmt::Write;
+
+use crate::tree::{Element, Node};
+
+/// A Rust view macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMacro {
+    /// Leptos `view!`, where text is a string literal node.
+    Leptos,
+    /// Yew `html!`, where text is a block holding a string literal.
+    Yew,
+    /// Sycamore `view!`, where elements are written as `name(attributes)`
+    /// followed by a block of children.
+    Sycamore,
+}
+
+/// A Rust string literal.
+#[must_use]
+pub fn string_literal(text: &str) -> String {
+    format!("{text:?}")
+}
+
+/// The name an attribute is written under, or `None` if it is dropped.
+fn attribute_name(name: &str) -> Option<&str> {
+    match name.split_once(':') {
+        None => Some(name),
+        Some((_, "href")) => Some("href"),
+        Some(_) => None,
+    }
+}
+
+fn write_element(el: &Element, target: ViewMacro, depth: usize, out: &mut String) {
+    let pad = "    ".repeat(depth);
+    let has_href = el.attributes.iter().any(|a| a.name == "href");
+    let attributes: Vec<String> = el
+        .attributes
+        .iter()
+        .filter_map(|attr| {
+            let name = attribute_name(&attr.name)?;
+            // A plain `href` wins over a legacy `xlink:href`
+            if name == "href" && attr.name != "href" && has_href {
+                return None;
+            }
+            Some(format!("{name}={}", string_literal(&attr.value)))
+        })
+        .collect();
+
+    let mut children = String::new();
+    write_nodes(&el.children, target, depth + 1, &mut children);
+    if target == ViewMacro::Sycamore {
+        let _ = write!(out, "{pad}{}", el.name);
+        if !attributes.is_empty() || children.is_empty() {
+            let _ = write!(out, "({})", attributes.join(", "));
+        }
+        if children.is_empty() {
+            out.push('\n');
+        } else {
+            let _ = write!(out, " {{\n{children}{pad}}}\n");
+        }
+        return;
+    }
+
+    let _ = write!(out, "{pad}<{}", el.name);
+    for attribute in &attributes {
+        let _ = write!(out, " {attribute}");
+    }
+    if children.is_empty() {
+        out.push_str("/>\n");
+    } else {
+        let _ = write!(out, ">\n{children}{pad}</{}>\n", el.name);
+    }
+}
+
+fn write_nodes(nodes: &[Node], target: ViewMacro, depth: usize, out: &mut String) {
+    let pad = "    ".repeat(depth);
+    for node in nodes {
+        match node {
+            Node::Element(el) if el.name.contains(':') => {}
+            Node::Element(el) => write_element(el, target, depth, out),
+            Node::Text(text) | Node::CData(text) if !text.trim().is_empty() => {
+                let text = string_literal(text);
+                match target {
+                    ViewMacro::Leptos | ViewMacro::Sycamore => {
+                        let _ = writeln!(out, "{pad}{text}");
+                    }
+                    ViewMacro::Yew => {
+                        let _ = writeln!(out, "{pad}{{ {text} }}");
+                    }
+                }
+            }
+            Node::Text(_) | Node::CData(_) | Node::Comment(_) => {}
+        }
+    }
+}
+
+/// Convert an SVG body to children of a view macro, one node per line at the
+/// top level.
+///
+/// Bodies that cannot be parsed produce an empty string.
+#[must_use]
+pub fn body_to_view(body: &str, target: ViewMacro) -> String {
+    let Ok(nodes) = crate::tree::parse(body) else {
+        return String::new();
+    };
+    let mut out = String::with_capacity(body.len() * 2);
+    write_nodes(&nodes, target, 0, &mut out);
+    out.truncate(out.trim_end().len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_macro_markup() {
+        let body = r##"<g fill-rule="evenodd" style="fill:red;stroke:{x}"><use xlink:href="#a" xml:space="preserve"/><text font-family="&quot;A&quot;">a {b} "c"</text><!-- note --></g><sodipodi:namedview/>"##;
+        assert_eq!(
+            body_to_view(body, ViewMacro::Leptos),
+            r##"<g fill-rule="evenodd" style="fill:red;stroke:{x}">
+    <use href="#a"/>
+    <text font-family="\"A\"">
+        "a {b} \"c\""
+    </text>
+</g>"##
+        );
+        assert_eq!(
+            body_to_view("<text>a</text>", ViewMacro::Yew),
+            "<text>\n    { \"a\" }\n</text>"
+        );
+        assert_eq!(body_to_view("<path", ViewMacro::Yew), "");
+    }
+
+    #[test]
+    fn writes_sycamore_elements() {
+        let body = r##"<g fill-rule="evenodd"><use xlink:href="#a"/><text x="1" y="2">a</text></g><defs><path/></defs>"##;
+        assert_eq!(
+            body_to_view(body, ViewMacro::Sycamore),
+            r##"g(fill-rule="evenodd") {
+    use(href="#a")
+    text(x="1", y="2") {
+        "a"
+    }
+}
+defs {
+    path()
+}"##
+        );
+    }
+
+    #[test]
+    fn keeps_one_href() {
+        assert_eq!(
+            body_to_view(
+                r##"<use xlink:href="#old" href="#new"/>"##,
+                ViewMacro::Leptos
+            ),
+            r##"<use href="#new"/>"##
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use gloo_net::http::Request;
+use rust_icons_core::sanitize;
 use rust_icons_core::types::{
     CollectionInfo, CollectionInfoRaw, CollectionResponse, IconifyResponse, ResolvedIcon,
 };
@@ -29,7 +30,7 @@ pub async fn fetch_collections() -> Result<Vec<CollectionInfo>, String> {
         .map(|(id, raw)| CollectionInfo::from_raw(id, raw))
         .collect();
 
-    collections.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    collections.sort_by_key(|c| c.name.to_lowercase());
     Ok(collections)
 }
 
@@ -76,6 +77,9 @@ pub async fn fetch_svg(prefix: &str, name: &str) -> Result<String, String> {
 }
 
 /// Fetch icon data (body, dimensions) for snippet generation.
+///
+/// The body is sanitized before it is returned, since it is injected into the
+/// page and pasted into generated code.
 pub async fn fetch_icon_data(prefix: &str, name: &str) -> Result<ResolvedIcon, String> {
     let response = Request::get(&format!("{BASE_URL}/{prefix}.json?icons={name}"))
         .send()
@@ -94,6 +98,12 @@ pub async fn fetch_icon_data(prefix: &str, name: &str) -> Result<ResolvedIcon, S
         .await
         .map_err(|e| format!("JSON error: {e}"))?;
 
-    ResolvedIcon::from_response(&data, name)
-        .ok_or_else(|| format!("Icon '{name}' not found in response"))
+    let icon =
+        ResolvedIcon::resolve(&data, name).map_err(|e| format!("Icon '{prefix}:{name}': {e}"))?;
+    let (icon, report) = sanitize::sanitize_icon(&icon)
+        .map_err(|e| format!("Icon '{prefix}:{name}' has malformed SVG: {e}"))?;
+    for removal in &report.removals {
+        web_sys::console::warn_1(&format!("Sanitized {prefix}:{name}: removed {removal}").into());
+    }
+    Ok(icon)
 }
@@ -1,25 +1,46 @@
 use leptos::prelude::*;
-use rust_icons_core::snippets::{self, SnippetType};
-use rust_icons_core::types::ResolvedIcon;
+use rust_icons_core::color::Color;
+use rust_icons_core::favicon::{self, FaviconOptions};
+use rust_icons_core::optimize::{self, OptimizeOptions};
+use rust_icons_core::raster::{self, RasterOptions};
+use rust_icons_core::snippets::{
+    self, Accessibility, SnippetCategory, SnippetOptions, SnippetRegistry, SnippetTemplate,
+    SnippetType,
+};
+use rust_icons_core::types::{CollectionInfoRaw, ResolvedIcon};
 use wasm_bindgen::JsCast;
-use wasm_bindgen_futures::spawn_local;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
 
 use crate::api;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Tab {
-    Rust,
-    Snippets,
-    Links,
-}
+/// localStorage key holding user snippet templates as `[[file, contents], …]`.
+const TEMPLATES_KEY: &str = "snippet-templates";
 
 #[component]
-pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl IntoView {
+pub fn IconDetail(
+    prefix: String,
+    name: String,
+    /// Whether the collection is a palette collection whose icons keep their colours.
+    #[prop(optional)]
+    palette: bool,
+    /// Collection metadata, used for license and author fields in templates.
+    #[prop(default = None)]
+    info: Option<CollectionInfoRaw>,
+    on_close: Callback<()>,
+) -> impl IntoView {
     let icon_id = format!("{prefix}:{name}");
+    let default_component_name = snippets::to_component_name(&icon_id);
     let (icon_data, set_icon_data) = signal(None::<ResolvedIcon>);
-    let (svg_html, set_svg_html) = signal(None::<String>);
+    let (preview_url, set_preview_url) = signal(None::<String>);
     let (copied_label, set_copied_label) = signal(None::<String>);
-    let (active_tab, set_active_tab) = signal(Tab::Rust);
+    let (active_tab, set_active_tab) = signal(SnippetCategory::Rust);
+    let (template_files, set_template_files) = signal(load_template_files());
+    let (template_error, set_template_error) = signal(None::<String>);
+    let (optimize, set_optimize) = signal(false);
+    let (snippet_options, set_snippet_options) = signal(SnippetOptions::default());
+    let (attributes_error, set_attributes_error) = signal(None::<String>);
+    let (png_color, set_png_color) = signal("#000000".to_string());
+    let (background_color, set_background_color) = signal("#ffffff".to_string());
 
     // Fetch icon data
     let prefix_clone = prefix.clone();
@@ -29,9 +50,11 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
         let n = name_clone.clone();
         spawn_local(async move {
             match api::fetch_icon_data(&p, &n).await {
-                Ok(icon) => {
+                Ok(mut icon) => {
+                    icon.palette |= palette;
+                    // An image cannot run scripts, whatever the sanitizer missed
                     let svg = rust_icons_core::svg::build_svg(&icon);
-                    set_svg_html.set(Some(svg));
+                    set_preview_url.set(Some(snippets::svg_to_data_url(&svg)));
                     set_icon_data.set(Some(icon));
                 }
                 Err(e) => {
@@ -41,14 +64,40 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
         });
     });
 
-    let copy_snippet = move |snippet_type: SnippetType, label: &'static str| {
+    let registry = Memo::new(move |_| {
+        let mut registry = SnippetRegistry::new();
+        for (file_name, contents) in template_files.get() {
+            if let Ok(template) = SnippetTemplate::from_file(&file_name, &contents) {
+                registry.register(template);
+            }
+        }
+        registry
+    });
+
+    let render = move |icon: &ResolvedIcon, template: &SnippetTemplate| {
+        let optimized = optimize.get().then(|| {
+            optimize::optimize_icon(icon, &OptimizeOptions::default()).map(|(icon, _)| icon)
+        });
+        let icon = match &optimized {
+            Some(Ok(optimized)) => optimized,
+            _ => icon,
+        };
+        let options = snippet_options.get();
+        snippets::render(template, icon, info.as_ref(), &options).unwrap_or_else(|e| {
+            web_sys::console::error_1(&format!("Failed to render {}: {e}", template.name).into());
+            String::new()
+        })
+    };
+    let render = StoredValue::new(render);
+
+    let copy_snippet = move |template: &SnippetTemplate| {
         if let Some(icon) = icon_data.get() {
-            let snippet = snippets::generate(&icon, snippet_type);
+            let snippet = render.with_value(|render| render(&icon, template));
             let window = web_sys::window().unwrap();
             let clipboard = window.navigator().clipboard();
             let _ = clipboard.write_text(&snippet);
 
-            set_copied_label.set(Some(label.to_string()));
+            set_copied_label.set(Some(template.id.clone()));
             spawn_local(async move {
                 gloo_timers::future::TimeoutFuture::new(2_000).await;
                 set_copied_label.set(None);
@@ -56,28 +105,103 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
         }
     };
 
-    let download = move |snippet_type: SnippetType, ext: &'static str| {
+    let download = move |snippet_type: SnippetType| {
         if let Some(icon) = icon_data.get() {
-            let content = snippets::generate(&icon, snippet_type);
-            let component_name =
-                snippets::to_component_name(&format!("{}:{}", icon.prefix, icon.name));
-            let filename = format!("{component_name}.{ext}");
-
-            let bag = web_sys::BlobPropertyBag::new();
-            bag.set_type("text/plain");
-            let blob = web_sys::Blob::new_with_str_sequence_and_options(
-                &js_sys::Array::of1(&content.into()),
-                &bag,
-            )
-            .unwrap();
+            let template = SnippetTemplate::from(snippet_type);
+            let content = render.with_value(|render| render(&icon, &template));
+            let options = snippet_options.get();
+            let component_name = options.component_name.clone().unwrap_or_else(|| {
+                snippets::to_component_name(&format!("{}:{}", icon.prefix, icon.name))
+            });
+            let filename = format!("{component_name}.{}", template.file_extension(&options));
+            save_file(&content.into(), "text/plain", &filename);
+        }
+    };
 
-            let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
-            let document = web_sys::window().unwrap().document().unwrap();
-            let a = document.create_element("a").unwrap();
-            a.set_attribute("href", &url).unwrap();
-            a.set_attribute("download", &filename).unwrap();
-            a.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
-            web_sys::Url::revoke_object_url(&url).unwrap();
+    let add_template = move |ev: web_sys::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+        spawn_local(async move {
+            let Ok(text) = JsFuture::from(file.text()).await else {
+                return;
+            };
+            let file_name = file.name();
+            let contents = text.as_string().unwrap_or_default();
+            match SnippetTemplate::from_file(&file_name, &contents) {
+                Ok(template) => {
+                    set_template_error.set(None);
+                    set_active_tab.set(template.category);
+                    set_template_files.update(|files| {
+                        files.retain(|(name, _)| name != &file_name);
+                        files.push((file_name, contents));
+                        save_template_files(files);
+                    });
+                }
+                Err(e) => set_template_error.set(Some(format!("{file_name}: {e}"))),
+            }
+        });
+    };
+
+    let set_attributes = move |ev: web_sys::Event| {
+        let text = event_target_value(&ev);
+        match parse_attributes(&text) {
+            Some(attributes) => {
+                set_attributes_error.set(None);
+                set_snippet_options.update(|o| o.attributes = attributes);
+            }
+            None => set_attributes_error.set(Some(format!("Invalid attributes: {text}"))),
+        }
+    };
+
+    let clear_templates = move |_| {
+        set_template_files.update(|files| {
+            files.clear();
+            save_template_files(files);
+        });
+        set_template_error.set(None);
+    };
+
+    let download_png = move |size: u32| {
+        if let Some(icon) = icon_data.get() {
+            let options = RasterOptions {
+                size,
+                foreground: Color::parse(&png_color.get()).unwrap_or(Color::BLACK),
+                ..Default::default()
+            };
+            match raster::render_png(&icon, &options) {
+                Ok(png) => {
+                    let filename = format!("{}-{}-{size}.png", icon.prefix, icon.name);
+                    let bytes = js_sys::Uint8Array::from(png.as_slice());
+                    save_file(&bytes.into(), "image/png", &filename);
+                }
+                Err(e) => {
+                    web_sys::console::error_1(&format!("Failed to render PNG: {e}").into());
+                }
+            }
+        }
+    };
+
+    let download_favicons = move || {
+        if let Some(icon) = icon_data.get() {
+            let options = FaviconOptions {
+                foreground: Color::parse(&png_color.get()).unwrap_or(Color::BLACK),
+                background: Color::parse(&background_color.get()).unwrap_or(Color::WHITE),
+                name: icon.name.clone(),
+                ..Default::default()
+            };
+            match favicon::generate(&icon, &options) {
+                Ok(bundle) => {
+                    let filename = format!("{}-{}-favicons.zip", icon.prefix, icon.name);
+                    let bytes = js_sys::Uint8Array::from(bundle.to_zip().as_slice());
+                    save_file(&bytes.into(), "application/zip", &filename);
+                }
+                Err(e) => {
+                    web_sys::console::error_1(&format!("Failed to build favicons: {e}").into());
+                }
+            }
         }
     };
 
@@ -89,9 +213,12 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
             // ── Large Preview (Left) ─────────────────────────
             <div class="drawer-preview">
                 <div class="drawer-preview-label">"PREVIEW"</div>
-                <div class="drawer-preview-svg" inner_html=move || {
-                    svg_html.get().unwrap_or_else(|| "Loading...".to_string())
-                } />
+                <div class="drawer-preview-svg">
+                    {move || match preview_url.get() {
+                        Some(url) => view! { <img src=url alt="" /> }.into_any(),
+                        None => "Loading...".into_any(),
+                    }}
+                </div>
             </div>
 
             // ── Details (Right) ──────────────────────────────
@@ -105,79 +232,252 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
 
                 // ── Tab Bar ──────────────────────────────────
                 <div class="drawer-tabs">
-                    <button
-                        class=move || if active_tab.get() == Tab::Rust { "drawer-tab active" } else { "drawer-tab" }
-                        on:click=move |_| set_active_tab.set(Tab::Rust)
-                    >"Rust"</button>
-                    <button
-                        class=move || if active_tab.get() == Tab::Snippets { "drawer-tab active" } else { "drawer-tab" }
-                        on:click=move |_| set_active_tab.set(Tab::Snippets)
-                    >"Snippets"</button>
-                    <button
-                        class=move || if active_tab.get() == Tab::Links { "drawer-tab active" } else { "drawer-tab" }
-                        on:click=move |_| set_active_tab.set(Tab::Links)
-                    >"Links"</button>
+                    {SnippetCategory::all()
+                        .iter()
+                        .map(|&category| view! {
+                            <button
+                                class=move || if active_tab.get() == category { "drawer-tab active" } else { "drawer-tab" }
+                                on:click=move |_| set_active_tab.set(category)
+                            >{category.name()}</button>
+                        })
+                        .collect_view()}
                 </div>
 
                 // ── Tab Content (sub-options) ────────────────
                 <div class="drawer-tab-content">
-                    {move || match active_tab.get() {
-                        Tab::Rust => view! {
-                            <div class="drawer-pills">
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Leptos, "Leptos")>
-                                    {move || if copied_label.get().as_deref() == Some("Leptos") { "Copied!" } else { "Leptos" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Yew, "Yew")>
-                                    {move || if copied_label.get().as_deref() == Some("Yew") { "Copied!" } else { "Yew" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Dioxus, "Dioxus")>
-                                    {move || if copied_label.get().as_deref() == Some("Dioxus") { "Copied!" } else { "Dioxus" }}
-                                </button>
-                            </div>
-                        }.into_any(),
-                        Tab::Snippets => view! {
-                            <div class="drawer-pills">
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Svg, "SVG")>
-                                    {move || if copied_label.get().as_deref() == Some("SVG") { "Copied!" } else { "SVG" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::SvgSymbol, "SVG Symbol")>
-                                    {move || if copied_label.get().as_deref() == Some("SVG Symbol") { "Copied!" } else { "SVG Symbol" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Iconify, "Iconify")>
-                                    {move || if copied_label.get().as_deref() == Some("Iconify") { "Copied!" } else { "Iconify" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Jsx, "JSX")>
-                                    {move || if copied_label.get().as_deref() == Some("JSX") { "Copied!" } else { "JSX" }}
-                                </button>
-                            </div>
-                        }.into_any(),
-                        Tab::Links => view! {
-                            <div class="drawer-pills">
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Url, "URL")>
-                                    {move || if copied_label.get().as_deref() == Some("URL") { "Copied!" } else { "URL" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::DataUrl, "Data URL")>
-                                    {move || if copied_label.get().as_deref() == Some("Data URL") { "Copied!" } else { "Data URL" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::Base64, "Base64")>
-                                    {move || if copied_label.get().as_deref() == Some("Base64") { "Copied!" } else { "Base64" }}
-                                </button>
-                                <button class="drawer-pill" on:click=move |_| copy_snippet(SnippetType::CssBackground, "CSS")>
-                                    {move || if copied_label.get().as_deref() == Some("CSS") { "Copied!" } else { "CSS" }}
-                                </button>
-                            </div>
-                        }.into_any(),
-                    }}
+                    <div class="drawer-pills">
+                        {move || registry
+                            .get()
+                            .by_category(active_tab.get())
+                            .into_iter()
+                            .map(|template| {
+                                let id = template.id.clone();
+                                let label = match &template.tag {
+                                    Some(tag) => format!("{} {tag}", template.name),
+                                    None => template.name.clone(),
+                                };
+                                view! {
+                                    <button class="drawer-pill" on:click=move |_| copy_snippet(&template)>
+                                        {move || if copied_label.get().as_deref() == Some(id.as_str()) { "Copied!".to_string() } else { label.clone() }}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+
+                // ── Options (persistent) ─────────────────────
+                <div class="drawer-persistent-section">
+                    <label class="drawer-option">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || optimize.get()
+                            on:change=move |ev| set_optimize.set(event_target_checked(&ev))
+                        />
+                        "Optimize SVG"
+                    </label>
+                    <label class="drawer-option">
+                        <input
+                            type="color"
+                            prop:value=move || png_color.get()
+                            on:input=move |ev| set_png_color.set(event_target_value(&ev))
+                        />
+                        "Image colour"
+                    </label>
+                    <label class="drawer-option">
+                        <input
+                            type="color"
+                            prop:value=move || background_color.get()
+                            on:input=move |ev| set_background_color.set(event_target_value(&ev))
+                        />
+                        "Favicon background"
+                    </label>
+                </div>
+
+                // ── Snippet options (persistent) ─────────────
+                <div class="drawer-persistent-section">
+                    <h4 class="drawer-section-title">"Snippet options"</h4>
+                    <div class="drawer-options">
+                        <label class="drawer-option">
+                            "Size"
+                            <input
+                                type="number"
+                                min="1"
+                                prop:value=move || snippet_options.get().size.to_string()
+                                on:change=move |ev| {
+                                    if let Ok(size) = event_target_value(&ev).parse::<u32>() {
+                                        set_snippet_options.update(|o| o.size = size.max(1));
+                                    }
+                                }
+                            />
+                        </label>
+                        <label class="drawer-option">
+                            "Language"
+                            <select on:change=move |ev| {
+                                let typescript = match event_target_value(&ev).as_str() {
+                                    "ts" => Some(true),
+                                    "js" => Some(false),
+                                    _ => None,
+                                };
+                                set_snippet_options.update(|o| o.typescript = typescript);
+                            }>
+                                <option value="">"Default"</option>
+                                <option value="ts">"TypeScript"</option>
+                                <option value="js">"JavaScript"</option>
+                            </select>
+                        </label>
+                        <label class="drawer-option">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || snippet_options.get().separate_dimensions
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    set_snippet_options.update(|o| o.separate_dimensions = checked);
+                                }
+                            />
+                            "Width and height props"
+                        </label>
+                        <label class="drawer-option">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || snippet_options.get().color_prop
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    set_snippet_options.update(|o| o.color_prop = checked);
+                                }
+                            />
+                            "Colour prop"
+                        </label>
+                        <label class="drawer-option">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || snippet_options.get().stroke_width_prop
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    set_snippet_options.update(|o| o.stroke_width_prop = checked);
+                                }
+                            />
+                            "Stroke width prop"
+                        </label>
+                        <label class="drawer-option">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || snippet_options.get().size_parameter
+                                on:change=move |ev| {
+                                    let checked = event_target_checked(&ev);
+                                    set_snippet_options.update(|o| o.size_parameter = checked);
+                                }
+                            />
+                            "Size parameter for paths"
+                        </label>
+                        <label class="drawer-option">
+                            "Component name"
+                            <input
+                                type="text"
+                                placeholder=default_component_name
+                                on:change=move |ev| {
+                                    let value = event_target_value(&ev).trim().to_string();
+                                    set_snippet_options
+                                        .update(|o| o.component_name = (!value.is_empty()).then_some(value));
+                                }
+                            />
+                        </label>
+                        <label class="drawer-option">
+                            "Attributes"
+                            <input type="text" placeholder="aria-hidden=\"true\"" on:change=set_attributes />
+                        </label>
+                        <label class="drawer-option">
+                            "Accessibility"
+                            <select on:change=move |ev| {
+                                let accessibility = match event_target_value(&ev).as_str() {
+                                    "decorative" => Accessibility::Decorative,
+                                    "meaningful" => Accessibility::Meaningful,
+                                    _ => Accessibility::Unspecified,
+                                };
+                                set_snippet_options.update(|o| o.accessibility = accessibility);
+                            }>
+                                <option value="">"None"</option>
+                                <option value="decorative">"Decorative"</option>
+                                <option value="meaningful">"Meaningful"</option>
+                            </select>
+                        </label>
+                        <Show when=move || snippet_options.get().accessibility == Accessibility::Meaningful>
+                            <label class="drawer-option">
+                                "Title"
+                                <input
+                                    type="text"
+                                    on:change=move |ev| {
+                                        let value = event_target_value(&ev).trim().to_string();
+                                        set_snippet_options
+                                            .update(|o| o.title = (!value.is_empty()).then_some(value));
+                                    }
+                                />
+                            </label>
+                            <label class="drawer-option">
+                                "Description"
+                                <input
+                                    type="text"
+                                    on:change=move |ev| {
+                                        let value = event_target_value(&ev).trim().to_string();
+                                        set_snippet_options
+                                            .update(|o| o.description = (!value.is_empty()).then_some(value));
+                                    }
+                                />
+                            </label>
+                            <label class="drawer-option">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || snippet_options.get().title_prop
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        set_snippet_options.update(|o| o.title_prop = checked);
+                                    }
+                                />
+                                "Title prop"
+                            </label>
+                        </Show>
+                    </div>
+                    {move || attributes_error.get().map(|e| view! { <p class="drawer-error">{e}</p> })}
+                </div>
+
+                // ── Templates (persistent) ───────────────────
+                <div class="drawer-persistent-section">
+                    <h4 class="drawer-section-title">"Templates"</h4>
+                    <div class="drawer-pills">
+                        <label class="drawer-pill">
+                            "Add template"
+                            <input type="file" accept=".tpl,.tmpl,.txt" hidden on:change=add_template />
+                        </label>
+                        <Show when=move || !template_files.get().is_empty()>
+                            <button class="drawer-pill" on:click=clear_templates>"Remove templates"</button>
+                        </Show>
+                    </div>
+                    {move || template_error.get().map(|e| view! { <p class="drawer-error">{e}</p> })}
                 </div>
 
                 // ── Download (persistent) ────────────────────
                 <div class="drawer-persistent-section">
                     <h4 class="drawer-section-title">"Download"</h4>
                     <div class="drawer-pills">
-                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Svg, "svg")>"SVG"</button>
-                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Leptos, "rs")>"Leptos"</button>
-                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Yew, "rs")>"Yew"</button>
-                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Dioxus, "rs")>"Dioxus"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Svg)>"SVG"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Leptos)>"Leptos"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Yew)>"Yew"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Dioxus)>"Dioxus"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::Slint)>"Slint"</button>
+                        <button class="drawer-pill" on:click=move |_| download(SnippetType::VectorDrawable)>"Android"</button>
+                    </div>
+                    <div class="drawer-pills">
+                        {raster::PNG_SIZES
+                            .iter()
+                            .map(|&size| view! {
+                                <button class="drawer-pill" on:click=move |_| download_png(size)>
+                                    {format!("PNG {size}px")}
+                                </button>
+                            })
+                            .collect_view()}
+                    </div>
+                    <div class="drawer-pills">
+                        <button class="drawer-pill" on:click=move |_| download_favicons()>"Favicon bundle"</button>
                     </div>
                 </div>
 
@@ -201,3 +501,70 @@ pub fn IconDetail(prefix: String, name: String, on_close: Callback<()>) -> impl
         </div>
     }
 }
+
+/// Parse attributes written as in markup, e.g. `role="img" aria-hidden="true"`.
+fn parse_attributes(text: &str) -> Option<Vec<(String, String)>> {
+    let nodes = rust_icons_core::tree::parse(&format!("<svg {text}/>")).ok()?;
+    match nodes.as_slice() {
+        [rust_icons_core::tree::Node::Element(el)] => Some(
+            el.attributes
+                .iter()
+                .map(|a| (a.name.clone(), a.value.clone()))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// User templates saved in localStorage, as `(file name, contents)`.
+fn load_template_files() -> Vec<(String, String)> {
+    let Some(json) = web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(TEMPLATES_KEY).ok().flatten())
+    else {
+        return Vec::new();
+    };
+    let Ok(entries) = js_sys::JSON::parse(&json) else {
+        return Vec::new();
+    };
+    js_sys::Array::from(&entries)
+        .iter()
+        .filter_map(|entry| {
+            let entry = js_sys::Array::from(&entry);
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+}
+
+fn save_template_files(files: &[(String, String)]) {
+    let entries: js_sys::Array = files
+        .iter()
+        .map(|(name, contents)| js_sys::Array::of2(&name.into(), &contents.into()))
+        .collect();
+    let json = js_sys::JSON::stringify(&entries)
+        .ok()
+        .and_then(|json| json.as_string());
+    if let (Some(json), Some(storage)) = (
+        json,
+        web_sys::window().and_then(|w| w.local_storage().ok().flatten()),
+    ) {
+        let _ = storage.set_item(TEMPLATES_KEY, &json);
+    }
+}
+
+/// Offer `content` to the user as a file download.
+fn save_file(content: &wasm_bindgen::JsValue, mime: &str, filename: &str) {
+    let bag = web_sys::BlobPropertyBag::new();
+    bag.set_type(mime);
+    let blob =
+        web_sys::Blob::new_with_u8_array_sequence_and_options(&js_sys::Array::of1(content), &bag)
+            .unwrap();
+
+    let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+    let document = web_sys::window().unwrap().document().unwrap();
+    let a = document.create_element("a").unwrap();
+    a.set_attribute("href", &url).unwrap();
+    a.set_attribute("download", filename).unwrap();
+    a.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+    web_sys::Url::revoke_object_url(&url).unwrap();
+}
@@ -208,6 +208,9 @@ pub fn CollectionPage() -> impl IntoView {
                                 let license_spdx = resp.info.as_ref()
                                     .and_then(|i| i.license.as_ref())
                                     .map(|l| l.spdx.clone().unwrap_or_else(|| l.title.clone()));
+                                let palette = resp.info.as_ref()
+                                    .and_then(|i| i.palette)
+                                    .unwrap_or(false);
 
                                 let meta_parts: Vec<String> = [
                                     author_name,
@@ -283,6 +286,7 @@ pub fn CollectionPage() -> impl IntoView {
                                 });
 
                                 let prefix_for_drawer = prefix_cloned.clone();
+                                let info_for_drawer = resp.info.clone();
 
                                 view! {
                                     <header class="collection-detail-header">
@@ -415,6 +419,7 @@ pub fn CollectionPage() -> impl IntoView {
                                         let is_open = selected_icon.get().is_some();
                                         let current_icon = selected_icon.get().unwrap_or_default();
                                         let p = prefix_for_drawer.clone();
+                                        let info = info_for_drawer.clone();
 
                                         view! {
                                             <div
@@ -428,6 +433,8 @@ pub fn CollectionPage() -> impl IntoView {
                                                     <IconDetail
                                                         prefix=p.clone()
                                                         name=current_icon.clone()
+                                                        palette=palette
+                                                        info=info.clone()
                                                         on_close=Callback::new(move |()| set_selected_icon.set(None))
                                                     />
                                                 </Show>